@@ -7,5 +7,24 @@ fn main() {
         println!("cargo:rustc-link-arg=/ENTRY:mainCRTStartup");
     }
     
+    // Shown by get_app_info
+    let build_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
+    println!("cargo:rustc-env=SACN_VERSION={}", locked_version("sacn").unwrap_or_else(|| "unknown".to_string()));
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    
     tauri_build::build()
 }
+
+/// Version of a dependency as resolved in Cargo.lock
+fn locked_version(package: &str) -> Option<String> {
+    let lock = std::fs::read_to_string("Cargo.lock").ok()?;
+    let name_line = format!("name = \"{}\"", package);
+    let mut lines = lock.lines();
+    lines.find(|line| *line == name_line)?;
+    let version = lines.next()?.strip_prefix("version = \"")?.strip_suffix('"')?;
+    Some(version.to_string())
+}
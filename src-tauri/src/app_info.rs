@@ -0,0 +1,38 @@
+use serde::Serialize;
+
+/// What's running, for the About panel and support requests
+#[derive(Debug, Clone, Serialize)]
+pub struct AppInfo {
+    pub version: String,
+    /// Unix time (seconds) the build script last ran
+    pub build_timestamp: u64,
+    /// e.g. "windows x86_64"
+    pub os: String,
+    /// First line of `ffmpeg -version`, None if FFmpeg wasn't found
+    pub ffmpeg_version: Option<String>,
+    pub sacn_version: String,
+}
+
+pub fn collect() -> AppInfo {
+    AppInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        build_timestamp: env!("BUILD_TIMESTAMP").parse().unwrap_or(0),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        ffmpeg_version: crate::find_ffmpeg().and_then(|ffmpeg| ffmpeg_version(&ffmpeg)),
+        sacn_version: env!("SACN_VERSION").to_string(),
+    }
+}
+
+/// "ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright ..." → "6.1.1-full_build-www.gyan.dev"
+fn ffmpeg_version(ffmpeg: &str) -> Option<String> {
+    let output = std::process::Command::new(ffmpeg)
+        .arg("-version")
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next()?;
+    let version = first_line.strip_prefix("ffmpeg version ")?
+        .split_whitespace()
+        .next()?;
+    Some(version.to_string())
+}
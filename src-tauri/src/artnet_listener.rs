@@ -0,0 +1,216 @@
+use crate::config::{DmxUpdate, SacnConfig};
+use crate::sacn_listener::LevelCache;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const ARTNET_PORT: u16 = 6454;
+
+const ARTNET_ID: &[u8; 8] = b"Art-Net\0";
+const OP_POLL: u16 = 0x2000;
+const OP_POLL_REPLY: u16 = 0x2100;
+const OP_DMX: u16 = 0x5000;
+
+/// ESTA code set aside for prototypes and in-house equipment
+const ESTA_PROTOTYPE: u16 = 0x7ff0;
+
+/// Levels from an ArtDMX packet
+struct ArtDmx<'a> {
+    /// 15-bit Port-Address: net (7 bits), sub-net (4 bits), universe (4 bits)
+    port_address: u16,
+    values: &'a [u8],
+}
+
+/// OpCode of an Art-Net packet; it is the one little-endian field in the header
+fn opcode(packet: &[u8]) -> Option<u16> {
+    if packet.len() < 10 || &packet[..8] != ARTNET_ID {
+        return None;
+    }
+    Some(u16::from_le_bytes([packet[8], packet[9]]))
+}
+
+fn parse_art_dmx(packet: &[u8]) -> Option<ArtDmx<'_>> {
+    if packet.len() < 18 || opcode(packet)? != OP_DMX {
+        return None;
+    }
+    let port_address = u16::from_le_bytes([packet[14], packet[15]]) & 0x7fff;
+    let length = (u16::from_be_bytes([packet[16], packet[17]]) as usize).min(512);
+    let values = packet.get(18..18 + length)?;
+    Some(ArtDmx { port_address, values })
+}
+
+/// ArtPollReply describing the player as a node with one output port bound
+/// to `port_address`, so desks and network scanners list it
+fn art_poll_reply(ip: Ipv4Addr, port_address: u16, receiving: bool, polls: u32) -> [u8; 239] {
+    let mut reply = [0u8; 239];
+    reply[..8].copy_from_slice(ARTNET_ID);
+    reply[8..10].copy_from_slice(&OP_POLL_REPLY.to_le_bytes());
+    reply[10..14].copy_from_slice(&ip.octets());
+    reply[14..16].copy_from_slice(&ARTNET_PORT.to_le_bytes());
+    reply[16..18].copy_from_slice(&[0, 1]); // firmware version
+    reply[18] = ((port_address >> 8) & 0x7f) as u8; // NetSwitch
+    reply[19] = ((port_address >> 4) & 0x0f) as u8; // SubSwitch
+    reply[20..22].copy_from_slice(&[0x00, 0xff]); // OEM unknown
+    reply[23] = 0xd0; // indicators normal, Port-Address set in the app
+    reply[24..26].copy_from_slice(&ESTA_PROTOTYPE.to_le_bytes());
+    write_name(&mut reply[26..44], "MediaPlayer");
+    write_name(&mut reply[44..108], &format!("StagePlayer DMX {}", env!("CARGO_PKG_VERSION")));
+    write_name(&mut reply[108..172], &format!("#0001 [{:04}] Receiving Port-Address {}", polls % 10000, port_address));
+    reply[173] = 1; // NumPorts
+    reply[174] = 0x80; // port 1 outputs DMX512 from Art-Net
+    if receiving {
+        reply[182] = 0x80; // GoodOutputA: data is being output
+    }
+    reply[190] = (port_address & 0x0f) as u8; // SwOut
+    reply[207..211].copy_from_slice(&ip.octets()); // BindIp
+    reply[211] = 1; // BindIndex
+    reply[212] = 0x08; // Status2: 15-bit Port-Address
+    reply
+}
+
+/// Null-terminated ASCII in a fixed-size field
+fn write_name(field: &mut [u8], name: &str) {
+    let len = name.len().min(field.len() - 1);
+    field[..len].copy_from_slice(&name.as_bytes()[..len]);
+}
+
+/// Our address as seen from `peer`: the configured one, or whichever local
+/// address the OS routes to the poller from
+fn local_ip_for(configured: &str, peer: IpAddr) -> Ipv4Addr {
+    if let Ok(ip) = configured.parse::<Ipv4Addr>() {
+        if !ip.is_unspecified() {
+            return ip;
+        }
+    }
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((peer, ARTNET_PORT))?;
+            socket.local_addr()
+        })
+        .ok()
+        .and_then(|addr| match addr.ip() {
+            IpAddr::V4(ip) => Some(ip),
+            IpAddr::V6(_) => None,
+        })
+        .unwrap_or(Ipv4Addr::UNSPECIFIED)
+}
+
+/// Receives ArtDMX for one Port-Address and feeds the same `DmxUpdate`
+/// pipeline as sACN, reported under the configured sACN universe so the rest
+/// of the app doesn't care which protocol the console speaks.
+pub struct ArtNetListener {
+    config: SacnConfig,
+    running: Arc<Mutex<bool>>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ArtNetListener {
+    pub fn new(config: SacnConfig) -> Self {
+        Self {
+            config,
+            running: Arc::new(Mutex::new(false)),
+            thread_handle: None,
+        }
+    }
+
+    pub fn start(&mut self, callback: impl Fn(DmxUpdate) + Send + 'static) -> Result<(), String> {
+        if *self.running.lock().unwrap() {
+            return Err("Art-Net listener already running".to_string());
+        }
+
+        let universe = self.config.universe;
+        let port_address = self.config.artnet_universe;
+        let ip_address = self.config.ip_address.clone();
+        println!("=== Art-Net Listener Starting ===");
+        println!("Port-Address: {} (net {}, sub-net {}, universe {})",
+            port_address, port_address >> 8, (port_address >> 4) & 0x0f, port_address & 0x0f);
+
+        // Consoles broadcast or unicast ArtDMX, so listen on every interface
+        let socket = bind_socket()
+            .map_err(|e| format!("Failed to bind Art-Net port {}: {}. Is another application using it?", ARTNET_PORT, e))?;
+
+        let running = Arc::clone(&self.running);
+        *running.lock().unwrap() = true;
+
+        let handle = std::thread::spawn(move || {
+            let mut buf = [0u8; 1024];
+            let mut polls: u32 = 0;
+            let mut last_dmx: Option<std::time::Instant> = None;
+            let mut levels_sent = LevelCache::default();
+
+            while *running.lock().unwrap() {
+                // Times out every 100 ms so the stop flag is noticed
+                let (len, from) = match socket.recv_from(&mut buf) {
+                    Ok(received) => received,
+                    Err(e) => {
+                        if e.kind() != std::io::ErrorKind::WouldBlock &&
+                           e.kind() != std::io::ErrorKind::TimedOut {
+                            eprintln!("Art-Net IO error: {}", e);
+                        }
+                        continue;
+                    }
+                };
+                // Controllers poll to build their node lists
+                if opcode(&buf[..len]) == Some(OP_POLL) {
+                    polls = polls.wrapping_add(1);
+                    let receiving = last_dmx.is_some_and(|t| t.elapsed() < Duration::from_secs(3));
+                    let reply = art_poll_reply(local_ip_for(&ip_address, from.ip()), port_address, receiving, polls);
+                    if let Err(e) = socket.send_to(&reply, (from.ip(), ARTNET_PORT)) {
+                        eprintln!("Failed to send ArtPollReply to {}: {}", from.ip(), e);
+                    }
+                    continue;
+                }
+                let Some(dmx) = parse_art_dmx(&buf[..len]) else {
+                    continue;
+                };
+                if dmx.port_address != port_address {
+                    continue;
+                }
+                last_dmx = Some(std::time::Instant::now());
+
+                // ArtDMX has no start code, so channel N is at index N - 1
+                let mut levels = [0u8; 512];
+                for (i, level) in levels.iter_mut().enumerate() {
+                    *level = dmx.values.get(i).copied().unwrap_or(0);
+                }
+                callback(DmxUpdate { universe, channels: levels_sent.changes(levels) });
+            }
+            println!("Art-Net listener stopped");
+        });
+
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    /// Stop and wait until the port is released
+    pub fn stop(&mut self) {
+        *self.running.lock().unwrap() = false;
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    pub fn signal_stop(&mut self) {
+        *self.running.lock().unwrap() = false;
+    }
+}
+
+impl Drop for ArtNetListener {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Port 6454 on every interface, shared with other Art-Net software
+fn bind_socket() -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.set_broadcast(true)?;
+    socket.bind(&SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ARTNET_PORT).into())?;
+    let socket: UdpSocket = socket.into();
+    socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+    Ok(socket)
+}
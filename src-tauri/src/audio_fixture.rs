@@ -0,0 +1,163 @@
+use crate::config::{AppConfig, AudioFixtureConfig};
+use crate::media_scanner;
+use crate::sacn_listener::DmxFrame;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Names of the audio output devices the fixture can play to
+pub fn list_output_devices() -> Result<Vec<String>, String> {
+    let host = rodio::cpal::default_host();
+    let devices = host.output_devices()
+        .map_err(|e| format!("Failed to list audio devices: {}", e))?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+/// Spawn the audio fixture.
+///
+/// While `audio_fixture.enabled` is set and a listener is running, the
+/// fixture follows its three channels like a monitor does: clip selects a
+/// sound file from its media folder (0 = silence), volume scales the output
+/// and playtype picks loop (0-127) or play once (128-255) when a clip starts.
+/// A play-once clip fires again when the clip channel changes.
+pub fn spawn_audio_fixture(
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        // OutputStream is not Send, so the device is opened on this thread
+        let mut output: Option<(OutputStream, OutputStreamHandle)> = None;
+        let mut opened_device: Option<String> = None;
+        let mut sink: Option<Sink> = None;
+        let mut current_clip = 0u8;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(20));
+
+            let fixture = config.lock().unwrap().audio_fixture.clone();
+
+            if !fixture.enabled {
+                if opened_device.take().is_some() {
+                    sink = None;
+                    output = None;
+                    current_clip = 0;
+                    println!("Audio fixture stopped");
+                }
+                continue;
+            }
+
+            // (Re)open the device when enabled or when the selection changes.
+            // A failed open is only retried after the setting changes.
+            if opened_device.as_deref() != Some(fixture.output_device.as_str()) {
+                sink = None;
+                current_clip = 0;
+                opened_device = Some(fixture.output_device.clone());
+                output = match open_output(&fixture.output_device) {
+                    Ok(stream) => {
+                        println!("Audio fixture started on {}", device_label(&fixture.output_device));
+                        Some(stream)
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        None
+                    }
+                };
+            }
+            let Some((_, handle)) = output.as_ref() else {
+                continue;
+            };
+
+            let (clip, volume, playtype) = {
+                let frame = dmx_frame.lock().unwrap();
+                // Listener stopped: production is over, go silent
+                if frame.listening_since.is_none() {
+                    (0, 0, 0)
+                } else {
+                    let channel = |ch: u16| frame.values.get(ch as usize).copied().unwrap_or(0);
+                    (
+                        channel(fixture.clip_channel()),
+                        channel(fixture.volume_channel()),
+                        channel(fixture.playtype_channel()),
+                    )
+                }
+            };
+
+            if clip != current_clip {
+                // Dropping the sink stops the previous sound
+                sink = None;
+                current_clip = clip;
+                if clip != 0 {
+                    sink = start_clip(handle, &fixture, clip, playtype);
+                }
+            }
+
+            if let Some(s) = sink.as_ref() {
+                s.set_volume(volume as f32 / 255.0);
+            }
+        }
+    });
+}
+
+fn device_label(device_name: &str) -> &str {
+    if device_name.is_empty() { "default audio device" } else { device_name }
+}
+
+fn open_output(device_name: &str) -> Result<(OutputStream, OutputStreamHandle), String> {
+    if device_name.is_empty() {
+        return OutputStream::try_default()
+            .map_err(|e| format!("Failed to open default audio device: {}", e));
+    }
+
+    let host = rodio::cpal::default_host();
+    let device = host.output_devices()
+        .map_err(|e| format!("Failed to list audio devices: {}", e))?
+        .find(|device| device.name().map(|name| name == device_name).unwrap_or(false))
+        .ok_or_else(|| format!("Audio device '{}' not found", device_name))?;
+
+    OutputStream::try_from_device(&device)
+        .map_err(|e| format!("Failed to open audio device '{}': {}", device_name, e))
+}
+
+fn start_clip(handle: &OutputStreamHandle, fixture: &AudioFixtureConfig, clip: u8, playtype: u8) -> Option<Sink> {
+    let media = match media_scanner::scan_audio_folder(&fixture.media_folder).ok()?.remove(&clip) {
+        Some(media) => media,
+        None => {
+            eprintln!("Audio clip {} not found in {:?}", clip, fixture.media_folder);
+            return None;
+        }
+    };
+
+    let file = match File::open(&media.path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Failed to open {:?}: {}", media.path, e);
+            return None;
+        }
+    };
+    let decoder = match Decoder::new(BufReader::new(file)) {
+        Ok(decoder) => decoder,
+        Err(e) => {
+            eprintln!("Failed to decode {}: {}", media.filename, e);
+            return None;
+        }
+    };
+    let sink = match Sink::try_new(handle) {
+        Ok(sink) => sink,
+        Err(e) => {
+            eprintln!("Failed to start audio playback: {}", e);
+            return None;
+        }
+    };
+
+    // Same convention as the output windows: 0-127 = loop, 128-255 = once
+    if playtype < 128 {
+        sink.append(decoder.buffered().repeat_infinite());
+    } else {
+        sink.append(decoder);
+    }
+    println!("Audio fixture playing {}", media.filename);
+    Some(sink)
+}
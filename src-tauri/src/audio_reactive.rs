@@ -0,0 +1,190 @@
+use crate::config::{AppConfig, AudioReactiveConfig, AudioReactiveMode, DmxUpdate};
+use crate::playback_state::PlaybackStates;
+use crate::sacn_listener::DmxFrame;
+use rodio::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rodio::cpal::{self, SampleFormat, SizedSample, Stream};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Analysis window; also how often channel values are updated
+const WINDOW: Duration = Duration::from_millis(20);
+
+/// About a second of window energies, the baseline a beat must stand out from
+const BEAT_HISTORY: usize = 50;
+
+/// Shortest gap between two beats (200 BPM)
+const MIN_BEAT_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Names of the audio input devices that can be analysed
+pub fn list_input_devices() -> Result<Vec<String>, String> {
+    let host = cpal::default_host();
+    let devices = host.input_devices()
+        .map_err(|e| format!("Failed to list audio inputs: {}", e))?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+/// Sum of squares and sample count since the last analysis window
+type Energy = Arc<Mutex<(f32, usize)>>;
+
+/// Spawn the audio-reactive driver.
+///
+/// While `audio_reactive.enabled` is set, the input device is analysed and
+/// the target monitor's channels are written as if a console had sent them:
+/// the input level sets the dimmer, or each beat moves the clip channel on
+/// through a range. The values go through the same pipeline as sACN, so
+/// the outputs play them like any other DMX.
+pub fn spawn_audio_reactive(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+    playback_states: Arc<Mutex<PlaybackStates>>,
+) {
+    std::thread::spawn(move || {
+        // cpal streams are not Send, so the input is opened on this thread
+        let mut input: Option<(Stream, String)> = None;
+        let energy: Energy = Arc::new(Mutex::new((0.0, 0)));
+        let mut level = 0.0f32;
+        let mut history: VecDeque<f32> = VecDeque::with_capacity(BEAT_HISTORY);
+        let mut last_beat = Instant::now();
+        let mut clip: Option<u8> = None;
+        let mut sent: Option<(u16, u8)> = None;
+
+        loop {
+            std::thread::sleep(WINDOW);
+
+            let (settings, universe, monitor) = {
+                let cfg = config.lock().unwrap();
+                (cfg.audio_reactive.clone(), cfg.sacn.universe, cfg.monitor(&cfg.audio_reactive.monitor_id).cloned())
+            };
+            let Some(monitor) = monitor.filter(|_| settings.enabled) else {
+                if input.take().is_some() {
+                    println!("Audio-reactive input closed");
+                }
+                clip = None;
+                sent = None;
+                continue;
+            };
+
+            // (Re)open the input when enabled or the device changes
+            if input.as_ref().map(|(_, device)| device) != Some(&settings.input_device) {
+                input = None;
+                match open_input(&settings.input_device, Arc::clone(&energy)) {
+                    Ok(stream) => {
+                        println!("Audio-reactive input opened: {}", display_name(&settings.input_device));
+                        input = Some((stream, settings.input_device.clone()));
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        // Don't retry every window
+                        std::thread::sleep(Duration::from_secs(5));
+                        continue;
+                    }
+                }
+            }
+
+            let rms = {
+                let mut energy = energy.lock().unwrap();
+                let rms = if energy.1 > 0 { (energy.0 / energy.1 as f32).sqrt() } else { 0.0 };
+                *energy = (0.0, 0);
+                rms
+            };
+
+            let update = match settings.mode {
+                AudioReactiveMode::Dimmer => {
+                    // Fast attack, slower release so the picture doesn't flicker
+                    let target = (rms * 4.0 * settings.sensitivity).min(1.0);
+                    level = if target > level { target } else { level * 0.92 + target * 0.08 };
+                    let range = 255 - settings.min_dimmer as u16;
+                    let dimmer = settings.min_dimmer as u16 + (level * range as f32).round() as u16;
+                    (monitor.dimmer_channel(), dimmer.min(255) as u8)
+                }
+                AudioReactiveMode::AdvanceClip => {
+                    let energy = rms * rms;
+                    let average = history.iter().sum::<f32>() / history.len().max(1) as f32;
+                    let threshold = average * (1.0 + 0.5 / settings.sensitivity.max(0.1));
+                    let beat = history.len() == BEAT_HISTORY
+                        && energy > threshold
+                        && energy > 1e-5
+                        && last_beat.elapsed() >= MIN_BEAT_INTERVAL;
+                    if history.len() == BEAT_HISTORY {
+                        history.pop_front();
+                    }
+                    history.push_back(energy);
+
+                    let (first, last) = (settings.first_clip.max(1), settings.last_clip.max(settings.first_clip.max(1)));
+                    let next = match clip {
+                        None => first,
+                        Some(current) if beat => {
+                            last_beat = Instant::now();
+                            if current >= last || current < first { first } else { current + 1 }
+                        }
+                        Some(current) => current,
+                    };
+                    clip = Some(next);
+                    (monitor.clip_channel(), next)
+                }
+            };
+
+            if sent != Some(update) {
+                let frame = DmxUpdate { universe, channels: vec![update] };
+                crate::apply_frame(&app_handle, &dmx_frame, &playback_states, &frame);
+                sent = Some(update);
+            }
+        }
+    });
+}
+
+fn display_name(device: &str) -> &str {
+    if device.is_empty() { "system default" } else { device }
+}
+
+fn open_input(device_name: &str, energy: Energy) -> Result<Stream, String> {
+    let host = cpal::default_host();
+    let device = if device_name.is_empty() {
+        host.default_input_device()
+    } else {
+        host.input_devices()
+            .map_err(|e| format!("Failed to list audio inputs: {}", e))?
+            .find(|device| device.name().map(|n| n == device_name).unwrap_or(false))
+    }
+    .ok_or_else(|| format!("Audio input '{}' not found", display_name(device_name)))?;
+
+    let supported = device.default_input_config()
+        .map_err(|e| format!("Failed to read audio input format: {}", e))?;
+    let stream_config = supported.config();
+
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => build_stream::<f32>(&device, &stream_config, energy, |s| s),
+        SampleFormat::I16 => build_stream::<i16>(&device, &stream_config, energy, |s| s as f32 / 32768.0),
+        SampleFormat::U16 => build_stream::<u16>(&device, &stream_config, energy, |s| (s as f32 - 32768.0) / 32768.0),
+        format => return Err(format!("Unsupported audio input format {:?}", format)),
+    }?;
+    stream.play()
+        .map_err(|e| format!("Failed to start audio input: {}", e))?;
+    Ok(stream)
+}
+
+fn build_stream<T: SizedSample>(
+    device: &cpal::Device,
+    stream_config: &cpal::StreamConfig,
+    energy: Energy,
+    to_f32: fn(T) -> f32,
+) -> Result<Stream, String> {
+    device.build_input_stream(
+        stream_config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let sum: f32 = data.iter().map(|s| {
+                let v = to_f32(*s);
+                v * v
+            }).sum();
+            let mut energy = energy.lock().unwrap();
+            energy.0 += sum;
+            energy.1 += data.len();
+        },
+        |e| eprintln!("Audio input error: {}", e),
+        None,
+    )
+    .map_err(|e| format!("Failed to open audio input: {}", e))
+}
@@ -0,0 +1,77 @@
+/// Command-line switch that starts the player straight into production mode
+pub const PRODUCTION_ARG: &str = "--production";
+
+#[cfg(windows)]
+const TASK_NAME: &str = "StagePlayer DMX";
+
+/// Register the scheduled task that launches the player in production mode.
+/// A logon-triggered task is used rather than a Windows service: services run
+/// in session 0 and cannot open windows on the desktop. On a show machine set
+/// to log in automatically, logon is effectively boot.
+#[cfg(windows)]
+pub fn enable() -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate executable: {}", e))?;
+    let task_command = format!("\"{}\" {}", exe.display(), PRODUCTION_ARG);
+
+    // 30 s delay gives displays and the network adapter time to come up
+    let output = std::process::Command::new("schtasks")
+        .args(["/Create", "/TN", TASK_NAME, "/TR", &task_command,
+               "/SC", "ONLOGON", "/DELAY", "0000:30", "/F"])
+        .output()
+        .map_err(|e| format!("Failed to run schtasks: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to create startup task (administrator rights may be required): {}", stderr.trim()));
+    }
+
+    println!("Auto-start enabled: {}", task_command);
+    Ok(())
+}
+
+/// Remove the scheduled task
+#[cfg(windows)]
+pub fn disable() -> Result<(), String> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    let output = std::process::Command::new("schtasks")
+        .args(["/Delete", "/TN", TASK_NAME, "/F"])
+        .output()
+        .map_err(|e| format!("Failed to run schtasks: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to remove startup task: {}", stderr.trim()));
+    }
+
+    println!("Auto-start disabled");
+    Ok(())
+}
+
+/// Whether the scheduled task exists
+#[cfg(windows)]
+pub fn is_enabled() -> bool {
+    std::process::Command::new("schtasks")
+        .args(["/Query", "/TN", TASK_NAME])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+pub fn enable() -> Result<(), String> {
+    Err("Auto-start is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn disable() -> Result<(), String> {
+    Err("Auto-start is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn is_enabled() -> bool {
+    false
+}
@@ -0,0 +1,132 @@
+use serde::Serialize;
+use tauri::WebviewWindow;
+
+/// A live video input (webcam, HDMI capture card) that a ".capture" clip can
+/// point at. The output window matches `name` against the browser's device
+/// labels, so it is the name as the operating system reports it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureDevice {
+    pub name: String,
+}
+
+/// Video capture devices from /sys/class/video4linux. Only the first node of
+/// each device is listed; the others are metadata or extra streams.
+#[cfg(target_os = "linux")]
+pub fn list_devices() -> Result<Vec<CaptureDevice>, String> {
+    let entries = match std::fs::read_dir("/sys/class/video4linux") {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut devices = Vec::new();
+    for entry in entries.flatten() {
+        let node = entry.path();
+        let index = std::fs::read_to_string(node.join("index")).unwrap_or_default();
+        if index.trim() != "0" {
+            continue;
+        }
+        if let Ok(name) = std::fs::read_to_string(node.join("name")) {
+            devices.push(CaptureDevice { name: name.trim().to_string() });
+        }
+    }
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices.dedup_by(|a, b| a.name == b.name);
+    Ok(devices)
+}
+
+/// Camera and imaging devices known to Plug and Play
+#[cfg(windows)]
+pub fn list_devices() -> Result<Vec<CaptureDevice>, String> {
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command",
+               "Get-PnpDevice -Class Camera,Image -Status OK | Select-Object -ExpandProperty FriendlyName"])
+        .output()
+        .map_err(|e| format!("Failed to run powershell: {}", e))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| CaptureDevice { name: name.to_string() })
+        .collect())
+}
+
+#[cfg(target_os = "macos")]
+pub fn list_devices() -> Result<Vec<CaptureDevice>, String> {
+    let output = std::process::Command::new("system_profiler")
+        .args(["SPCameraDataType", "-json"])
+        .output()
+        .map_err(|e| format!("Failed to run system_profiler: {}", e))?;
+
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse camera list: {}", e))?;
+    Ok(report["SPCameraDataType"]
+        .as_array()
+        .map(|cameras| cameras.iter()
+            .filter_map(|camera| camera["_name"].as_str())
+            .map(|name| CaptureDevice { name: name.to_string() })
+            .collect())
+        .unwrap_or_default())
+}
+
+/// Grant camera access to an output window without a permission prompt;
+/// nobody is there to click it on a show machine.
+#[cfg(windows)]
+pub fn allow_capture(window: &WebviewWindow) -> Result<(), String> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        COREWEBVIEW2_PERMISSION_KIND, COREWEBVIEW2_PERMISSION_KIND_CAMERA,
+        COREWEBVIEW2_PERMISSION_STATE_ALLOW,
+    };
+    use webview2_com::PermissionRequestedEventHandler;
+
+    window.with_webview(|webview| unsafe {
+        let core = match webview.controller().CoreWebView2() {
+            Ok(core) => core,
+            Err(e) => {
+                eprintln!("Failed to get WebView2 for capture permission: {}", e);
+                return;
+            }
+        };
+        let handler = PermissionRequestedEventHandler::create(Box::new(|_, args| {
+            let Some(args) = args else { return Ok(()) };
+            let mut kind = COREWEBVIEW2_PERMISSION_KIND::default();
+            args.PermissionKind(&mut kind)?;
+            if kind == COREWEBVIEW2_PERMISSION_KIND_CAMERA {
+                args.SetState(COREWEBVIEW2_PERMISSION_STATE_ALLOW)?;
+            }
+            Ok(())
+        }));
+        let mut token = 0i64;
+        if let Err(e) = core.add_PermissionRequested(&handler, &mut token) {
+            eprintln!("Failed to install capture permission handler: {}", e);
+        }
+    }).map_err(|e| format!("Failed to access webview: {}", e))
+}
+
+/// WebKitGTK has media capture off by default and denies unanswered requests
+#[cfg(target_os = "linux")]
+pub fn allow_capture(window: &WebviewWindow) -> Result<(), String> {
+    use webkit2gtk::glib::prelude::*;
+    use webkit2gtk::{PermissionRequestExt, SettingsExt, UserMediaPermissionRequest, WebViewExt};
+
+    window.with_webview(|webview| {
+        let view = webview.inner();
+        if let Some(settings) = view.settings() {
+            settings.set_enable_media_stream(true);
+        }
+        view.connect_permission_request(|_, request| {
+            if request.is::<UserMediaPermissionRequest>() {
+                request.allow();
+                true
+            } else {
+                false
+            }
+        });
+    }).map_err(|e| format!("Failed to access webview: {}", e))
+}
+
+/// WKWebView asks once per app; the answer is remembered by macOS
+#[cfg(target_os = "macos")]
+pub fn allow_capture(_window: &WebviewWindow) -> Result<(), String> {
+    Ok(())
+}
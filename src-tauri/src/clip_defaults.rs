@@ -0,0 +1,97 @@
+use crate::media_scanner;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Defaults from a ".json" file next to a clip with the same name
+/// (e.g. 001_intro.mp4 + 001_intro.json), or from the clip's entry in the
+/// folder's clips.json. Every field is optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClipDefaults {
+    /// Level at full console dimmer; lower console levels scale it
+    pub dimmer: Option<u8>,
+    /// Used while the console's playtype channel is at 0
+    pub playtype: Option<u8>,
+    /// Video volume 0-100; without it videos play muted
+    pub volume: Option<u8>,
+    /// Fade up from black when the clip is selected
+    pub transition_ms: Option<u32>,
+    /// Seconds into the video where playback starts (and loops back to)
+    pub in_secs: Option<f64>,
+    /// Seconds into the video where playback ends or loops
+    pub out_secs: Option<f64>,
+}
+
+/// In and out points sent to the output window with the clip
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct Trim {
+    pub in_secs: f64,
+    pub out_secs: Option<f64>,
+}
+
+impl ClipDefaults {
+    /// None when the clip plays from start to end. An out point that isn't
+    /// after the in point is ignored.
+    pub fn trim(&self) -> Option<Trim> {
+        if self.in_secs.is_none() && self.out_secs.is_none() {
+            return None;
+        }
+        let in_secs = self.in_secs.unwrap_or(0.0).max(0.0);
+        Some(Trim {
+            in_secs,
+            out_secs: self.out_secs.filter(|&out| out > in_secs),
+        })
+    }
+    
+    /// Fill in what this leaves unset from `fallback`
+    fn or(self, fallback: ClipDefaults) -> ClipDefaults {
+        ClipDefaults {
+            dimmer: self.dimmer.or(fallback.dimmer),
+            playtype: self.playtype.or(fallback.playtype),
+            volume: self.volume.or(fallback.volume),
+            transition_ms: self.transition_ms.or(fallback.transition_ms),
+            in_secs: self.in_secs.or(fallback.in_secs),
+            out_secs: self.out_secs.or(fallback.out_secs),
+        }
+    }
+}
+
+/// Defaults for the clip at a media URL; all None without a sidecar
+pub fn for_url(media_url: &str) -> ClipDefaults {
+    media_scanner::path_from_asset_url(media_url)
+        .map(|path| for_path(&path))
+        .unwrap_or_default()
+}
+
+/// The clip's own sidecar, with anything it leaves unset taken from the
+/// folder's clips.json
+pub fn for_path(media_path: &Path) -> ClipDefaults {
+    let listed = match (media_path.parent(), media_path.file_name().and_then(|n| n.to_str())) {
+        (Some(folder), Some(filename)) => media_scanner::read_clip_list(folder).remove(filename).unwrap_or_default(),
+        _ => ClipDefaults::default(),
+    };
+    
+    let sidecar = media_path.with_extension("json");
+    let Ok(contents) = std::fs::read_to_string(&sidecar) else {
+        return listed;
+    };
+    match serde_json::from_str::<ClipDefaults>(&contents) {
+        Ok(defaults) => defaults.or(listed),
+        Err(e) => {
+            eprintln!("Ignoring clip defaults {:?}: {}", sidecar, e);
+            listed
+        }
+    }
+}
+
+/// Dimmer and playtype after filling in what the console leaves unset
+pub fn resolve(defaults: &ClipDefaults, dimmer: u8, playtype: u8) -> (u8, u8) {
+    let dimmer = match defaults.dimmer {
+        Some(level) => (dimmer as u16 * level as u16 / 255) as u8,
+        None => dimmer,
+    };
+    let playtype = match defaults.playtype {
+        Some(default) if playtype == 0 => default,
+        _ => playtype,
+    };
+    (dimmer, playtype)
+}
@@ -31,6 +31,131 @@ impl Default for Orientation {
     }
 }
 
+/// How a monitor's resolution maps to window size on scaled (high-DPI) displays
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DpiSizing {
+    /// Resolution is in physical panel pixels regardless of OS scaling
+    Physical,
+    /// Resolution is in scaled (logical) units, multiplied by the display's scale factor
+    Logical,
+}
+
+impl Default for DpiSizing {
+    fn default() -> Self {
+        DpiSizing::Physical
+    }
+}
+
+/// What the playtype channel asks the output to do with the clip
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PlayType {
+    /// 0-63: play the clip over and over
+    Loop,
+    /// 64-95: hold the current frame; a loop value resumes from there
+    Pause,
+    /// 96-127: start the clip over on entering this range, then loop
+    Restart,
+    /// 128-159: play the clip once and hold its last frame
+    FreezeLastFrame,
+    /// 160-191: play the clip once, then go black
+    PlayOnce,
+    /// 192-255: cycle through the images numbered after a still image;
+    /// videos hold their last frame
+    Slideshow,
+}
+
+impl PlayType {
+    pub fn from_dmx(value: u8) -> Self {
+        match value {
+            0..=63 => PlayType::Loop,
+            64..=95 => PlayType::Pause,
+            96..=127 => PlayType::Restart,
+            128..=159 => PlayType::FreezeLastFrame,
+            160..=191 => PlayType::PlayOnce,
+            192..=255 => PlayType::Slideshow,
+        }
+    }
+}
+
+impl Default for PlayType {
+    fn default() -> Self {
+        PlayType::Loop
+    }
+}
+
+/// How an output window turns the dimmer level into opacity
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DimmingCurve {
+    /// Opacity follows the level directly
+    Linear,
+    /// Gamma 2.2, so equal dimmer steps look like equal brightness steps
+    Gamma,
+    /// CIE 1931 lightness; a little more light than gamma near the bottom
+    Cie,
+}
+
+impl Default for DimmingCurve {
+    fn default() -> Self {
+        DimmingCurve::Linear
+    }
+}
+
+/// Which GPU renders a monitor's output window (WebView2 only)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum GpuPreference {
+    Default,
+    /// Discrete GPU on hybrid (iGPU + dGPU) machines
+    HighPerformance,
+    /// Integrated GPU on hybrid machines
+    LowPower,
+    /// A specific adapter, by LUID as reported by get_gpu_adapters
+    Adapter { luid: String },
+}
+
+impl Default for GpuPreference {
+    fn default() -> Self {
+        GpuPreference::Default
+    }
+}
+
+/// Built-in content a generator clip produces
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum Generator {
+    /// Solid color; red, green and blue at start_channel, +1, +2
+    SolidColor { start_channel: u16 },
+    /// Flashing color; rate at start_channel (0 = off, 1-255 = 1-25 Hz),
+    /// red, green, blue at +1..+3
+    Strobe { start_channel: u16 },
+    /// Two-color gradient; first color at start_channel..+2, second at
+    /// +3..+5, angle at +6 (0-255 = 0-360°)
+    Gradient { start_channel: u16 },
+    /// Animated video noise; speed at start_channel (0 = frozen),
+    /// grain size at +1
+    Noise { start_channel: u16 },
+}
+
+impl Generator {
+    /// Channels the generator reads its parameters from
+    pub fn channels(&self) -> std::ops::Range<u16> {
+        let (start_channel, count) = match *self {
+            Generator::SolidColor { start_channel } => (start_channel, 3),
+            Generator::Strobe { start_channel } => (start_channel, 4),
+            Generator::Gradient { start_channel } => (start_channel, 7),
+            Generator::Noise { start_channel } => (start_channel, 2),
+        };
+        start_channel..start_channel.saturating_add(count)
+    }
+}
+
+/// A clip value mapped to a generator (no media file needed)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GeneratorClip {
+    pub clip: u8,
+    pub generator: Generator,
+}
+
 /// sACN reception mode
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SacnMode {
@@ -44,6 +169,56 @@ impl Default for SacnMode {
     }
 }
 
+/// What the outputs do when the last source sends stream-terminated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TerminationAction {
+    /// Keep showing the last look
+    Hold,
+    /// Take every channel to 0, which clears the clips
+    Blackout,
+}
+
+impl Default for TerminationAction {
+    fn default() -> Self {
+        TerminationAction::Hold
+    }
+}
+
+/// What the outputs do once no DMX has arrived for the E1.31 data loss
+/// timeout (2.5 s)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DataLossAction {
+    /// Keep showing the last look
+    Hold,
+    /// Fade every output to black over `data_loss_fade_secs`
+    FadeToBlack,
+    /// Monitors with a fallback clip switch to it after their fallback
+    /// timeout; the others hold
+    FallbackClip,
+}
+
+impl Default for DataLossAction {
+    fn default() -> Self {
+        DataLossAction::FallbackClip
+    }
+}
+
+/// How sources sending the same universe at the same priority are combined
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum MergeMode {
+    /// Highest takes precedence: each channel takes the highest level
+    Htp,
+    /// Latest takes precedence: each channel follows the source that
+    /// changed it last
+    Ltp,
+}
+
+impl Default for MergeMode {
+    fn default() -> Self {
+        MergeMode::Ltp
+    }
+}
+
 /// sACN configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SacnConfig {
@@ -52,6 +227,50 @@ pub struct SacnConfig {
     pub ip_address: String,
     pub unicast_ip: String,
     pub network_interface: String,
+    /// Registered input protocols started alongside sACN, e.g. "kinet"
+    #[serde(default)]
+    pub extra_inputs: Vec<String>,
+    /// Art-Net Port-Address listened to when "artnet" is an extra input
+    #[serde(default)]
+    pub artnet_universe: u16,
+    /// Merge for equal-priority sources; higher priority always wins
+    #[serde(default)]
+    pub merge_mode: MergeMode,
+    #[serde(default)]
+    pub on_termination: TerminationAction,
+    #[serde(default)]
+    pub on_data_loss: DataLossAction,
+    #[serde(default = "default_data_loss_fade_secs")]
+    pub data_loss_fade_secs: f32,
+    /// In unicast mode, only packets from these IPs are accepted; empty accepts any
+    #[serde(default)]
+    pub allowed_sources: Vec<String>,
+    /// Registered input protocol (e.g. "artnet") used only while sACN is
+    /// silent; empty disables failover
+    #[serde(default)]
+    pub failover_input: String,
+    /// Seconds without sACN before the failover input takes over
+    #[serde(default = "default_failover_timeout_secs")]
+    pub failover_timeout_secs: f32,
+    /// A new clip channel value must hold this long before the clip changes;
+    /// 0 with `clip_stable_frames` at 1 turns the glitch filter off
+    #[serde(default)]
+    pub clip_stable_ms: u32,
+    /// ...and arrive in this many consecutive frames
+    #[serde(default = "default_clip_stable_frames")]
+    pub clip_stable_frames: u32,
+}
+
+fn default_data_loss_fade_secs() -> f32 {
+    3.0
+}
+
+fn default_failover_timeout_secs() -> f32 {
+    3.0
+}
+
+fn default_clip_stable_frames() -> u32 {
+    1
 }
 
 impl Default for SacnConfig {
@@ -62,10 +281,109 @@ impl Default for SacnConfig {
             ip_address: "0.0.0.0".to_string(),
             unicast_ip: String::new(),
             network_interface: String::new(),
+            extra_inputs: Vec::new(),
+            artnet_universe: 0,
+            merge_mode: MergeMode::Ltp,
+            on_termination: TerminationAction::Hold,
+            on_data_loss: DataLossAction::FallbackClip,
+            data_loss_fade_secs: default_data_loss_fade_secs(),
+            allowed_sources: Vec::new(),
+            failover_input: String::new(),
+            failover_timeout_secs: default_failover_timeout_secs(),
+            clip_stable_ms: 0,
+            clip_stable_frames: default_clip_stable_frames(),
+        }
+    }
+}
+
+/// A strip of pixels sampled from the picture. The rectangle is given as
+/// fractions (0-1) of the video frame and divided into `pixels` equal parts
+/// along its longer side; each part's average color is sent as RGB from
+/// `start_channel` of `universe`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PixelRegion {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub pixels: u16,
+    pub universe: u16,
+    pub start_channel: u16,
+}
+
+/// sACN output of colors sampled from a monitor's picture
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PixelMapConfig {
+    pub enabled: bool,
+    /// Samples per second
+    pub fps: u32,
+    pub priority: u8,
+    pub regions: Vec<PixelRegion>,
+}
+
+impl Default for PixelMapConfig {
+    fn default() -> Self {
+        PixelMapConfig {
+            enabled: false,
+            fps: 30,
+            priority: 100,
+            regions: Vec::new(),
         }
     }
 }
 
+/// Spout sender sharing a monitor's picture with other apps on the same
+/// machine (Windows only)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpoutConfig {
+    pub enabled: bool,
+    /// Name receivers list the sender under; empty uses "StagePlayer DMX <monitor name>"
+    #[serde(default)]
+    pub sender_name: String,
+    /// Frames shared per second
+    pub fps: u32,
+}
+
+impl Default for SpoutConfig {
+    fn default() -> Self {
+        SpoutConfig {
+            enabled: false,
+            sender_name: String::new(),
+            fps: 30,
+        }
+    }
+}
+
+/// Caption overlay for clips with a sidecar subtitle file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SubtitleConfig {
+    pub enabled: bool,
+    /// When set, captions only show while this channel is at 128 or above
+    pub channel: Option<u16>,
+}
+
+/// Channels a monitor's fixed functions are patched to. Anything left None
+/// follows the layout from `start_channel` (clip, dimmer, playtype, ...).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ChannelMap {
+    #[serde(default)]
+    pub clip: Option<u16>,
+    #[serde(default)]
+    pub clip_fine: Option<u16>,
+    #[serde(default)]
+    pub dimmer: Option<u16>,
+    #[serde(default)]
+    pub playtype: Option<u16>,
+    #[serde(default)]
+    pub volume: Option<u16>,
+    #[serde(default)]
+    pub pan: Option<u16>,
+    #[serde(default)]
+    pub zoom: Option<u16>,
+    #[serde(default)]
+    pub speed: Option<u16>,
+}
+
 /// Monitor configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitorConfig {
@@ -78,6 +396,109 @@ pub struct MonitorConfig {
     pub display_index: usize,
     pub window_x: Option<i32>,
     pub window_y: Option<i32>,
+    /// Clip (DMX value) played automatically when sACN is lost; None disables it
+    #[serde(default)]
+    pub fallback_clip: Option<u16>,
+    /// Seconds without valid sACN before the fallback clip takes over
+    #[serde(default = "default_fallback_timeout_secs")]
+    pub fallback_timeout_secs: u32,
+    #[serde(default)]
+    pub dpi_sizing: DpiSizing,
+    #[serde(default)]
+    pub gpu: GpuPreference,
+    /// Per-function channel patch overriding the start_channel layout
+    #[serde(default)]
+    pub channel_map: ChannelMap,
+    /// Clip selected by a coarse/fine channel pair (1-65534, files named
+    /// 00261_name.ext); every later channel moves up by one
+    #[serde(default)]
+    pub clip_16bit: bool,
+    /// Fourth channel after playtype that sets the clip's audio volume;
+    /// the Ken Burns channels follow it
+    #[serde(default)]
+    pub volume_control: bool,
+    /// Pan/zoom/speed channels after playtype that animate still images
+    #[serde(default)]
+    pub ken_burns: bool,
+    /// Seconds each image is shown by the slideshow playtype
+    #[serde(default = "default_slideshow_interval_secs")]
+    pub slideshow_interval_secs: f32,
+    /// Crossfade between slideshow images; 0 = hard cut
+    #[serde(default = "default_slideshow_crossfade_ms")]
+    pub slideshow_crossfade_ms: u32,
+    /// Clip values that show built-in generated content instead of a file
+    #[serde(default)]
+    pub generators: Vec<GeneratorClip>,
+    /// Colors sampled from the picture and sent as sACN, e.g. for LED tape
+    #[serde(default)]
+    pub pixel_map: PixelMapConfig,
+    /// The picture shared as a Spout texture, e.g. for Resolume
+    #[serde(default)]
+    pub spout: SpoutConfig,
+    /// First of three shader effect channels (pixelate, chroma shift,
+    /// vignette); None disables them
+    #[serde(default)]
+    pub effects_channel: Option<u16>,
+    /// First of three channels (X offset, Y offset, scale) that move and
+    /// size the picture live; None disables them
+    #[serde(default)]
+    pub position_channel: Option<u16>,
+    /// Channel turning the picture 0/90/180/270° (from 0/64/128/192),
+    /// overriding `orientation` while set
+    #[serde(default)]
+    pub rotation_channel: Option<u16>,
+    /// First of three channels (red, green, blue) multiplying the output
+    /// color; None disables them
+    #[serde(default)]
+    pub tint_channel: Option<u16>,
+    /// Channel choosing the bank the clip channel selects from: 0 = the
+    /// media folder, 1-255 = its "###_name" subfolders; None disables it
+    #[serde(default)]
+    pub bank_channel: Option<u16>,
+    /// Channel blinking the output between the media and black
+    /// (0 = off, 1-255 = 1-25 Hz); None disables it
+    #[serde(default)]
+    pub strobe_channel: Option<u16>,
+    /// Channel setting the transition time when the clip changes
+    /// (0 = cut, 255 = 10 s); None leaves it to the clip's defaults
+    #[serde(default)]
+    pub crossfade_channel: Option<u16>,
+    /// Channel naming the clip (1-254) to buffer in the output window
+    /// ahead of selecting it; None disables it
+    #[serde(default)]
+    pub preload_channel: Option<u16>,
+    /// Crossfade between clips when neither the crossfade channel nor the
+    /// clip's defaults give a time; 0 cuts
+    #[serde(default)]
+    pub clip_crossfade_ms: u32,
+    /// Captions from ".srt" / ".vtt" files next to the clips
+    #[serde(default)]
+    pub subtitles: SubtitleConfig,
+    /// Applied in the output window, after the console's dimmer level
+    #[serde(default)]
+    pub dimming_curve: DimmingCurve,
+    /// Time constant (ms) the backend eases console dimmer changes over,
+    /// updating the output at 60 fps; 0 passes them straight through
+    #[serde(default)]
+    pub dimmer_smoothing_ms: u32,
+    /// Seek the playing clip to follow incoming timecode instead of free-running
+    #[serde(default)]
+    pub chase_timecode: bool,
+    /// Timecode (in seconds) at which a chasing clip is at its first frame
+    #[serde(default)]
+    pub timecode_offset_secs: f64,
+}
+
+fn default_fallback_timeout_secs() -> u32 {
+    5
+}
+
+fn default_slideshow_interval_secs() -> f32 {
+    8.0
+}
+
+fn default_slideshow_crossfade_ms() -> u32 {
+    1000
 }
 
 impl Default for MonitorConfig {
@@ -92,21 +513,143 @@ impl Default for MonitorConfig {
             display_index: 0,
             window_x: None,
             window_y: None,
+            fallback_clip: None,
+            fallback_timeout_secs: default_fallback_timeout_secs(),
+            dpi_sizing: DpiSizing::default(),
+            gpu: GpuPreference::default(),
+            channel_map: ChannelMap::default(),
+            clip_16bit: false,
+            volume_control: false,
+            ken_burns: false,
+            slideshow_interval_secs: default_slideshow_interval_secs(),
+            slideshow_crossfade_ms: default_slideshow_crossfade_ms(),
+            generators: Vec::new(),
+            pixel_map: PixelMapConfig::default(),
+            spout: SpoutConfig::default(),
+            effects_channel: None,
+            crossfade_channel: None,
+            clip_crossfade_ms: 0,
+            preload_channel: None,
+            position_channel: None,
+            rotation_channel: None,
+            tint_channel: None,
+            strobe_channel: None,
+            bank_channel: None,
+            subtitles: SubtitleConfig::default(),
+            dimming_curve: DimmingCurve::default(),
+            dimmer_smoothing_ms: 0,
+            chase_timecode: false,
+            timecode_offset_secs: 0.0,
         }
     }
 }
 
 impl MonitorConfig {
+    // Where each function sits in the start_channel layout
+    fn layout_dimmer(&self) -> u16 {
+        self.start_channel + 1 + self.clip_16bit as u16
+    }
+    
+    fn layout_pan(&self) -> u16 {
+        self.layout_dimmer() + 2 + self.volume_control as u16
+    }
+    
+    /// Coarse clip channel in 16-bit mode
     pub fn clip_channel(&self) -> u16 {
-        self.start_channel
+        self.channel_map.clip.unwrap_or(self.start_channel)
+    }
+    
+    /// Only used when `clip_16bit` is set
+    pub fn clip_fine_channel(&self) -> u16 {
+        self.channel_map.clip_fine.unwrap_or(self.start_channel + 1)
+    }
+    
+    /// Clip value selected in a full frame (index N = channel N), coarse
+    /// and fine together in 16-bit mode
+    pub fn clip_value(&self, values: &[u8; 513]) -> u16 {
+        let channel = |ch: u16| values.get(ch as usize).copied().unwrap_or(0) as u16;
+        if self.clip_16bit {
+            (channel(self.clip_channel()) << 8) | channel(self.clip_fine_channel())
+        } else {
+            channel(self.clip_channel())
+        }
+    }
+
+    /// Highest clip value, which closes the output window
+    pub fn close_value(&self) -> u16 {
+        if self.clip_16bit { u16::MAX } else { u8::MAX as u16 }
     }
     
     pub fn dimmer_channel(&self) -> u16 {
-        self.start_channel + 1
+        self.channel_map.dimmer.unwrap_or(self.layout_dimmer())
     }
     
     pub fn playtype_channel(&self) -> u16 {
-        self.start_channel + 2
+        self.channel_map.playtype.unwrap_or(self.layout_dimmer() + 1)
+    }
+    
+    /// Only used when `volume_control` is set
+    pub fn volume_channel(&self) -> u16 {
+        self.channel_map.volume.unwrap_or(self.layout_dimmer() + 2)
+    }
+    
+    /// Ken Burns channels, only used when `ken_burns` is set
+    pub fn pan_channel(&self) -> u16 {
+        self.channel_map.pan.unwrap_or(self.layout_pan())
+    }
+    
+    pub fn zoom_channel(&self) -> u16 {
+        self.channel_map.zoom.unwrap_or(self.layout_pan() + 1)
+    }
+    
+    pub fn speed_channel(&self) -> u16 {
+        self.channel_map.speed.unwrap_or(self.layout_pan() + 2)
+    }
+    
+    /// Every channel the monitor responds to, with the function it drives
+    pub fn channel_functions(&self) -> Vec<(&'static str, u16)> {
+        let mut functions = vec![
+            ("clip", self.clip_channel()),
+            ("dimmer", self.dimmer_channel()),
+            ("playtype", self.playtype_channel()),
+        ];
+        if self.clip_16bit {
+            functions.push(("clip fine", self.clip_fine_channel()));
+        }
+        if self.volume_control {
+            functions.push(("volume", self.volume_channel()));
+        }
+        if self.ken_burns {
+            functions.extend([("pan", self.pan_channel()), ("zoom", self.zoom_channel()), ("speed", self.speed_channel())]);
+        }
+        let single = [
+            ("crossfade", self.crossfade_channel),
+            ("rotation", self.rotation_channel),
+            ("strobe", self.strobe_channel),
+            ("bank", self.bank_channel),
+            ("preload", self.preload_channel),
+        ];
+        functions.extend(single.into_iter().filter_map(|(name, channel)| Some((name, channel?))));
+        if let Some(first) = self.position_channel {
+            functions.extend([("position x", first), ("position y", first + 1), ("position scale", first + 2)]);
+        }
+        if let Some(first) = self.tint_channel {
+            functions.extend([("tint red", first), ("tint green", first + 1), ("tint blue", first + 2)]);
+        }
+        if let Some(first) = self.effects_channel {
+            functions.extend([("pixelate", first), ("chroma shift", first + 1), ("vignette", first + 2)]);
+        }
+        if self.subtitles.enabled {
+            functions.extend(self.subtitles.channel.map(|channel| ("subtitles", channel)));
+        }
+        // Only the selected generator runs, so generators may share channels
+        let mut generator_channels: Vec<u16> = self.generators.iter()
+            .flat_map(|clip| clip.generator.channels())
+            .collect();
+        generator_channels.sort_unstable();
+        generator_channels.dedup();
+        functions.extend(generator_channels.into_iter().map(|channel| ("generator", channel)));
+        functions
     }
 }
 
@@ -138,6 +681,619 @@ impl Default for PreviewMode {
     }
 }
 
+/// Output rendering profile
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PlaybackProfile {
+    /// Full compositing path for desktop GPUs
+    Standard,
+    /// Reduced path for single-board computers (e.g. Raspberry Pi):
+    /// hardware H.264 decode and minimal compositing in the output window
+    Lightweight,
+}
+
+impl Default for PlaybackProfile {
+    fn default() -> Self {
+        if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+            PlaybackProfile::Lightweight
+        } else {
+            PlaybackProfile::Standard
+        }
+    }
+}
+
+/// How output windows are placed on their displays
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum OutputPlacement {
+    /// Fullscreen-on-output under Wayland, coordinates everywhere else
+    Auto,
+    /// Absolute desktop coordinates (Windows, macOS, X11)
+    Coordinates,
+    /// Compositor-managed fullscreen on the chosen output
+    FullscreenOnOutput,
+}
+
+impl Default for OutputPlacement {
+    fn default() -> Self {
+        OutputPlacement::Auto
+    }
+}
+
+/// sACN feedback output: the player's state sent back to the console
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeedbackConfig {
+    pub enabled: bool,
+    pub universe: u16,
+    /// First channel of the feedback block (8 channels per monitor)
+    pub start_channel: u16,
+    pub priority: u8,
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        FeedbackConfig {
+            enabled: false,
+            universe: 2,
+            start_channel: 1,
+            priority: 100,
+        }
+    }
+}
+
+/// Wire format of outbound trigger messages
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TriggerProtocol {
+    Osc,
+    Udp,
+}
+
+impl Default for TriggerProtocol {
+    fn default() -> Self {
+        TriggerProtocol::Osc
+    }
+}
+
+/// Message sent to downstream show control when a play-once clip ends
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TriggerOutputConfig {
+    pub enabled: bool,
+    pub protocol: TriggerProtocol,
+    /// Destination as "host:port"
+    pub target: String,
+    /// OSC address; the message carries the monitor id and clip value
+    pub osc_address: String,
+    /// Plain UDP payload; {monitor} and {clip} are substituted
+    pub udp_message: String,
+}
+
+impl Default for TriggerOutputConfig {
+    fn default() -> Self {
+        TriggerOutputConfig {
+            enabled: false,
+            protocol: TriggerProtocol::default(),
+            target: "127.0.0.1:53000".to_string(),
+            osc_address: "/stageplayer/clip-finished".to_string(),
+            udp_message: "{monitor} finished {clip}".to_string(),
+        }
+    }
+}
+
+/// One monitor's part of a cue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct CueLook {
+    /// Clip value as on the clip channel, up to 65535 with 16-bit clips
+    pub clip: u16,
+    pub dimmer: u8,
+    pub playtype: u8,
+}
+
+/// A look in the cue stack. A monitor without a look keeps what it shows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Cue {
+    pub name: String,
+    #[serde(default)]
+    pub monitor1: Option<CueLook>,
+    #[serde(default)]
+    pub monitor2: Option<CueLook>,
+    /// Fade the changing outputs to black before the new look
+    #[serde(default)]
+    pub fade_out_ms: u32,
+    /// Fade the new look up from black
+    #[serde(default)]
+    pub fade_in_ms: u32,
+}
+
+/// Ordered looks advanced one at a time by a Go channel or the cue_go command
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CueStackConfig {
+    pub enabled: bool,
+    /// Moving this channel from below 128 to 128 or above runs the next cue; 0 = none
+    pub go_channel: u16,
+    pub cues: Vec<Cue>,
+}
+
+/// A clip placed on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineEvent {
+    /// Seconds from the start of the show
+    pub at_secs: f64,
+    pub monitor_id: String,
+    pub look: CueLook,
+}
+
+/// Self-contained show: clips per monitor at fixed times
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TimelineConfig {
+    pub events: Vec<TimelineEvent>,
+    /// Jump back to the start at this time; None = run on
+    pub loop_at_secs: Option<f64>,
+}
+
+/// One step of a macro
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MacroAction {
+    SelectClip { monitor_id: String, clip: u16 },
+    SetDimmer { monitor_id: String, dimmer: u8 },
+    Wait { ms: u64 },
+    /// OSC message with int32 arguments to "host:port"
+    SendOsc { target: String, address: String, args: Vec<i32> },
+}
+
+/// Actions fired when the control channel is set to `value`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Macro {
+    pub name: String,
+    pub value: u8,
+    pub actions: Vec<MacroAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MacroConfig {
+    pub enabled: bool,
+    /// Channel whose value picks the macro to run; 0 = none
+    pub control_channel: u16,
+    pub macros: Vec<Macro>,
+}
+
+/// Site-specific logic in a Rhai script
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScriptingConfig {
+    pub enabled: bool,
+    /// Path to the .rhai file; reloaded when it changes
+    pub script_path: String,
+    /// Seconds without sACN before on_signal_loss is called
+    pub signal_loss_secs: u32,
+}
+
+impl Default for ScriptingConfig {
+    fn default() -> Self {
+        ScriptingConfig {
+            enabled: false,
+            script_path: String::new(),
+            signal_loss_secs: 5,
+        }
+    }
+}
+
+/// OSC control of the monitors from show control software or a tablet.
+/// "{monitor}" in an address stands for the monitor number (1 or 2).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OscInputConfig {
+    pub enabled: bool,
+    /// UDP port to listen on
+    pub port: u16,
+    /// Takes the clip value (0-255), as the clip channel would
+    pub clip_address: String,
+    /// Takes the dimmer level (0-255)
+    pub dimmer_address: String,
+    /// Takes the playtype value (0-255)
+    pub playtype_address: String,
+    /// Dims both monitors to 0; no argument needed
+    pub blackout_address: String,
+}
+
+impl Default for OscInputConfig {
+    fn default() -> Self {
+        OscInputConfig {
+            enabled: false,
+            port: 8000,
+            clip_address: "/clip/{monitor}".to_string(),
+            dimmer_address: "/dimmer/{monitor}".to_string(),
+            playtype_address: "/playtype/{monitor}".to_string(),
+            blackout_address: "/blackout".to_string(),
+        }
+    }
+}
+
+/// MIDI control of the monitors, for bands without a lighting console
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MidiInputConfig {
+    pub enabled: bool,
+    /// Part of the input device's name; empty uses the first device
+    pub device: String,
+    /// MIDI Show Control device ID to answer; 127 answers every message
+    #[serde(default = "default_msc_device_id")]
+    pub msc_device_id: u8,
+    pub monitor1: MidiMapping,
+    pub monitor2: MidiMapping,
+}
+
+fn default_msc_device_id() -> u8 {
+    0x7f
+}
+
+impl Default for MidiInputConfig {
+    fn default() -> Self {
+        MidiInputConfig {
+            enabled: false,
+            device: String::new(),
+            msc_device_id: default_msc_device_id(),
+            monitor1: MidiMapping { channel: 1, ..Default::default() },
+            monitor2: MidiMapping { channel: 2, ..Default::default() },
+        }
+    }
+}
+
+/// How one monitor follows a MIDI channel. A note-on selects the clip with
+/// the note's number; CC values (0-127) are scaled to 0-255.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MidiMapping {
+    /// MIDI channel 1-16; 0 = not controlled over MIDI
+    pub channel: u8,
+    /// Added to the note number, so clips above 127 can be reached
+    #[serde(default)]
+    pub clip_offset: u8,
+    pub dimmer_cc: Option<u8>,
+    pub playtype_cc: Option<u8>,
+    /// MSC cue list this monitor follows; GO selects the clip numbered like
+    /// the cue. None ignores MSC.
+    #[serde(default)]
+    pub msc_cue_list: Option<u16>,
+    /// Playtype set by MSC STOP; the default plays the clip out and stops
+    #[serde(default = "default_msc_stop_playtype")]
+    pub msc_stop_playtype: u8,
+    /// Playtype set by MSC RESUME; the default loops again
+    #[serde(default)]
+    pub msc_resume_playtype: u8,
+}
+
+fn default_msc_stop_playtype() -> u8 {
+    128
+}
+
+impl Default for MidiMapping {
+    fn default() -> Self {
+        MidiMapping {
+            channel: 0,
+            clip_offset: 0,
+            dimmer_cc: Some(7),
+            playtype_cc: None,
+            msc_cue_list: None,
+            msc_stop_playtype: default_msc_stop_playtype(),
+            msc_resume_playtype: 0,
+        }
+    }
+}
+
+/// HTTP control API for building automation and other systems without DMX
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    /// TCP port to listen on, on every interface
+    pub port: u16,
+    /// Required as "Authorization: Bearer <token>"; empty lets anyone on
+    /// the network in
+    #[serde(default)]
+    pub token: String,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        HttpApiConfig {
+            enabled: false,
+            port: 8080,
+            token: String::new(),
+        }
+    }
+}
+
+/// MQTT connection for publishing status and taking commands
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MqttConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+    /// Retained JSON with listener and per-monitor playback state
+    pub status_topic: String,
+    /// Commands arrive below this: "<topic>/monitor/1/clip" with the value
+    /// as payload, and "<topic>/blackout"
+    pub command_topic: String,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        MqttConfig {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            client_id: "stageplayer-dmx".to_string(),
+            username: String::new(),
+            password: String::new(),
+            status_topic: "stageplayer/status".to_string(),
+            command_topic: "stageplayer/command".to_string(),
+        }
+    }
+}
+
+/// Where incoming timecode comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum TimecodeSource {
+    /// ArtTimeCode on UDP port 6454
+    ArtNet,
+    /// MIDI Timecode (quarter frames and full-frame messages)
+    Midi,
+}
+
+impl Default for TimecodeSource {
+    fn default() -> Self {
+        TimecodeSource::ArtNet
+    }
+}
+
+/// Timecode input that monitors with `chase_timecode` follow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimecodeConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub source: TimecodeSource,
+    /// Part of the MIDI input's name carrying MTC; empty takes the first one
+    #[serde(default)]
+    pub midi_device: String,
+    /// Keep the timeline's clock at the incoming timecode too
+    #[serde(default)]
+    pub drive_timeline: bool,
+    /// How far a chasing clip may drift before it is seeked back
+    pub max_drift_ms: u32,
+}
+
+impl Default for TimecodeConfig {
+    fn default() -> Self {
+        TimecodeConfig {
+            enabled: false,
+            source: TimecodeSource::default(),
+            midi_device: String::new(),
+            drive_timeline: false,
+            max_drift_ms: 150,
+        }
+    }
+}
+
+/// Deinterlacing filter for interlaced (broadcast) sources
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Deinterlace {
+    Off,
+    /// Fast, fine for most material
+    Yadif,
+    /// Sharper and steadier than yadif, a little slower
+    Bwdif,
+}
+
+impl Default for Deinterlace {
+    fn default() -> Self {
+        Deinterlace::Off
+    }
+}
+
+/// Extra video steps when FFmpeg converts clips (import and split)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConversionConfig {
+    /// Only frames flagged as interlaced are processed
+    pub deinterlace: Deinterlace,
+    /// Output frame rate; None keeps the source's
+    pub frame_rate: Option<u32>,
+    /// Motion-interpolate to the new frame rate instead of dropping or
+    /// repeating frames. Much slower.
+    pub interpolate: bool,
+    /// Tone-map HDR (PQ / HLG) sources to SDR BT.709 so they don't look
+    /// washed out on the output screens
+    #[serde(default = "default_tone_map")]
+    pub tone_map: bool,
+}
+
+fn default_tone_map() -> bool {
+    true
+}
+
+impl Default for ConversionConfig {
+    fn default() -> Self {
+        ConversionConfig {
+            deinterlace: Deinterlace::default(),
+            frame_rate: None,
+            interpolate: false,
+            tone_map: default_tone_map(),
+        }
+    }
+}
+
+/// Release feed the updater follows
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+impl Default for UpdateChannel {
+    fn default() -> Self {
+        UpdateChannel::Stable
+    }
+}
+
+/// Application updates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UpdateConfig {
+    pub channel: UpdateChannel,
+    /// Look for an update shortly after launch
+    pub check_on_startup: bool,
+    /// Install a found update without asking (never while production runs)
+    pub auto_install: bool,
+    /// Show period: report updates but refuse to install them
+    pub defer: bool,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        UpdateConfig {
+            channel: UpdateChannel::default(),
+            check_on_startup: true,
+            auto_install: false,
+            defer: false,
+        }
+    }
+}
+
+/// Global shortcuts for emergency output control.
+/// Accelerator strings use the Tauri format, e.g. "CmdOrControl+Shift+B".
+/// An empty string disables that shortcut.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HotkeyConfig {
+    pub blackout: String,
+    pub freeze: String,
+    pub restore: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        HotkeyConfig {
+            blackout: "CmdOrControl+Shift+B".to_string(),
+            freeze: "CmdOrControl+Shift+F".to_string(),
+            restore: "CmdOrControl+Shift+R".to_string(),
+        }
+    }
+}
+
+/// Monitor-less fixture that plays sound files from DMX
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioFixtureConfig {
+    pub enabled: bool,
+    /// Clip at start_channel, volume at +1, playtype at +2
+    pub start_channel: u16,
+    pub media_folder: PathBuf,
+    /// Output device name as reported by get_audio_devices; empty = system default
+    pub output_device: String,
+}
+
+impl Default for AudioFixtureConfig {
+    fn default() -> Self {
+        AudioFixtureConfig {
+            enabled: false,
+            start_channel: 20,
+            media_folder: PathBuf::new(),
+            output_device: String::new(),
+        }
+    }
+}
+
+impl AudioFixtureConfig {
+    pub fn clip_channel(&self) -> u16 {
+        self.start_channel
+    }
+    
+    pub fn volume_channel(&self) -> u16 {
+        self.start_channel + 1
+    }
+    
+    pub fn playtype_channel(&self) -> u16 {
+        self.start_channel + 2
+    }
+}
+
+/// What the audio-reactive mode drives
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AudioReactiveMode {
+    /// Input level sets the monitor's dimmer
+    Dimmer,
+    /// Each detected beat moves to the next clip in the range
+    AdvanceClip,
+}
+
+impl Default for AudioReactiveMode {
+    fn default() -> Self {
+        AudioReactiveMode::Dimmer
+    }
+}
+
+/// Drive a monitor's channels from an audio input, for installations with
+/// music but no lighting console
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioReactiveConfig {
+    pub enabled: bool,
+    /// Input device name as reported by get_audio_inputs; empty = system default
+    pub input_device: String,
+    pub monitor_id: String,
+    pub mode: AudioReactiveMode,
+    /// Higher reacts to quieter input (1.0 = normal)
+    pub sensitivity: f32,
+    /// Dimmer mode: level shown when the input is silent
+    pub min_dimmer: u8,
+    /// AdvanceClip mode: clips cycled through on beats
+    pub first_clip: u8,
+    pub last_clip: u8,
+}
+
+impl Default for AudioReactiveConfig {
+    fn default() -> Self {
+        AudioReactiveConfig {
+            enabled: false,
+            input_device: String::new(),
+            monitor_id: "monitor1".to_string(),
+            mode: AudioReactiveMode::default(),
+            sensitivity: 1.0,
+            min_dimmer: 0,
+            first_clip: 1,
+            last_clip: 10,
+        }
+    }
+}
+
+/// What a folder picked in select_folder is for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum FolderPurpose {
+    Media,
+    Convert,
+    Presentation,
+}
+
+/// Last folder chosen in the picker for each purpose, so the next pick
+/// starts there. Maintained by the backend.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LastFolders {
+    pub media: Option<PathBuf>,
+    pub convert: Option<PathBuf>,
+    pub presentation: Option<PathBuf>,
+}
+
+impl LastFolders {
+    pub fn get(&self, purpose: FolderPurpose) -> Option<&PathBuf> {
+        match purpose {
+            FolderPurpose::Media => self.media.as_ref(),
+            FolderPurpose::Convert => self.convert.as_ref(),
+            FolderPurpose::Presentation => self.presentation.as_ref(),
+        }
+    }
+    
+    pub fn set(&mut self, purpose: FolderPurpose, folder: PathBuf) {
+        match purpose {
+            FolderPurpose::Media => self.media = Some(folder),
+            FolderPurpose::Convert => self.convert = Some(folder),
+            FolderPurpose::Presentation => self.presentation = Some(folder),
+        }
+    }
+}
+
 /// Main application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -150,6 +1306,58 @@ pub struct AppConfig {
     pub presentation_folder: PathBuf,
     #[serde(default)]
     pub convert_folder: PathBuf,
+    #[serde(default)]
+    pub conversion: ConversionConfig,
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    #[serde(default)]
+    pub feedback: FeedbackConfig,
+    #[serde(default)]
+    pub playback_profile: PlaybackProfile,
+    /// Hardening for long-running installs: no sleep, no notifications,
+    /// outputs kept on top and reopened, window closing blocked
+    #[serde(default)]
+    pub kiosk_mode: bool,
+    /// Output windows ignore the mouse, so stray clicks can't focus or move them
+    #[serde(default)]
+    pub output_click_through: bool,
+    #[serde(default)]
+    pub output_placement: OutputPlacement,
+    #[serde(default)]
+    pub audio_fixture: AudioFixtureConfig,
+    #[serde(default)]
+    pub clip_finished_trigger: TriggerOutputConfig,
+    #[serde(default)]
+    pub last_folders: LastFolders,
+    #[serde(default)]
+    pub updates: UpdateConfig,
+    #[serde(default)]
+    pub cue_stack: CueStackConfig,
+    #[serde(default)]
+    pub audio_reactive: AudioReactiveConfig,
+    #[serde(default)]
+    pub timeline: TimelineConfig,
+    #[serde(default)]
+    pub macros: MacroConfig,
+    #[serde(default)]
+    pub scripting: ScriptingConfig,
+    #[serde(default)]
+    pub osc_input: OscInputConfig,
+    #[serde(default)]
+    pub midi_input: MidiInputConfig,
+    #[serde(default)]
+    pub http_api: HttpApiConfig,
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+    #[serde(default)]
+    pub timecode: TimecodeConfig,
+    /// Warn when a media or convert folder's drive has less free space (MB)
+    #[serde(default = "default_low_disk_space_mb")]
+    pub low_disk_space_mb: u64,
+}
+
+fn default_low_disk_space_mb() -> u64 {
+    2048
 }
 
 impl Default for AppConfig {
@@ -171,6 +1379,28 @@ impl Default for AppConfig {
             production_mode: false,
             presentation_folder: PathBuf::new(),
             convert_folder: PathBuf::new(),
+            conversion: ConversionConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            feedback: FeedbackConfig::default(),
+            playback_profile: PlaybackProfile::default(),
+            kiosk_mode: false,
+            output_click_through: false,
+            output_placement: OutputPlacement::default(),
+            audio_fixture: AudioFixtureConfig::default(),
+            clip_finished_trigger: TriggerOutputConfig::default(),
+            last_folders: LastFolders::default(),
+            updates: UpdateConfig::default(),
+            cue_stack: CueStackConfig::default(),
+            audio_reactive: AudioReactiveConfig::default(),
+            timeline: TimelineConfig::default(),
+            macros: MacroConfig::default(),
+            scripting: ScriptingConfig::default(),
+            osc_input: OscInputConfig::default(),
+            midi_input: MidiInputConfig::default(),
+            http_api: HttpApiConfig::default(),
+            mqtt: MqttConfig::default(),
+            timecode: TimecodeConfig::default(),
+            low_disk_space_mb: default_low_disk_space_mb(),
         }
     }
 }
@@ -178,25 +1408,51 @@ impl Default for AppConfig {
 /// Media file information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaFile {
-    pub dmx_value: u8,
+    /// Clip number: 1-255, or up to 65534 for five-digit names
+    pub dmx_value: u16,
     pub filename: String,
     pub path: PathBuf,
     pub media_type: MediaType,
 }
 
+/// A numbered file in a media folder, with anything that stops it playing
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaEntry {
+    /// Prefix as written; values outside 1-254 are listed but never shown
+    pub dmx_value: u16,
+    pub name: String,
+    pub path: PathBuf,
+    /// None for file types the player doesn't support
+    pub media_type: Option<MediaType>,
+    /// Bytes
+    pub size: u64,
+    /// Seconds, for videos when ffprobe is available
+    pub duration: Option<f64>,
+    pub warnings: Vec<String>,
+}
+
 /// Media file type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MediaType {
     Video,
     Image,
+    Audio,
+    /// ".url" file pointing at a web page shown live in the output
+    Web,
+    /// ".capture" file naming a camera or capture card
+    Capture,
+    /// ".stream" file with an RTSP or NDI source
+    Stream,
+    /// File handled by a registered media source plugin
+    Plugin,
 }
 
-/// DMX update event
+/// Levels from one packet, delivered and emitted as one dmx-update event
 #[derive(Debug, Clone, Serialize)]
 pub struct DmxUpdate {
     pub universe: u16,
-    pub channel: u16,
-    pub value: u8,
+    /// (channel, value) pairs; channels count from 1
+    pub channels: Vec<(u16, u8)>,
 }
 
 /// Network interface information
@@ -207,11 +1463,74 @@ pub struct NetworkInterface {
 }
 
 impl AppConfig {
-    /// Get the path to the configuration file (%APPDATA%\StagePlayer DMX\configuration.json)
+    /// Refuse a patch where one channel drives two functions of the enabled
+    /// monitors and audio fixture, or a function sits outside 1-512
+    pub fn check_channel_patch(&self) -> Result<(), String> {
+        let functions = self.channel_functions();
+        for (i, (owner, name, channel)) in functions.iter().enumerate() {
+            if !(1..=512).contains(channel) {
+                return Err(format!("{} {} channel {} is outside 1-512", owner, name, channel));
+            }
+            if let Some((other_owner, other_name, _)) = functions[..i].iter().find(|(_, _, c)| c == channel) {
+                return Err(format!(
+                    "Channel {} is patched to both {} {} and {} {}",
+                    channel, other_owner, other_name, owner, name
+                ));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Every channel the player responds to, with what owns it and the
+    /// function it drives: the enabled monitors, the audio fixture, the
+    /// cue stack's Go channel and the macro control channel
+    pub fn channel_functions(&self) -> Vec<(String, &'static str, u16)> {
+        let mut functions: Vec<(String, &'static str, u16)> = Vec::new();
+        for monitor in [&self.monitor1, &self.monitor2].into_iter().filter(|m| m.enabled) {
+            functions.extend(monitor.channel_functions().into_iter().map(|(name, channel)| (monitor.name.clone(), name, channel)));
+        }
+        let audio = &self.audio_fixture;
+        if audio.enabled {
+            functions.extend([
+                ("Audio fixture".to_string(), "clip", audio.clip_channel()),
+                ("Audio fixture".to_string(), "volume", audio.volume_channel()),
+                ("Audio fixture".to_string(), "playtype", audio.playtype_channel()),
+            ]);
+        }
+        if self.cue_stack.enabled && self.cue_stack.go_channel != 0 {
+            functions.push(("Cue stack".to_string(), "go", self.cue_stack.go_channel));
+        }
+        if self.macros.enabled && self.macros.control_channel != 0 {
+            functions.push(("Macros".to_string(), "control", self.macros.control_channel));
+        }
+        functions
+    }
+    
+    /// Look up a monitor by the id used for its output window ("monitor1" / "monitor2")
+    pub fn monitor(&self, monitor_id: &str) -> Option<&MonitorConfig> {
+        match monitor_id {
+            "monitor1" => Some(&self.monitor1),
+            "monitor2" => Some(&self.monitor2),
+            _ => None,
+        }
+    }
+    
+    /// Get the path to the configuration file (%APPDATA%\StagePlayer DMX\configuration.json).
+    /// On Linux/macOS (e.g. a Raspberry Pi player) this is
+    /// $XDG_CONFIG_HOME/StagePlayer DMX or ~/.config/StagePlayer DMX instead.
     pub fn get_config_path() -> Result<PathBuf, String> {
-        let appdata = std::env::var("APPDATA")
-            .map_err(|_| "APPDATA environment variable not set".to_string())?;
-        let dir = PathBuf::from(appdata).join("StagePlayer DMX");
+        let base = if cfg!(windows) {
+            std::env::var("APPDATA")
+                .map(PathBuf::from)
+                .map_err(|_| "APPDATA environment variable not set".to_string())?
+        } else if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+            PathBuf::from(xdg)
+        } else {
+            std::env::var("HOME")
+                .map(|home| PathBuf::from(home).join(".config"))
+                .map_err(|_| "HOME environment variable not set".to_string())?
+        };
+        let dir = base.join("StagePlayer DMX");
         std::fs::create_dir_all(&dir)
             .map_err(|e| format!("Failed to create config directory: {}", e))?;
         Ok(dir.join("configuration.json"))
@@ -255,3 +1574,29 @@ impl AppConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_type_band_edges() {
+        let cases = [
+            (0, PlayType::Loop),
+            (63, PlayType::Loop),
+            (64, PlayType::Pause),
+            (95, PlayType::Pause),
+            (96, PlayType::Restart),
+            (127, PlayType::Restart),
+            (128, PlayType::FreezeLastFrame),
+            (159, PlayType::FreezeLastFrame),
+            (160, PlayType::PlayOnce),
+            (191, PlayType::PlayOnce),
+            (192, PlayType::Slideshow),
+            (255, PlayType::Slideshow),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(PlayType::from_dmx(value), expected, "playtype {}", value);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::config::{AppConfig, Cue, CueLook, MonitorConfig};
+use crate::output_window;
+use crate::sacn_listener::DmxFrame;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Where the cue stack is; shared by the Go channel driver and commands
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CueState {
+    /// Index of the last cue run
+    pub current: Option<usize>,
+    pub current_name: Option<String>,
+    /// A cue's fades are still running
+    pub running: bool,
+}
+
+/// Spawn the Go channel driver.
+///
+/// While a listener is running and the cue stack is enabled, each time the
+/// Go channel moves from below 128 to 128 or above the next cue runs.
+pub fn spawn_cue_driver(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+    cue_state: Arc<Mutex<CueState>>,
+) {
+    std::thread::spawn(move || {
+        let mut was_high = false;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(25));
+
+            let go_channel = {
+                let cfg = config.lock().unwrap();
+                if !cfg.cue_stack.enabled {
+                    was_high = false;
+                    continue;
+                }
+                cfg.cue_stack.go_channel
+            };
+            let (value, listening) = {
+                let frame = dmx_frame.lock().unwrap();
+                (frame.values.get(go_channel as usize).copied().unwrap_or(0), frame.listening_since.is_some())
+            };
+            if go_channel == 0 || !listening {
+                was_high = false;
+                continue;
+            }
+
+            let high = value >= 128;
+            if high && !was_high {
+                if let Err(e) = go(&app_handle, &config, &cue_state) {
+                    eprintln!("Cue GO: {}", e);
+                }
+            }
+            was_high = high;
+        }
+    });
+}
+
+/// Run the cue after the current one. Fades run on their own thread; a GO
+/// while they are still running is refused.
+pub fn go(app_handle: &AppHandle, config: &Arc<Mutex<AppConfig>>, cue_state: &Arc<Mutex<CueState>>) -> Result<(), String> {
+    let cfg = config.lock().unwrap().clone();
+    let mut state = cue_state.lock().unwrap();
+    if state.running {
+        return Err("previous cue is still fading".to_string());
+    }
+    let index = state.current.map(|i| i + 1).unwrap_or(0);
+    let Some(cue) = cfg.cue_stack.cues.get(index).cloned() else {
+        return Err("end of cue list".to_string());
+    };
+    state.current = Some(index);
+    state.current_name = Some(cue.name.clone());
+    state.running = true;
+    drop(state);
+
+    println!("Cue GO: {} '{}'", index + 1, cue.name);
+    let _ = app_handle.emit("cue-changed", &*cue_state.lock().unwrap());
+
+    let app_handle = app_handle.clone();
+    let cue_state = Arc::clone(cue_state);
+    std::thread::spawn(move || {
+        run_cue(&app_handle, &cfg, &cue);
+        cue_state.lock().unwrap().running = false;
+        let _ = app_handle.emit("cue-changed", &*cue_state.lock().unwrap());
+    });
+    Ok(())
+}
+
+/// Go back to before the first cue; outputs keep their current look
+pub fn reset(cue_state: &Mutex<CueState>) {
+    let mut state = cue_state.lock().unwrap();
+    state.current = None;
+    state.current_name = None;
+}
+
+fn run_cue(app_handle: &AppHandle, cfg: &AppConfig, cue: &Cue) {
+    let changes: Vec<(&str, &MonitorConfig, CueLook)> = [
+        ("monitor1", &cfg.monitor1, cue.monitor1),
+        ("monitor2", &cfg.monitor2, cue.monitor2),
+    ]
+    .into_iter()
+    .filter(|(_, monitor, _)| monitor.enabled)
+    .filter_map(|(monitor_id, monitor, look)| Some((monitor_id, monitor, look?)))
+    .collect();
+
+    if cue.fade_out_ms > 0 {
+        for (monitor_id, _, _) in &changes {
+            if let Err(e) = output_window::push_fade(app_handle, monitor_id, 0.0, cue.fade_out_ms) {
+                eprintln!("Failed to fade out {}: {}", monitor_id, e);
+            }
+        }
+        std::thread::sleep(Duration::from_millis(cue.fade_out_ms as u64));
+    }
+
+    for (monitor_id, monitor, look) in &changes {
+        show_look(app_handle, monitor_id, monitor, *look);
+        // Start from black only if there is a fade in; otherwise cut straight up
+        let start = if cue.fade_in_ms > 0 { 0.0 } else { 1.0 };
+        let _ = output_window::push_fade(app_handle, monitor_id, start, 0);
+        if let Err(e) = output_window::push_fade(app_handle, monitor_id, 1.0, cue.fade_in_ms) {
+            eprintln!("Failed to fade in {}: {}", monitor_id, e);
+        }
+    }
+    std::thread::sleep(Duration::from_millis(cue.fade_in_ms as u64));
+}
+
+/// Show a look on a monitor. It goes through the monitor's playback state
+/// like a DMX change, so the clip comes from the current bank and crossfades,
+/// and the UI loads it from the playback-state event. Also used by the
+/// timeline and macros.
+pub fn show_look(app_handle: &AppHandle, monitor_id: &str, monitor: &MonitorConfig, look: CueLook) {
+    let change = app_handle.state::<crate::AppState>().playback_states.lock().unwrap().show_look(monitor_id, &look);
+    let Some(change) = change else {
+        return;
+    };
+    // 0 = no clip, the close value = nothing (cues don't close the output window)
+    if change.after.media_url.is_none() && look.clip != 0 && look.clip != monitor.close_value() {
+        eprintln!("Cue clip {} not found in bank {} of {:?}", look.clip, change.after.bank.unwrap_or(0), monitor.media_folder);
+    }
+    crate::publish_state_change(app_handle, &change);
+}
@@ -0,0 +1,84 @@
+use crate::output_window;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Output windows get a new level at 60 fps while a dimmer is moving
+const FRAME: Duration = Duration::from_micros(16_667);
+
+/// Closer than this to the target (in DMX steps) counts as arrived
+const SETTLED: f32 = 0.1;
+
+struct Smoothed {
+    current: f32,
+    target: f32,
+    time_constant_ms: u32,
+}
+
+/// Dimmer levels being eased towards what the console last sent, per monitor.
+/// Console fades arrive as steps a few times a second; this turns them into
+/// a smooth curve before they reach the output windows.
+#[derive(Default)]
+pub struct DimmerSmoother {
+    monitors: HashMap<String, Smoothed>,
+}
+
+impl DimmerSmoother {
+    /// Set where a monitor's dimmer is heading and return the level to show
+    /// right now. A time constant of 0 turns smoothing off for the monitor.
+    pub fn set_target(&mut self, monitor_id: &str, target: u8, time_constant_ms: u32) -> f32 {
+        let target = target as f32;
+        if time_constant_ms == 0 {
+            self.monitors.remove(monitor_id);
+            return target;
+        }
+        let smoothed = self.monitors.entry(monitor_id.to_string()).or_insert(Smoothed {
+            current: target,
+            target,
+            time_constant_ms,
+        });
+        smoothed.target = target;
+        smoothed.time_constant_ms = time_constant_ms;
+        smoothed.current
+    }
+
+    /// Move every dimmer that hasn't arrived; returns the new levels
+    fn step(&mut self, elapsed: Duration) -> Vec<(String, f32)> {
+        let mut moved = Vec::new();
+        for (monitor_id, smoothed) in &mut self.monitors {
+            if smoothed.current == smoothed.target {
+                continue;
+            }
+            // Exponential approach: about 63% of the way per time constant
+            let elapsed_ms = elapsed.as_secs_f32() * 1000.0;
+            let share = 1.0 - (-elapsed_ms / smoothed.time_constant_ms as f32).exp();
+            smoothed.current += (smoothed.target - smoothed.current) * share;
+            if (smoothed.target - smoothed.current).abs() < SETTLED {
+                smoothed.current = smoothed.target;
+            }
+            moved.push((monitor_id.clone(), smoothed.current));
+        }
+        moved
+    }
+}
+
+/// Spawn the thread that steps the smoother and sends each moving dimmer
+/// to its output window
+pub fn spawn_smoother(app_handle: AppHandle, smoother: Arc<Mutex<DimmerSmoother>>) {
+    std::thread::spawn(move || {
+        let mut last = Instant::now();
+        loop {
+            std::thread::sleep(FRAME);
+            let now = Instant::now();
+            let moved = smoother.lock().unwrap().step(now - last);
+            last = now;
+
+            for (monitor_id, level) in moved {
+                if let Err(e) = output_window::push_dimmer(&app_handle, &monitor_id, level) {
+                    eprintln!("Failed to update dimmer on {}: {}", monitor_id, e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,86 @@
+use crate::config::AppConfig;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Free space on the drive behind one of the configured folders
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskSpace {
+    /// "monitor1", "monitor2" or "convert"
+    pub purpose: String,
+    pub folder: PathBuf,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    /// Below the configured low_disk_space_mb
+    pub low: bool,
+}
+
+/// Free space for each configured media and convert folder. Folders that
+/// aren't set or don't exist are left out.
+pub fn check(config: &AppConfig) -> Vec<DiskSpace> {
+    let threshold = config.low_disk_space_mb.saturating_mul(1024 * 1024);
+    let folders = [
+        ("monitor1", &config.monitor1.media_folder),
+        ("monitor2", &config.monitor2.media_folder),
+        ("convert", &config.convert_folder),
+    ];
+
+    folders.iter()
+        .filter(|(_, folder)| folder.is_dir())
+        .filter_map(|(purpose, folder)| {
+            let (free_bytes, total_bytes) = match space(folder) {
+                Ok(space) => space,
+                Err(e) => {
+                    eprintln!("Failed to read free space for {}: {}", folder.display(), e);
+                    return None;
+                }
+            };
+            Some(DiskSpace {
+                purpose: purpose.to_string(),
+                folder: folder.to_path_buf(),
+                free_bytes,
+                total_bytes,
+                low: free_bytes < threshold,
+            })
+        })
+        .collect()
+}
+
+fn space(folder: &Path) -> std::io::Result<(u64, u64)> {
+    Ok((fs2::available_space(folder)?, fs2::total_space(folder)?))
+}
+
+/// Spawn the disk space check.
+///
+/// Every minute the drives behind the media and convert folders are checked,
+/// and a "disk-space-low" event is emitted when one drops below the
+/// threshold. It is emitted once per drop, not every minute.
+pub fn spawn_disk_monitor(app_handle: AppHandle, config: Arc<Mutex<AppConfig>>) {
+    std::thread::spawn(move || {
+        let mut warned: Vec<PathBuf> = Vec::new();
+
+        loop {
+            let cfg = config.lock().unwrap().clone();
+            let spaces = check(&cfg);
+
+            warned.retain(|folder| spaces.iter().any(|s| s.low && &s.folder == folder));
+            for space in spaces.iter().filter(|s| s.low) {
+                if warned.contains(&space.folder) {
+                    continue;
+                }
+                eprintln!(
+                    "Low disk space for {} ({}): {} MB free",
+                    space.purpose,
+                    space.folder.display(),
+                    space.free_bytes / (1024 * 1024)
+                );
+                let _ = app_handle.emit("disk-space-low", space);
+                warned.push(space.folder.clone());
+            }
+
+            std::thread::sleep(Duration::from_secs(60));
+        }
+    });
+}
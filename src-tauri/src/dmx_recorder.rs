@@ -0,0 +1,190 @@
+use crate::config::DmxUpdate;
+use crate::sacn_listener::DmxFrame;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How often the live frame is checked for changes while recording
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(20);
+
+/// One line of a recording file (JSON lines): the channels that changed
+/// `t_ms` after recording started. The first line holds all 512 channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedFrame {
+    t_ms: u64,
+    changes: Vec<(u16, u8)>,
+}
+
+/// What a finished recording contains
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingSummary {
+    pub path: String,
+    pub frames: u64,
+    pub duration_secs: f64,
+}
+
+/// Writes every change of the live DMX frame to a file until stopped
+pub struct Recorder {
+    path: PathBuf,
+    running: Arc<Mutex<bool>>,
+    thread_handle: Option<JoinHandle<Result<(u64, u64), String>>>,
+}
+
+impl Recorder {
+    pub fn start(path: &Path, dmx_frame: Arc<Mutex<DmxFrame>>) -> Result<Self, String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+        let running = Arc::new(Mutex::new(true));
+        let running_thread = Arc::clone(&running);
+
+        let handle = std::thread::spawn(move || {
+            let mut writer = BufWriter::new(file);
+            let started = Instant::now();
+            let mut last: Option<[u8; 513]> = None;
+            let mut frames = 0u64;
+            let mut t_ms = 0u64;
+
+            while *running_thread.lock().unwrap() {
+                let values = dmx_frame.lock().unwrap().values;
+                let changes: Vec<(u16, u8)> = (1..=512)
+                    .filter(|&ch| !matches!(last, Some(last) if last[ch] == values[ch]))
+                    .map(|ch| (ch as u16, values[ch]))
+                    .collect();
+
+                if !changes.is_empty() {
+                    t_ms = started.elapsed().as_millis() as u64;
+                    let line = serde_json::to_string(&RecordedFrame { t_ms, changes })
+                        .map_err(|e| format!("Failed to encode frame: {}", e))?;
+                    writeln!(writer, "{}", line)
+                        .map_err(|e| format!("Failed to write recording: {}", e))?;
+                    frames += 1;
+                    last = Some(values);
+                }
+                std::thread::sleep(SAMPLE_INTERVAL);
+            }
+
+            writer.flush().map_err(|e| format!("Failed to write recording: {}", e))?;
+            Ok((frames, t_ms))
+        });
+
+        println!("Recording DMX to {:?}", path);
+        Ok(Self {
+            path: path.to_path_buf(),
+            running,
+            thread_handle: Some(handle),
+        })
+    }
+
+    /// Stop, close the file and report what was recorded
+    pub fn stop(mut self) -> Result<RecordingSummary, String> {
+        *self.running.lock().unwrap() = false;
+        let (frames, last_ms) = match self.thread_handle.take() {
+            Some(handle) => handle.join().map_err(|_| "Recorder thread panicked".to_string())??,
+            None => (0, 0),
+        };
+        println!("Recorded {} DMX frames to {:?}", frames, self.path);
+        Ok(RecordingSummary {
+            path: self.path.to_string_lossy().into_owned(),
+            frames,
+            duration_secs: last_ms as f64 / 1000.0,
+        })
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        *self.running.lock().unwrap() = false;
+    }
+}
+
+/// Plays a recording back through a `DmxUpdate` callback, keeping its timing
+pub struct Replay {
+    running: Arc<Mutex<bool>>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl Replay {
+    /// Start replaying `path` as updates on `universe`; with `looped` it
+    /// starts over at the end until stopped
+    pub fn start(
+        path: &Path,
+        universe: u16,
+        looped: bool,
+        callback: impl Fn(DmxUpdate) + Send + 'static,
+    ) -> Result<Self, String> {
+        let frames = load(path)?;
+        if frames.is_empty() {
+            return Err(format!("{:?} contains no DMX frames", path));
+        }
+        let running = Arc::new(Mutex::new(true));
+        let running_thread = Arc::clone(&running);
+
+        let handle = std::thread::spawn(move || {
+            loop {
+                let started = Instant::now();
+                for frame in &frames {
+                    // Sleep in short steps so stop() is noticed promptly
+                    let due = started + Duration::from_millis(frame.t_ms);
+                    while Instant::now() < due {
+                        if !*running_thread.lock().unwrap() {
+                            return;
+                        }
+                        std::thread::sleep((due - Instant::now()).min(Duration::from_millis(50)));
+                    }
+                    if !*running_thread.lock().unwrap() {
+                        return;
+                    }
+                    callback(DmxUpdate { universe, channels: frame.changes.clone() });
+                }
+                if !looped {
+                    break;
+                }
+            }
+            *running_thread.lock().unwrap() = false;
+            println!("DMX replay finished");
+        });
+
+        println!("Replaying DMX from {:?}{}", path, if looped { " (looped)" } else { "" });
+        Ok(Self {
+            running,
+            thread_handle: Some(handle),
+        })
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self.running.lock().unwrap()
+    }
+
+    pub fn stop(&mut self) {
+        *self.running.lock().unwrap() = false;
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Replay {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn load(path: &Path) -> Result<Vec<RecordedFrame>, String> {
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut frames = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let frame: RecordedFrame = serde_json::from_str(&line)
+            .map_err(|e| format!("{:?} line {}: {}", path, i + 1, e))?;
+        frames.push(frame);
+    }
+    Ok(frames)
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Show a folder, or the folder holding a file with the file selected, in
+/// the system file manager
+pub fn open(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+
+    let mut command = command_for(path);
+    // Not waited on: Explorer's exit code is 1 even when it opens fine
+    command.spawn()
+        .map_err(|e| format!("Failed to open file manager: {}", e))?;
+    println!("Opened {} in file manager", path.display());
+    Ok(())
+}
+
+#[cfg(windows)]
+fn command_for(path: &Path) -> Command {
+    use std::os::windows::process::CommandExt;
+    let mut command = Command::new("explorer");
+    if path.is_file() {
+        // Explorer wants the quotes after the comma, which normal argument
+        // quoting would put around the whole switch
+        command.raw_arg(format!("/select,\"{}\"", path.display()));
+    } else {
+        command.arg(path);
+    }
+    command
+}
+
+#[cfg(target_os = "macos")]
+fn command_for(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    if path.is_file() {
+        command.arg("-R");
+    }
+    command.arg(path);
+    command
+}
+
+/// There is no common way to select a file, so its folder is opened
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn command_for(path: &Path) -> Command {
+    let folder = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    let mut command = Command::new("xdg-open");
+    command.arg(folder);
+    command
+}
@@ -0,0 +1,216 @@
+use crate::config::{DmxUpdate, SacnConfig};
+use crate::plugins::InputCallback;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Payload of the input-failover event
+#[derive(Debug, Clone, Serialize)]
+pub struct FailoverEvent {
+    /// Input now driving the player
+    pub active: String,
+    pub on_backup: bool,
+    pub message: String,
+}
+
+struct FailoverState {
+    /// Last update from sACN; the listener's start counts as one so the
+    /// backup doesn't take over before sACN had a chance
+    primary_last: Instant,
+    on_backup: bool,
+    /// Each input's current levels (channel N at index N - 1). Inputs only
+    /// send the channels that changed, so these are what a switch delivers.
+    primary_levels: [u8; 512],
+    backup_levels: [u8; 512],
+}
+
+impl FailoverState {
+    fn new() -> Self {
+        Self {
+            primary_last: Instant::now(),
+            on_backup: false,
+            primary_levels: [0; 512],
+            backup_levels: [0; 512],
+        }
+    }
+
+    /// Record an sACN update; returns what to deliver and whether sACN
+    /// just took back over from the backup
+    fn primary(&mut self, update: DmxUpdate) -> (DmxUpdate, bool) {
+        store(&mut self.primary_levels, &update);
+        self.primary_last = Instant::now();
+        if std::mem::replace(&mut self.on_backup, false) {
+            // Channels sACN left alone still hold the backup's levels
+            (full_frame(update.universe, &self.primary_levels), true)
+        } else {
+            (update, false)
+        }
+    }
+
+    /// Record a backup update; returns what to deliver (nothing while sACN
+    /// is live) and whether the backup just took over
+    fn backup(&mut self, update: DmxUpdate, timeout: Duration) -> (Option<DmxUpdate>, bool) {
+        store(&mut self.backup_levels, &update);
+        if self.on_backup {
+            (Some(update), false)
+        } else if self.primary_last.elapsed() >= timeout {
+            self.on_backup = true;
+            // The backup's look, not just what changed in its last packet
+            (Some(full_frame(update.universe, &self.backup_levels)), true)
+        } else {
+            (None, false)
+        }
+    }
+}
+
+fn store(levels: &mut [u8; 512], update: &DmxUpdate) {
+    for &(channel, value) in &update.channels {
+        if (1..=512).contains(&channel) {
+            levels[channel as usize - 1] = value;
+        }
+    }
+}
+
+/// All 512 channels, so every channel the other input set is overwritten
+fn full_frame(universe: u16, levels: &[u8; 512]) -> DmxUpdate {
+    DmxUpdate {
+        universe,
+        channels: levels.iter().enumerate().map(|(i, &level)| (i as u16 + 1, level)).collect(),
+    }
+}
+
+/// Lets a backup input through only while sACN (the primary) is silent,
+/// and switches back as soon as sACN returns
+pub struct Failover {
+    app_handle: AppHandle,
+    backup: String,
+    timeout: Duration,
+    state: Mutex<FailoverState>,
+}
+
+impl Failover {
+    pub fn new(app_handle: AppHandle, config: &SacnConfig) -> Arc<Self> {
+        Arc::new(Self {
+            app_handle,
+            backup: config.failover_input.clone(),
+            timeout: Duration::from_secs_f32(config.failover_timeout_secs.max(0.1)),
+            state: Mutex::new(FailoverState::new()),
+        })
+    }
+
+    /// Wrap the sACN listener's callback
+    pub fn primary(self: &Arc<Self>, deliver: InputCallback) -> InputCallback {
+        let failover = Arc::clone(self);
+        Arc::new(move |update: DmxUpdate| {
+            let (update, restored) = failover.state.lock().unwrap().primary(update);
+            if restored {
+                failover.emit("sACN", false, format!("sACN is back; switched from backup input '{}' to sACN", failover.backup));
+            }
+            deliver(update);
+        })
+    }
+
+    /// Wrap the backup input's callback; its updates are dropped while sACN is live
+    pub fn backup(self: &Arc<Self>, deliver: InputCallback) -> InputCallback {
+        let failover = Arc::clone(self);
+        Arc::new(move |update: DmxUpdate| {
+            let (update, switched) = failover.state.lock().unwrap().backup(update, failover.timeout);
+            if switched {
+                failover.emit(&failover.backup, true, format!("No sACN for {:.1}s; switched to backup input '{}'",
+                    failover.timeout.as_secs_f32(), failover.backup));
+            }
+            if let Some(update) = update {
+                deliver(update);
+            }
+        })
+    }
+
+    fn emit(&self, active: &str, on_backup: bool, message: String) {
+        if on_backup {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+        let _ = self.app_handle.emit("input-failover", FailoverEvent {
+            active: active.to_string(),
+            on_backup,
+            message,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::playback_state::PlaybackStates;
+    use crate::sacn_listener::LevelCache;
+
+    fn look(channels: &[(usize, u8)]) -> [u8; 512] {
+        let mut levels = [0u8; 512];
+        for &(channel, value) in channels {
+            levels[channel - 1] = value;
+        }
+        levels
+    }
+
+    /// A packet from an input, as its listener's cache passes it on
+    fn packet(cache: &mut LevelCache, levels: [u8; 512]) -> DmxUpdate {
+        DmxUpdate { universe: 1, channels: cache.changes(levels) }
+    }
+
+    #[test]
+    fn switches_deliver_the_whole_look_of_the_new_input() {
+        let timeout = Duration::from_millis(50);
+        let sacn_look = look(&[(1, 10), (2, 20)]);
+        let backup_look = look(&[(1, 200), (3, 30)]);
+        let (mut sacn, mut backup) = (LevelCache::default(), LevelCache::default());
+        let mut state = FailoverState::new();
+        // What the player was sent, channel N at index N - 1
+        let mut output = [0u8; 512];
+
+        let (update, _) = state.primary(packet(&mut sacn, sacn_look));
+        store(&mut output, &update);
+        // The backup sends the same static look throughout, so after its
+        // first packet its updates are empty
+        for _ in 0..2 {
+            let (update, switched) = state.backup(packet(&mut backup, backup_look), timeout);
+            assert!(update.is_none() && !switched);
+        }
+
+        std::thread::sleep(timeout + Duration::from_millis(20));
+        let (update, switched) = state.backup(packet(&mut backup, backup_look), timeout);
+        assert!(switched);
+        store(&mut output, &update.unwrap());
+        assert_eq!(output, backup_look);
+
+        // sACN comes back well within its cache's resend gap, unchanged
+        let (update, restored) = state.primary(packet(&mut sacn, sacn_look));
+        assert!(restored);
+        store(&mut output, &update);
+        assert_eq!(output, sacn_look);
+        let (update, switched) = state.backup(packet(&mut backup, backup_look), timeout);
+        assert!(update.is_none() && !switched);
+    }
+
+    #[test]
+    fn backup_look_reaches_the_monitors() {
+        let timeout = Duration::from_millis(20);
+        let mut monitors = PlaybackStates::new(&AppConfig::default());
+        let (mut sacn, mut backup) = (LevelCache::default(), LevelCache::default());
+        let mut state = FailoverState::new();
+
+        // sACN: clip 1 at full on monitor 1; backup: clip 2 at half
+        let backup_look = look(&[(1, 2), (2, 128)]);
+        monitors.apply(&state.primary(packet(&mut sacn, look(&[(1, 1), (2, 255)]))).0);
+        state.backup(packet(&mut backup, backup_look), timeout);
+
+        std::thread::sleep(timeout + Duration::from_millis(20));
+        let (update, _) = state.backup(packet(&mut backup, backup_look), timeout);
+        let changed = monitors.apply(&update.unwrap());
+        assert_eq!(changed.len(), 1);
+        let monitor = monitors.get("monitor1").unwrap();
+        assert_eq!((monitor.clip, monitor.dimmer), (2, 128));
+    }
+}
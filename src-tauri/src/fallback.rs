@@ -0,0 +1,160 @@
+use crate::config::{AppConfig, DataLossAction, MonitorConfig};
+use crate::output_window;
+use crate::playback_state;
+use crate::sacn_listener::DmxFrame;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sacn::packet::E131_NETWORK_DATA_LOSS_TIMEOUT;
+use tauri::{AppHandle, Emitter};
+
+/// Payload for the fallback-activated / fallback-cleared events
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FallbackEvent {
+    pub monitor_id: String,
+    pub clip: Option<u16>,
+}
+
+/// Payload for the dmx-signal-lost / dmx-signal-restored events
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SignalLossEvent {
+    pub universe: u16,
+    pub action: DataLossAction,
+}
+
+/// Spawn the signal-loss watchdog.
+///
+/// While a listener is running and no DMX has arrived for the E1.31 data
+/// loss timeout, dmx-signal-lost is emitted and `sacn.on_data_loss` applied.
+/// With `FallbackClip`, each monitor with a `fallback_clip` switches to that
+/// clip once no valid sACN has arrived for `fallback_timeout_secs`.
+/// As soon as data returns the live look from the latest frame is restored.
+pub fn spawn_watchdog(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        let mut active = [false, false];
+        // Action applied for the current loss, None while data is arriving
+        let mut lost: Option<DataLossAction> = None;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(100));
+
+            let cfg = config.lock().unwrap().clone();
+            let (values, last_received, listening_since) = {
+                let frame = dmx_frame.lock().unwrap();
+                (frame.values, frame.last_received, frame.listening_since)
+            };
+            let monitors = [("monitor1", &cfg.monitor1), ("monitor2", &cfg.monitor2)];
+
+            let universe_lost = listening_since.is_some_and(|since| {
+                let last_activity = last_received.filter(|t| *t >= since).unwrap_or(since);
+                Instant::now().duration_since(last_activity) >= E131_NETWORK_DATA_LOSS_TIMEOUT
+            });
+            if universe_lost && lost.is_none() {
+                let action = cfg.sacn.on_data_loss;
+                println!("No DMX on universe {} for {:?}: {:?}", cfg.sacn.universe, E131_NETWORK_DATA_LOSS_TIMEOUT, action);
+                if action == DataLossAction::FadeToBlack {
+                    let fade_ms = (cfg.sacn.data_loss_fade_secs.max(0.0) * 1000.0) as u32;
+                    for (monitor_id, _) in &monitors {
+                        if let Err(e) = output_window::push_fade(&app_handle, monitor_id, 0.0, fade_ms) {
+                            eprintln!("Failed to fade out {}: {}", monitor_id, e);
+                        }
+                    }
+                }
+                let _ = app_handle.emit("dmx-signal-lost", &SignalLossEvent { universe: cfg.sacn.universe, action });
+                lost = Some(action);
+            } else if !universe_lost {
+                if let Some(action) = lost.take() {
+                    if action == DataLossAction::FadeToBlack {
+                        for (monitor_id, _) in &monitors {
+                            let _ = output_window::push_fade(&app_handle, monitor_id, 1.0, 0);
+                        }
+                    }
+                    // A stopped listener isn't a restored signal
+                    if listening_since.is_some() {
+                        println!("DMX restored on universe {}", cfg.sacn.universe);
+                        let _ = app_handle.emit("dmx-signal-restored", &SignalLossEvent { universe: cfg.sacn.universe, action });
+                    }
+                }
+            }
+
+            for (i, (monitor_id, monitor)) in monitors.iter().enumerate() {
+                let fallback_clip = match monitor.fallback_clip {
+                    Some(clip) if monitor.enabled && clip > 0 && cfg.sacn.on_data_loss == DataLossAction::FallbackClip => clip,
+                    _ => {
+                        if active[i] {
+                            restore_live_look(&app_handle, monitor_id, monitor, &values);
+                            active[i] = false;
+                        }
+                        continue;
+                    }
+                };
+
+                // Listener stopped: production is over, nothing to restore
+                let Some(since) = listening_since else {
+                    active[i] = false;
+                    continue;
+                };
+
+                let last_activity = last_received.filter(|t| *t >= since).unwrap_or(since);
+                let timeout = Duration::from_secs(monitor.fallback_timeout_secs as u64);
+                let signal_lost = Instant::now().duration_since(last_activity) >= timeout;
+
+                if signal_lost && !active[i] {
+                    println!("No sACN for {}s on {}, playing fallback clip {}",
+                        monitor.fallback_timeout_secs, monitor_id, fallback_clip);
+                    play_fallback(&app_handle, monitor_id, monitor, fallback_clip, &values);
+                    active[i] = true;
+                } else if !signal_lost && active[i] {
+                    println!("sACN restored on {}, leaving fallback clip", monitor_id);
+                    restore_live_look(&app_handle, monitor_id, monitor, &values);
+                    active[i] = false;
+                }
+            }
+        }
+    });
+}
+
+fn play_fallback(app_handle: &AppHandle, monitor_id: &str, monitor: &MonitorConfig, clip: u16, values: &[u8; 513]) {
+    // From the bank the console had selected before the signal went
+    let bank = monitor.bank_channel.map(|ch| values.get(ch as usize).copied().unwrap_or(0));
+    let media_url = playback_state::clip_url(monitor, clip, bank);
+    if media_url.is_none() {
+        eprintln!("Fallback clip {} not found in bank {} of {:?}", clip, bank.unwrap_or(0), monitor.media_folder);
+    }
+
+    let orientation = format!("{:?}", monitor.orientation);
+    if let Err(e) = output_window::push_media_update(app_handle, monitor_id, media_url.as_deref(), 255, 0, &orientation, None, None) {
+        eprintln!("Failed to show fallback clip on {}: {}", monitor_id, e);
+    }
+
+    let _ = app_handle.emit("fallback-activated", &FallbackEvent {
+        monitor_id: monitor_id.to_string(),
+        clip: Some(clip),
+    });
+}
+
+fn restore_live_look(app_handle: &AppHandle, monitor_id: &str, monitor: &MonitorConfig, values: &[u8; 513]) {
+    let channel = |ch: u16| values.get(ch as usize).copied().unwrap_or(0);
+    let dimmer = channel(monitor.dimmer_channel());
+    let playtype = channel(monitor.playtype_channel());
+
+    let bank = monitor.bank_channel.map(channel);
+
+    // Same lookup as live playback: no file for 0 or the close value, and
+    // the clip comes from the bank the console has selected
+    let media_url = playback_state::clip_url(monitor, monitor.clip_value(values), bank);
+
+    let slideshow = output_window::resolve_slideshow(monitor, media_url.as_deref(), playtype);
+    let orientation = format!("{:?}", monitor.orientation);
+    if let Err(e) = output_window::push_media_update(app_handle, monitor_id, media_url.as_deref(), dimmer, playtype, &orientation, slideshow.as_ref(), None) {
+        eprintln!("Failed to restore live look on {}: {}", monitor_id, e);
+    }
+
+    let _ = app_handle.emit("fallback-cleared", &FallbackEvent {
+        monitor_id: monitor_id.to_string(),
+        clip: None,
+    });
+}
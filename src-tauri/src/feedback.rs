@@ -0,0 +1,131 @@
+use crate::config::AppConfig;
+use crate::playback::{MonitorPlayback, PlaybackHealth, PlaybackTracker};
+use sacn::source::SacnSource;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Channels used per monitor in the feedback universe
+pub const CHANNELS_PER_MONITOR: u16 = 8;
+
+/// Spawn the sACN feedback sender.
+///
+/// While `feedback.enabled` is set, the state of each monitor is transmitted
+/// at 10 Hz on the feedback universe. Monitor 1 occupies the first block of
+/// `CHANNELS_PER_MONITOR` channels from `start_channel`, monitor 2 the next:
+///
+/// | Offset | Meaning                                               |
+/// |--------|-------------------------------------------------------|
+/// | +0     | Current clip (DMX value, 0 = none)                    |
+/// | +1     | Dimmer                                                |
+/// | +2     | Playtype                                              |
+/// | +3     | Progress through the clip (0-255)                     |
+/// | +4/+5  | Elapsed seconds, 16-bit coarse/fine                   |
+/// | +6     | Health: 0 no output, 1 idle, 2 playing, 3 paused, 4 error |
+/// | +7     | Reserved                                              |
+pub fn spawn_feedback_sender(
+    config: Arc<Mutex<AppConfig>>,
+    playback: Arc<Mutex<PlaybackTracker>>,
+) {
+    std::thread::spawn(move || {
+        let mut source: Option<(SacnSource, u16)> = None;
+        let mut warned_conflict = false;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(100));
+
+            let cfg = config.lock().unwrap().clone();
+            let feedback = cfg.feedback;
+
+            if !feedback.enabled {
+                if source.take().is_some() {
+                    println!("sACN feedback sender stopped");
+                }
+                continue;
+            }
+
+            // Never transmit on the universe we're listening to
+            if feedback.universe == cfg.sacn.universe {
+                if !warned_conflict {
+                    eprintln!("Feedback universe {} is the listen universe; feedback disabled", feedback.universe);
+                    warned_conflict = true;
+                }
+                source = None;
+                continue;
+            }
+            warned_conflict = false;
+
+            // (Re)create the source when enabled or when the universe changes
+            if source.as_ref().map(|(_, universe)| *universe) != Some(feedback.universe) {
+                source = None;
+                match create_source(feedback.universe) {
+                    Ok(s) => source = Some((s, feedback.universe)),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        continue;
+                    }
+                }
+            }
+
+            let (monitor1, monitor2) = {
+                let tracker = playback.lock().unwrap();
+                (tracker.status("monitor1"), tracker.status("monitor2"))
+            };
+
+            let mut dmx_data = [0u8; 513];
+            write_monitor_block(&mut dmx_data, feedback.start_channel, &monitor1);
+            write_monitor_block(&mut dmx_data, feedback.start_channel + CHANNELS_PER_MONITOR, &monitor2);
+
+            if let Some((s, universe)) = source.as_mut() {
+                if let Err(e) = s.send(&[*universe], &dmx_data, Some(feedback.priority), None, None) {
+                    eprintln!("Failed to send sACN feedback: {}", e);
+                }
+            }
+        }
+    });
+}
+
+fn create_source(universe: u16) -> Result<SacnSource, String> {
+    let mut source = SacnSource::with_ip("StagePlayer DMX Feedback", SocketAddr::from(([0, 0, 0, 0], 0)))
+        .map_err(|e| format!("Failed to create sACN feedback source: {}", e))?;
+    source.register_universe(universe)
+        .map_err(|e| format!("Failed to register feedback universe {}: {}", universe, e))?;
+    println!("sACN feedback sender started on universe {}", universe);
+    Ok(source)
+}
+
+/// Encode one monitor's state into the frame; channel N lives at index N
+fn write_monitor_block(dmx_data: &mut [u8; 513], start_channel: u16, status: &MonitorPlayback) {
+    let progress = if status.duration > 0.0 {
+        ((status.position / status.duration).clamp(0.0, 1.0) * 255.0).round() as u8
+    } else {
+        0
+    };
+    let elapsed = status.position.clamp(0.0, u16::MAX as f64) as u16;
+    let health = match status.health {
+        PlaybackHealth::NoOutput => 0,
+        PlaybackHealth::Idle => 1,
+        PlaybackHealth::Playing if status.paused => 3,
+        PlaybackHealth::Playing => 2,
+        PlaybackHealth::Error => 4,
+    };
+
+    // One channel per value; 16-bit clips above 255 report as 255
+    let values = [
+        status.clip.min(u8::MAX as u16) as u8,
+        status.dimmer,
+        status.playtype,
+        progress,
+        (elapsed >> 8) as u8,
+        (elapsed & 0xFF) as u8,
+        health,
+        0,
+    ];
+
+    for (offset, value) in values.iter().enumerate() {
+        let channel = start_channel as usize + offset;
+        if (1..=512).contains(&channel) {
+            dmx_data[channel] = *value;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::config::{AppConfig, Generator, MonitorConfig};
+use crate::output_window;
+use crate::sacn_listener::DmxFrame;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// What the output window's generator layer should draw
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum GeneratorFrame {
+    SolidColor { red: u8, green: u8, blue: u8, dimmer: u8 },
+    Strobe { rate: u8, red: u8, green: u8, blue: u8, dimmer: u8 },
+    Gradient { from: [u8; 3], to: [u8; 3], angle: u8, dimmer: u8 },
+    Noise { speed: u8, grain: u8, dimmer: u8 },
+}
+
+/// Spawn the generator driver.
+///
+/// Generator clips have no media file, so their parameters can't travel
+/// with updateMedia(). While a listener is running this follows each
+/// monitor's clip channel and, when the clip is mapped to a generator,
+/// pushes the generator's channel values to the output window's generator
+/// layer whenever they change.
+pub fn spawn_generator_driver(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        let mut shown: [Option<GeneratorFrame>; 2] = [None, None];
+
+        loop {
+            // 40 Hz, same as a DMX refresh
+            std::thread::sleep(Duration::from_millis(25));
+
+            let cfg = config.lock().unwrap().clone();
+            let (values, listening) = {
+                let frame = dmx_frame.lock().unwrap();
+                (frame.values, frame.listening_since.is_some())
+            };
+
+            let monitors = [("monitor1", &cfg.monitor1), ("monitor2", &cfg.monitor2)];
+            for (i, (monitor_id, monitor)) in monitors.iter().enumerate() {
+                let wanted = if listening && monitor.enabled {
+                    generator_frame(monitor, &values)
+                } else {
+                    None
+                };
+                if wanted == shown[i] {
+                    continue;
+                }
+
+                // A closed window shows nothing; retry once it is (re)opened
+                match push_generator(&app_handle, monitor_id, wanted.as_ref()) {
+                    Ok(true) => shown[i] = wanted,
+                    Ok(false) => shown[i] = None,
+                    Err(e) => eprintln!("Failed to update generator on {}: {}", monitor_id, e),
+                }
+            }
+        }
+    });
+}
+
+/// The generator frame for the monitor's current clip, if it is a generator clip
+fn generator_frame(monitor: &MonitorConfig, values: &[u8; 513]) -> Option<GeneratorFrame> {
+    let channel = |ch: u16| values.get(ch as usize).copied().unwrap_or(0);
+    let clip = channel(monitor.clip_channel());
+    let generator = monitor.generators.iter().find(|g| g.clip == clip && clip != 0)?;
+    let dimmer = channel(monitor.dimmer_channel());
+
+    Some(match generator.generator {
+        Generator::SolidColor { start_channel } => GeneratorFrame::SolidColor {
+            red: channel(start_channel),
+            green: channel(start_channel + 1),
+            blue: channel(start_channel + 2),
+            dimmer,
+        },
+        Generator::Strobe { start_channel } => GeneratorFrame::Strobe {
+            rate: channel(start_channel),
+            red: channel(start_channel + 1),
+            green: channel(start_channel + 2),
+            blue: channel(start_channel + 3),
+            dimmer,
+        },
+        Generator::Gradient { start_channel } => GeneratorFrame::Gradient {
+            from: [channel(start_channel), channel(start_channel + 1), channel(start_channel + 2)],
+            to: [channel(start_channel + 3), channel(start_channel + 4), channel(start_channel + 5)],
+            angle: channel(start_channel + 6),
+            dimmer,
+        },
+        Generator::Noise { start_channel } => GeneratorFrame::Noise {
+            speed: channel(start_channel),
+            grain: channel(start_channel + 1),
+            dimmer,
+        },
+    })
+}
+
+/// Returns false if the output window isn't open
+fn push_generator(app_handle: &AppHandle, monitor_id: &str, frame: Option<&GeneratorFrame>) -> Result<bool, String> {
+    let Some(window) = app_handle.get_webview_window(&output_window::window_label(monitor_id)) else {
+        return Ok(false);
+    };
+    let frame_js = serde_json::to_string(&frame)
+        .map_err(|e| format!("Failed to encode generator: {}", e))?;
+    window.eval(&format!("if (typeof setGenerator === 'function') {{ setGenerator({}); }}", frame_js))
+        .map_err(|e| format!("Failed to execute script: {}", e))?;
+    Ok(true)
+}
@@ -0,0 +1,148 @@
+use crate::config::{DmxUpdate, SacnConfig};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A value seen on a filtered channel that isn't passed on yet
+struct Candidate {
+    value: u8,
+    first_seen: Instant,
+    frames: u32,
+}
+
+/// Holds back changes on clip channels until the new value has been
+/// steady for a while, so a single corrupt frame from noisy wireless DMX
+/// doesn't flash another clip. Other channels pass straight through.
+pub struct GlitchFilter {
+    channels: Vec<u16>,
+    hold: Duration,
+    frames: u32,
+    /// Last value passed on per filtered channel
+    accepted: HashMap<u16, u8>,
+    candidates: HashMap<u16, Candidate>,
+}
+
+impl GlitchFilter {
+    /// None when the filter is off in the config
+    pub fn new(config: &SacnConfig, channels: Vec<u16>) -> Option<Self> {
+        if config.clip_stable_ms == 0 && config.clip_stable_frames <= 1 {
+            return None;
+        }
+        println!("Clip glitch filter: values must hold {} ms and {} frame(s) on channels {:?}",
+            config.clip_stable_ms, config.clip_stable_frames.max(1), channels);
+        Some(Self {
+            channels,
+            hold: Duration::from_millis(config.clip_stable_ms as u64),
+            frames: config.clip_stable_frames.max(1),
+            accepted: HashMap::new(),
+            candidates: HashMap::new(),
+        })
+    }
+
+    /// The update without channels whose changed value is unconfirmed,
+    /// plus any held value this packet confirms
+    pub fn filter(&mut self, mut update: DmxUpdate) -> DmxUpdate {
+        let listed: Vec<u16> = update.channels.iter().map(|&(channel, _)| channel).collect();
+        update.channels.retain(|&(channel, value)| self.pass(channel, value));
+
+        // Inputs only list changed channels, so a held value missing from
+        // the packet arrived again unchanged and counts another frame
+        let held: Vec<(u16, u8)> = self.candidates.iter()
+            .filter(|(channel, _)| !listed.contains(channel))
+            .map(|(&channel, candidate)| (channel, candidate.value))
+            .collect();
+        for (channel, value) in held {
+            if self.pass(channel, value) {
+                update.channels.push((channel, value));
+            }
+        }
+        update
+    }
+
+    fn pass(&mut self, channel: u16, value: u8) -> bool {
+        if !self.channels.contains(&channel) {
+            return true;
+        }
+        // The first value after starting has nothing to be a glitch of
+        let Some(&accepted) = self.accepted.get(&channel) else {
+            self.accepted.insert(channel, value);
+            return true;
+        };
+        if value == accepted {
+            self.candidates.remove(&channel);
+            return true;
+        }
+
+        let now = Instant::now();
+        let candidate = self.candidates.entry(channel).or_insert(Candidate {
+            value,
+            first_seen: now,
+            frames: 0,
+        });
+        if candidate.value != value {
+            *candidate = Candidate { value, first_seen: now, frames: 0 };
+        }
+        candidate.frames += 1;
+        if candidate.frames < self.frames || now.duration_since(candidate.first_seen) < self.hold {
+            return false;
+        }
+        self.candidates.remove(&channel);
+        self.accepted.insert(channel, value);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wait in ms, channel 1 in the packet, channel 1 passed on
+    type Step = (u64, Option<u8>, Option<u8>);
+
+    /// Channel 1's value after filtering, if it was passed on
+    fn step(filter: &mut GlitchFilter, value: Option<u8>) -> Option<u8> {
+        let update = DmxUpdate { universe: 1, channels: value.map(|v| (1, v)).into_iter().collect() };
+        filter.filter(update).channels.iter()
+            .find(|&&(channel, _)| channel == 1)
+            .map(|&(_, value)| value)
+    }
+
+    #[test]
+    fn holds_changes_until_stable() {
+        // (clip_stable_ms, clip_stable_frames, steps)
+        let cases: [(u32, u32, &[Step]); 5] = [
+            // Three frames: the third in a row confirms
+            (0, 3, &[(0, Some(1), Some(1)), (0, Some(2), None), (0, Some(2), None), (0, Some(2), Some(2))]),
+            // Packets without the channel count as frames of the held value
+            (0, 2, &[(0, Some(1), Some(1)), (0, Some(2), None), (0, None, Some(2)), (0, None, None)]),
+            // The hold expires on the next packet, even one without the channel
+            (100, 1, &[(0, Some(1), Some(1)), (0, Some(2), None), (20, Some(2), None), (120, None, Some(2))]),
+            // A one-frame glitch never reaches the output
+            (100, 1, &[(0, Some(1), Some(1)), (0, Some(9), None), (0, Some(1), Some(1)), (120, None, None)]),
+            // A different value restarts the hold
+            (100, 1, &[(0, Some(1), Some(1)), (0, Some(2), None), (60, Some(3), None), (60, Some(3), None), (60, None, Some(3))]),
+        ];
+        for (i, (hold_ms, frames, steps)) in cases.into_iter().enumerate() {
+            let config = SacnConfig { clip_stable_ms: hold_ms, clip_stable_frames: frames, ..Default::default() };
+            let mut filter = GlitchFilter::new(&config, vec![1]).unwrap();
+            for (j, &(wait_ms, value, expected)) in steps.iter().enumerate() {
+                std::thread::sleep(Duration::from_millis(wait_ms));
+                assert_eq!(step(&mut filter, value), expected, "case {} step {}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn other_channels_pass_straight_through() {
+        let config = SacnConfig { clip_stable_ms: 1000, clip_stable_frames: 5, ..Default::default() };
+        let mut filter = GlitchFilter::new(&config, vec![1]).unwrap();
+        filter.filter(DmxUpdate { universe: 1, channels: vec![(1, 1), (2, 1)] });
+        let update = filter.filter(DmxUpdate { universe: 1, channels: vec![(1, 2), (2, 2)] });
+        assert_eq!(update.channels, vec![(2, 2)]);
+    }
+
+    #[test]
+    fn off_without_a_window() {
+        let config = SacnConfig { clip_stable_ms: 0, clip_stable_frames: 1, ..Default::default() };
+        assert!(GlitchFilter::new(&config, vec![1]).is_none());
+    }
+}
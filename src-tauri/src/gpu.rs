@@ -0,0 +1,72 @@
+use crate::config::GpuPreference;
+use serde::Serialize;
+
+/// A graphics adapter that output rendering can be pinned to
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuAdapter {
+    pub name: String,
+    /// Adapter LUID in the "high,low" form expected by GpuPreference::Adapter
+    pub luid: String,
+    pub vendor_id: u32,
+    pub dedicated_memory_mb: u64,
+}
+
+/// Wry's default WebView2 arguments; they must be repeated when adding our own
+const WRY_DEFAULT_ARGS: &str = "--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection";
+
+/// WebView2 (Chromium) arguments that select the GPU for an output window.
+/// Returns None for the default adapter. Only WebView2 honours these;
+/// other platforms always render on the system's default GPU.
+pub fn browser_args(preference: &GpuPreference) -> Option<String> {
+    let gpu_arg = match preference {
+        GpuPreference::Default => return None,
+        GpuPreference::HighPerformance => "--force_high_performance_gpu".to_string(),
+        GpuPreference::LowPower => "--force_low_power_gpu".to_string(),
+        GpuPreference::Adapter { luid } => format!("--use-adapter-luid={}", luid),
+    };
+    Some(format!("{} {}", WRY_DEFAULT_ARGS, gpu_arg))
+}
+
+/// Enumerate hardware graphics adapters via DXGI
+#[cfg(windows)]
+pub fn list_adapters() -> Result<Vec<GpuAdapter>, String> {
+    use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1};
+
+    // DXGI_ADAPTER_FLAG_SOFTWARE (Microsoft Basic Render Driver)
+    const SOFTWARE_ADAPTER: u32 = 2;
+
+    let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }
+        .map_err(|e| format!("Failed to create DXGI factory: {}", e))?;
+
+    let mut adapters = Vec::new();
+    let mut index = 0;
+    while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+        index += 1;
+
+        let desc = match unsafe { adapter.GetDesc1() } {
+            Ok(desc) => desc,
+            Err(e) => {
+                eprintln!("Failed to read adapter {} description: {}", index - 1, e);
+                continue;
+            }
+        };
+        if desc.Flags & SOFTWARE_ADAPTER != 0 {
+            continue;
+        }
+
+        let name_len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+        adapters.push(GpuAdapter {
+            name: String::from_utf16_lossy(&desc.Description[..name_len]),
+            luid: format!("{:#x},{:#x}", desc.AdapterLuid.HighPart, desc.AdapterLuid.LowPart),
+            vendor_id: desc.VendorId,
+            dedicated_memory_mb: (desc.DedicatedVideoMemory / (1024 * 1024)) as u64,
+        });
+    }
+
+    Ok(adapters)
+}
+
+#[cfg(not(windows))]
+pub fn list_adapters() -> Result<Vec<GpuAdapter>, String> {
+    Ok(Vec::new())
+}
@@ -0,0 +1,121 @@
+use crate::config::{AppConfig, DmxUpdate, MediaType, MonitorConfig, PlayType};
+use crate::media_scanner;
+use crate::sacn_listener::{DmxFrame, SacnListener};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Command-line switch that runs the backend without any windows
+pub const HEADLESS_ARG: &str = "--headless";
+
+/// What a monitor's three channels currently ask for
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Look {
+    clip: u8,
+    dimmer: u8,
+    playtype: u8,
+}
+
+/// Run the listener and clip routing with no webviews, logging every
+/// decision the output windows would act on. For servers without displays
+/// and for automated checks; runs until the process is stopped.
+pub fn run(config: AppConfig) -> Result<(), String> {
+    println!("=== Headless mode: no windows will be opened ===");
+
+    let monitors: Vec<(&str, &MonitorConfig)> = [("monitor1", &config.monitor1), ("monitor2", &config.monitor2)]
+        .into_iter()
+        .filter(|(_, monitor)| monitor.enabled)
+        .collect();
+    for (monitor_id, monitor) in &monitors {
+        log_inventory(monitor_id, monitor);
+    }
+
+    let dmx_frame = Arc::new(Mutex::new(DmxFrame::default()));
+    let dmx_frame_cb = Arc::clone(&dmx_frame);
+    let mut listener = SacnListener::new(config.sacn.clone());
+    listener.start(move |update: DmxUpdate| {
+        let mut frame = dmx_frame_cb.lock().unwrap();
+        for (channel, value) in update.channels {
+            if let Some(slot) = frame.values.get_mut(channel as usize) {
+                *slot = value;
+            }
+        }
+        frame.last_received = Some(Instant::now());
+    })?;
+
+    let mut shown: Vec<Option<Look>> = vec![None; monitors.len()];
+    loop {
+        std::thread::sleep(Duration::from_millis(25));
+        let values = dmx_frame.lock().unwrap().values;
+
+        for (i, (monitor_id, monitor)) in monitors.iter().enumerate() {
+            let channel = |ch: u16| values.get(ch as usize).copied().unwrap_or(0);
+            let look = Look {
+                clip: channel(monitor.clip_channel()),
+                dimmer: channel(monitor.dimmer_channel()),
+                playtype: channel(monitor.playtype_channel()),
+            };
+            if shown[i] == Some(look) {
+                continue;
+            }
+            let clip_changed = shown[i].map(|s| s.clip) != Some(look.clip);
+            shown[i] = Some(look);
+
+            if clip_changed {
+                println!("{}: clip {:03} -> {}", monitor_id, look.clip, describe_clip(monitor, look));
+            }
+            println!(
+                "{}: dimmer {} ({}%), playtype {} ({:?})",
+                monitor_id,
+                look.dimmer,
+                look.dimmer as u32 * 100 / 255,
+                look.playtype,
+                PlayType::from_dmx(look.playtype)
+            );
+        }
+    }
+}
+
+fn log_inventory(monitor_id: &str, monitor: &MonitorConfig) {
+    println!(
+        "{} ({}): channels {}-{}, folder {}",
+        monitor_id,
+        monitor.name,
+        monitor.clip_channel(),
+        monitor.playtype_channel(),
+        monitor.media_folder.display()
+    );
+    match media_scanner::list_media(&monitor.media_folder, None) {
+        Ok(entries) => {
+            println!("{}: {} numbered files", monitor_id, entries.len());
+            for entry in entries.iter().filter(|e| !e.warnings.is_empty()) {
+                println!("{}: {} - {}", monitor_id, entry.name, entry.warnings.join("; "));
+            }
+        }
+        Err(e) => eprintln!("{}: failed to read media folder: {}", monitor_id, e),
+    }
+}
+
+/// What the output window would show for the clip value
+fn describe_clip(monitor: &MonitorConfig, look: Look) -> String {
+    match look.clip {
+        0 => return "nothing (clear)".to_string(),
+        255 => return "close output".to_string(),
+        _ => {}
+    }
+    if let Some(generator) = monitor.generators.iter().find(|g| g.clip == look.clip) {
+        return format!("generator {:?}", generator.generator);
+    }
+
+    let media = match media_scanner::scan_media_folder(&monitor.media_folder) {
+        Ok(media) => media,
+        Err(e) => return format!("media folder unreadable: {}", e),
+    };
+    match media.get(&look.clip) {
+        Some(file) if file.media_type == MediaType::Image && PlayType::from_dmx(look.playtype) == PlayType::Slideshow => {
+            let count = media_scanner::consecutive_images(&monitor.media_folder, look.clip as u16, false).len();
+            format!("slideshow from {} ({} images)", file.filename, count)
+        }
+        Some(file) => format!("{} ({:?})", file.filename, file.media_type),
+        None => "no file, output cleared".to_string(),
+    }
+}
@@ -0,0 +1,88 @@
+use crate::config::HotkeyConfig;
+use crate::output_window;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use std::str::FromStr;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/// Emergency actions applied to every output window at once
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PanicAction {
+    /// Cut all outputs to black
+    Blackout,
+    /// Hold the current frame and ignore incoming updates
+    Freeze,
+    /// Return to the live look
+    Restore,
+}
+
+/// Refuse shortcuts that can't be parsed, or that are given to two actions,
+/// so a bad edit is caught before the config is saved
+pub fn check_hotkeys(hotkeys: &HotkeyConfig) -> Result<(), String> {
+    let mut parsed: Vec<(Shortcut, &str)> = Vec::new();
+    for (accelerator, action) in [(&hotkeys.blackout, "blackout"), (&hotkeys.freeze, "freeze"), (&hotkeys.restore, "restore")] {
+        if accelerator.is_empty() {
+            continue;
+        }
+        let shortcut = Shortcut::from_str(accelerator)
+            .map_err(|e| format!("Invalid {} shortcut '{}': {}", action, accelerator, e))?;
+        if let Some((_, other)) = parsed.iter().find(|(existing, _)| *existing == shortcut) {
+            return Err(format!("Shortcut '{}' is used for both {} and {}", accelerator, other, action));
+        }
+        parsed.push((shortcut, action));
+    }
+    Ok(())
+}
+
+/// (Re)register the configured global shortcuts.
+/// Shortcuts are handled in the backend so they fire even when an output
+/// window has focus or the main window is minimized.
+pub fn register_hotkeys(app_handle: &AppHandle, hotkeys: &HotkeyConfig) -> Result<(), String> {
+    let shortcuts = app_handle.global_shortcut();
+    shortcuts.unregister_all()
+        .map_err(|e| format!("Failed to clear global shortcuts: {}", e))?;
+
+    let bindings = [
+        (&hotkeys.blackout, PanicAction::Blackout),
+        (&hotkeys.freeze, PanicAction::Freeze),
+        (&hotkeys.restore, PanicAction::Restore),
+    ];
+
+    let mut errors = Vec::new();
+    for (accelerator, action) in bindings {
+        if accelerator.is_empty() {
+            continue;
+        }
+
+        let result = shortcuts.on_shortcut(accelerator.as_str(), move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                apply_panic_action(app, action);
+            }
+        });
+
+        match result {
+            Ok(()) => println!("Registered global shortcut {} for {:?}", accelerator, action),
+            Err(e) => errors.push(format!("{} ({:?}): {}", accelerator, action, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to register global shortcuts: {}", errors.join("; ")))
+    }
+}
+
+/// Apply a panic action to all open output windows and notify the UI
+pub fn apply_panic_action(app_handle: &AppHandle, action: PanicAction) {
+    println!("Panic action: {:?}", action);
+
+    let mode = match action {
+        PanicAction::Blackout => "blackout",
+        PanicAction::Freeze => "freeze",
+        PanicAction::Restore => "none",
+    };
+    output_window::set_override_all(app_handle, mode);
+
+    let _ = app_handle.emit("panic-action", &action);
+}
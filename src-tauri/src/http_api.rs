@@ -0,0 +1,342 @@
+use crate::config::{AppConfig, CueLook};
+use crate::macros;
+use crate::playback::{MonitorPlayback, PlaybackTracker};
+use crate::sacn_listener::DmxFrame;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+/// No DMX for this long and /status reports the signal as lost
+const SIGNAL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often WebSocket clients are checked for a changed status
+const PUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Unchanged status is resent this often so clients can tell the player is alive
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Body of GET /status
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiStatus {
+    /// A DMX listener is running
+    pub listening: bool,
+    /// DMX arrived within the last few seconds
+    pub receiving: bool,
+    pub monitors: Vec<MonitorPlayback>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    /// From "?token=", for WebSocket clients that can't set headers
+    query_token: Option<String>,
+    /// Sec-WebSocket-Key of an upgrade request
+    websocket_key: Option<String>,
+}
+
+impl Request {
+    fn authorized(&self, token: &str) -> bool {
+        token.is_empty()
+            || self.authorization.as_deref() == Some(&format!("Bearer {}", token))
+            || self.query_token.as_deref() == Some(token)
+    }
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(value: &impl Serialize) -> Self {
+        Response {
+            status: "200 OK",
+            body: serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string()),
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        Response {
+            status,
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// Spawn the HTTP control API.
+///
+/// While enabled it serves:
+/// - `GET /status`: listener and per-monitor playback state
+/// - `POST /monitor/{1|2}/{clip|dimmer|playtype}/{value}`, clips up to 65535
+/// - `POST /blackout`: dims both monitors to 0
+/// - `GET /ws`: WebSocket pushing the /status JSON whenever it changes
+///
+/// Changes set the monitor's playback state, like macros and OSC, and the
+/// console takes over again when it changes that monitor's channels.
+pub fn spawn_http_api(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+    playback: Arc<Mutex<PlaybackTracker>>,
+) {
+    std::thread::spawn(move || {
+        let mut listener: Option<(u16, TcpListener)> = None;
+        let looks: Arc<Mutex<HashMap<String, CueLook>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        loop {
+            let api = config.lock().unwrap().http_api.clone();
+            if !api.enabled {
+                if listener.take().is_some() {
+                    println!("HTTP API stopped");
+                }
+                looks.lock().unwrap().clear();
+                std::thread::sleep(Duration::from_millis(250));
+                continue;
+            }
+
+            if listener.as_ref().map(|(port, _)| *port) != Some(api.port) {
+                listener = match bind(api.port) {
+                    Ok(bound) => {
+                        println!("HTTP API listening on port {}", api.port);
+                        Some((api.port, bound))
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to open HTTP API port {}: {}", api.port, e);
+                        std::thread::sleep(Duration::from_secs(2));
+                        continue;
+                    }
+                };
+            }
+            let Some((_, bound)) = listener.as_ref() else {
+                continue;
+            };
+
+            // Non-blocking so config changes are noticed
+            let connection = match bound.accept() {
+                Ok((connection, _)) => connection,
+                Err(e) => {
+                    if e.kind() != std::io::ErrorKind::WouldBlock {
+                        eprintln!("HTTP API accept error: {}", e);
+                    }
+                    std::thread::sleep(Duration::from_millis(50));
+                    continue;
+                }
+            };
+            // Each connection gets its own thread, so a client that stalls
+            // mid-request doesn't hold up everyone else
+            let app_handle = app_handle.clone();
+            let config = Arc::clone(&config);
+            let dmx_frame = Arc::clone(&dmx_frame);
+            let playback = Arc::clone(&playback);
+            let looks = Arc::clone(&looks);
+            std::thread::spawn(move || serve(connection, &api.token, &app_handle, &config, &dmx_frame, &playback, &looks));
+        }
+    });
+}
+
+fn serve(
+    connection: TcpStream,
+    token: &str,
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    playback: &Mutex<PlaybackTracker>,
+    looks: &Mutex<HashMap<String, CueLook>>,
+) {
+    let response = match read_request(&connection) {
+        Some(request) if !request.authorized(token) => {
+            Response::error("401 Unauthorized", "Missing or wrong token")
+        }
+        Some(Request { method, path, websocket_key: Some(key), .. }) if method == "GET" && path == "/ws" => {
+            // Status displays stay connected on this thread
+            push_status(connection, &key, dmx_frame, playback);
+            return;
+        }
+        Some(request) => handle(app_handle, config, dmx_frame, playback, &mut looks.lock().unwrap(), &request),
+        None => Response::error("400 Bad Request", "Malformed request"),
+    };
+    // Without a token any web page could drive the player from a
+    // browser, so only a protected API is opened to other origins
+    write_response(connection, &response, !token.is_empty());
+}
+
+fn bind(port: u16) -> std::io::Result<TcpListener> {
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+fn handle(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    playback: &Mutex<PlaybackTracker>,
+    looks: &mut HashMap<String, CueLook>,
+    request: &Request,
+) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["status"]) => Response::json(&status(dmx_frame, playback)),
+        ("POST", ["blackout"]) => {
+            macros::blackout(app_handle, config, dmx_frame, looks);
+            println!("HTTP API: blackout");
+            Response::json(&serde_json::json!({ "ok": true }))
+        }
+        ("POST", ["monitor", monitor, field, value]) => {
+            let monitor_id = match *monitor {
+                "1" | "2" => format!("monitor{}", monitor),
+                _ => return Response::error("404 Not Found", "Monitor must be 1 or 2"),
+            };
+            // Clips go up to 65535 for 16-bit clip selection
+            let Ok(value) = value.parse::<u16>() else {
+                return Response::error("400 Bad Request", "Value must be 0-65535");
+            };
+            let level = u8::try_from(value);
+            let result = match (*field, level) {
+                ("clip", _) => macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.clip = value),
+                ("dimmer", Ok(level)) => macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.dimmer = level),
+                ("playtype", Ok(level)) => macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.playtype = level),
+                ("dimmer" | "playtype", Err(_)) => return Response::error("400 Bad Request", "Dimmer and playtype must be 0-255"),
+                _ => return Response::error("404 Not Found", "Expected clip, dimmer or playtype"),
+            };
+            match result {
+                Ok(()) => {
+                    println!("HTTP API: {} {} = {}", monitor_id, field, value);
+                    Response::json(&serde_json::json!({ "ok": true }))
+                }
+                Err(e) => Response::error("409 Conflict", &e),
+            }
+        }
+        ("GET", ["ws"]) => Response::error("426 Upgrade Required", "Connect with a WebSocket client"),
+        (_, ["status"] | ["blackout"] | ["monitor", ..]) => {
+            Response::error("405 Method Not Allowed", "Use GET for /status and POST for changes")
+        }
+        _ => Response::error("404 Not Found", "Unknown endpoint"),
+    }
+}
+
+/// Listener and playback state, as served on /status and published over MQTT
+pub fn status(dmx_frame: &Mutex<DmxFrame>, playback: &Mutex<PlaybackTracker>) -> ApiStatus {
+    let (listening, receiving) = {
+        let frame = dmx_frame.lock().unwrap();
+        (
+            frame.listening_since.is_some(),
+            frame.last_received.is_some_and(|at| at.elapsed() < SIGNAL_TIMEOUT),
+        )
+    };
+    let tracker = playback.lock().unwrap();
+    ApiStatus {
+        listening,
+        receiving,
+        monitors: vec![tracker.status("monitor1"), tracker.status("monitor2")],
+    }
+}
+
+fn read_request(connection: &TcpStream) -> Option<Request> {
+    connection.set_nonblocking(false).ok()?;
+    connection.set_read_timeout(Some(Duration::from_secs(2))).ok()?;
+    let mut reader = BufReader::new(connection.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.to_string();
+    let query_token = query.split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(crate::media_scanner::percent_decode);
+
+    let mut authorization = None;
+    let mut websocket_key = None;
+    let mut content_length = 0usize;
+    let mut line = String::new();
+    while reader.read_line(&mut line).ok()? > 2 {
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "authorization" => authorization = Some(value.to_string()),
+                "sec-websocket-key" => websocket_key = Some(value.to_string()),
+                "content-length" => content_length = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        line.clear();
+    }
+    // Everything is in the path; read and ignore any body so the client
+    // doesn't see a reset
+    let _ = reader.take(content_length.min(64 * 1024) as u64).read_to_end(&mut Vec::new());
+
+    Some(Request { method, path, authorization, query_token, websocket_key })
+}
+
+fn write_response(mut connection: TcpStream, response: &Response, cross_origin: bool) {
+    let header = format!(
+        "HTTP/1.1 {}\r\n\
+        Content-Type: application/json\r\n\
+        Content-Length: {}\r\n\
+        {}\
+        Connection: close\r\n\r\n",
+        response.status,
+        response.body.len(),
+        if cross_origin { "Access-Control-Allow-Origin: *\r\n" } else { "" },
+    );
+    let _ = connection.write_all(header.as_bytes());
+    let _ = connection.write_all(response.body.as_bytes());
+}
+
+/// Complete the WebSocket handshake, then send the status JSON whenever it
+/// changes until the client goes away
+fn push_status(mut connection: TcpStream, key: &str, dmx_frame: &Mutex<DmxFrame>, playback: &Mutex<PlaybackTracker>) {
+    let accept = tungstenite::handshake::derive_accept_key(key.as_bytes());
+    let handshake = format!(
+        "HTTP/1.1 101 Switching Protocols\r\n\
+        Upgrade: websocket\r\n\
+        Connection: Upgrade\r\n\
+        Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept,
+    );
+    if connection.write_all(handshake.as_bytes()).is_err() {
+        return;
+    }
+    // Short reads so pushes aren't held up waiting on the client
+    if connection.set_read_timeout(Some(PUSH_INTERVAL)).is_err() {
+        return;
+    }
+    let peer = connection.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+    println!("Status WebSocket connected: {}", peer);
+
+    let mut socket = WebSocket::from_raw_socket(connection, Role::Server, None);
+    let mut last_sent: Option<(String, Instant)> = None;
+    loop {
+        let json = serde_json::to_string(&status(dmx_frame, playback)).unwrap_or_default();
+        let due = match &last_sent {
+            Some((sent, at)) => *sent != json || at.elapsed() >= KEEPALIVE_INTERVAL,
+            None => true,
+        };
+        if due {
+            if socket.send(Message::text(json.clone())).is_err() {
+                break;
+            }
+            last_sent = Some((json, Instant::now()));
+        }
+
+        // Doubles as the wait between pushes; pings are answered by the library
+        match socket.read() {
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(_) => break,
+        }
+    }
+    println!("Status WebSocket disconnected: {}", peer);
+}
@@ -0,0 +1,223 @@
+use crate::config::AppConfig;
+use crate::output_window::{self, OutputWindowSpec};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Spawn the kiosk guard for installations that run unattended for months.
+///
+/// While `kiosk_mode` is on it keeps the system awake, silences OS toast
+/// notifications where the platform allows it (the user's own setting comes
+/// back when kiosk mode is switched off or the app exits), re-asserts
+/// always-on-top on every output window and reopens any output window that
+/// has disappeared.
+/// Blocking accidental window closes is done by the window event handlers.
+pub fn spawn_kiosk_guard(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    output_windows: Arc<Mutex<HashMap<String, OutputWindowSpec>>>,
+) {
+    std::thread::spawn(move || {
+        let mut sleep_blocker = SleepBlocker::default();
+        let mut active = false;
+
+        loop {
+            let kiosk_mode = config.lock().unwrap().kiosk_mode;
+
+            if kiosk_mode != active {
+                active = kiosk_mode;
+                if active {
+                    println!("Kiosk mode enabled");
+                    sleep_blocker.block();
+                    suppress_notifications();
+                } else {
+                    println!("Kiosk mode disabled");
+                    sleep_blocker.release();
+                    restore_notifications();
+                }
+            }
+
+            if active {
+                let specs: Vec<OutputWindowSpec> = output_windows.lock().unwrap().values().cloned().collect();
+                for spec in specs {
+                    guard_output_window(&app_handle, &config, &spec);
+                }
+            }
+
+            std::thread::sleep(Duration::from_secs(2));
+        }
+    });
+}
+
+fn guard_output_window(app_handle: &AppHandle, config: &Arc<Mutex<AppConfig>>, spec: &OutputWindowSpec) {
+    let label = output_window::window_label(&spec.monitor_id);
+
+    match app_handle.get_webview_window(&label) {
+        // Decorated: in windowed setup, leave it where the operator put it
+        Some(window) if window.is_decorated().unwrap_or(false) => {}
+        Some(window) => {
+            // Another app may have pushed itself above us
+            if let Err(e) = window.set_always_on_top(true) {
+                eprintln!("Kiosk: failed to re-assert always-on-top for '{}': {}", label, e);
+            }
+            #[cfg(target_os = "macos")]
+            if let Err(e) = output_window::apply_macos_window_level(&window) {
+                eprintln!("Kiosk: {}", e);
+            }
+        }
+        None => {
+            println!("Kiosk: output window '{}' is gone, reopening", label);
+            if let Err(e) = output_window::open_window(app_handle, config, spec) {
+                eprintln!("Kiosk: failed to reopen '{}': {}", label, e);
+            }
+        }
+    }
+}
+
+/// Keeps the system and displays awake while held
+#[derive(Default)]
+struct SleepBlocker {
+    /// caffeinate / systemd-inhibit helper on macOS and Linux
+    #[cfg(not(windows))]
+    child: Option<std::process::Child>,
+}
+
+impl SleepBlocker {
+    #[cfg(windows)]
+    fn block(&mut self) {
+        use windows::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+        // ES_CONTINUOUS keeps this in force for as long as the guard thread lives
+        unsafe {
+            SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED);
+        }
+    }
+
+    #[cfg(windows)]
+    fn release(&mut self) {
+        use windows::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS};
+        unsafe {
+            SetThreadExecutionState(ES_CONTINUOUS);
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn block(&mut self) {
+        if self.child.is_some() {
+            return;
+        }
+
+        let spawned = if cfg!(target_os = "macos") {
+            std::process::Command::new("caffeinate").args(["-d", "-i", "-s"]).spawn()
+        } else {
+            std::process::Command::new("systemd-inhibit")
+                .args(["--what=idle:sleep", "--who=StagePlayer DMX", "--why=Kiosk mode", "sleep", "infinity"])
+                .spawn()
+        };
+
+        match spawned {
+            Ok(child) => self.child = Some(child),
+            Err(e) => eprintln!("Kiosk: failed to block system sleep: {}", e),
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn release(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(windows)]
+const PUSH_NOTIFICATIONS_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\PushNotifications";
+
+/// The user's ToastEnabled value from before kiosk mode silenced toasts;
+/// None while they aren't silenced, Some(None) if the value wasn't set
+static SAVED_TOAST_ENABLED: Mutex<Option<Option<u32>>> = Mutex::new(None);
+
+/// Turn Windows toast notifications off for the current user, keeping the
+/// user's own setting for restore_notifications. If that setting can't be
+/// read, notifications are left alone. Other platforms have no supported
+/// way to do this; it is a no-op there.
+fn suppress_notifications() {
+    #[cfg(windows)]
+    {
+        let mut saved = SAVED_TOAST_ENABLED.lock().unwrap();
+        if saved.is_some() {
+            return;
+        }
+        let original = match read_toast_enabled() {
+            Ok(original) => original,
+            Err(e) => {
+                eprintln!("Kiosk: leaving notifications on, could not read the current setting: {}", e);
+                return;
+            }
+        };
+        if write_toast_enabled(Some(0)) {
+            *saved = Some(original);
+            println!("Kiosk: toast notifications suppressed");
+        }
+    }
+}
+
+/// Put back exactly the toast setting suppress_notifications replaced.
+/// Called when kiosk mode is switched off and when the app exits.
+pub fn restore_notifications() {
+    let Some(original) = SAVED_TOAST_ENABLED.lock().unwrap().take() else {
+        return;
+    };
+    #[cfg(windows)]
+    {
+        if write_toast_enabled(original) {
+            println!("Kiosk: toast notifications restored");
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = original;
+}
+
+/// ToastEnabled from the registry, None if it (or its key) isn't there
+#[cfg(windows)]
+fn read_toast_enabled() -> Result<Option<u32>, String> {
+    let output = std::process::Command::new("reg")
+        .args(["query", PUSH_NOTIFICATIONS_KEY])
+        .output()
+        .map_err(|e| format!("failed to run reg: {}", e))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    // "    ToastEnabled    REG_DWORD    0x1"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(line) = stdout.lines().find(|line| line.split_whitespace().next() == Some("ToastEnabled")) else {
+        return Ok(None);
+    };
+    let value = line.split_whitespace().nth(2).unwrap_or_default();
+    u32::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map(Some)
+        .map_err(|_| format!("unexpected ToastEnabled value '{}'", value))
+}
+
+/// Set ToastEnabled, or delete it for None; returns false if reg failed
+#[cfg(windows)]
+fn write_toast_enabled(value: Option<u32>) -> bool {
+    let mut command = std::process::Command::new("reg");
+    match value {
+        Some(value) => command.args(["add", PUSH_NOTIFICATIONS_KEY, "/v", "ToastEnabled", "/t", "REG_DWORD", "/d", &value.to_string(), "/f"]),
+        None => command.args(["delete", PUSH_NOTIFICATIONS_KEY, "/v", "ToastEnabled", "/f"]),
+    };
+    match command.output() {
+        Ok(output) if output.status.success() => true,
+        Ok(output) => {
+            eprintln!("Kiosk: failed to change notification setting: {}", String::from_utf8_lossy(&output.stderr).trim());
+            false
+        }
+        Err(e) => {
+            eprintln!("Kiosk: failed to run reg: {}", e);
+            false
+        }
+    }
+}
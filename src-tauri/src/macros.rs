@@ -0,0 +1,153 @@
+use crate::config::{AppConfig, CueLook, Macro, MacroAction};
+use crate::cue_stack;
+use crate::sacn_listener::DmxFrame;
+use crate::trigger_output;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Spawn the macro driver.
+///
+/// While a listener is running and macros are enabled, each time the
+/// control channel changes to a value with a macro, that macro runs on its
+/// own thread. Clip and dimmer steps set the monitor's playback state, like
+/// a cue; the console takes over again when it changes that monitor's
+/// channels. A value arriving while a macro is still running is ignored.
+pub fn spawn_macro_driver(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        let running = Arc::new(AtomicBool::new(false));
+        let mut last_value: Option<u8> = None;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(25));
+
+            let control_channel = {
+                let cfg = config.lock().unwrap();
+                if !cfg.macros.enabled {
+                    last_value = None;
+                    continue;
+                }
+                cfg.macros.control_channel
+            };
+            let (value, listening) = {
+                let frame = dmx_frame.lock().unwrap();
+                (frame.values.get(control_channel as usize).copied().unwrap_or(0), frame.listening_since.is_some())
+            };
+            if control_channel == 0 || !listening {
+                last_value = None;
+                continue;
+            }
+            if last_value == Some(value) {
+                continue;
+            }
+            last_value = Some(value);
+
+            let cfg = config.lock().unwrap().clone();
+            let Some(found) = cfg.macros.macros.iter().find(|m| m.value == value).cloned() else {
+                continue;
+            };
+            if running.swap(true, Ordering::SeqCst) {
+                println!("Macro '{}' skipped: another macro is still running", found.name);
+                continue;
+            }
+
+            let app_handle = app_handle.clone();
+            let config = Arc::clone(&config);
+            let dmx_frame = Arc::clone(&dmx_frame);
+            let running = Arc::clone(&running);
+            std::thread::spawn(move || {
+                run_macro(&app_handle, &config, &dmx_frame, &found);
+                running.store(false, Ordering::SeqCst);
+            });
+        }
+    });
+}
+
+fn run_macro(app_handle: &AppHandle, config: &Mutex<AppConfig>, dmx_frame: &Mutex<DmxFrame>, found: &Macro) {
+    println!("Macro '{}' started", found.name);
+    let mut looks: HashMap<String, CueLook> = HashMap::new();
+    for action in &found.actions {
+        if let Err(e) = run_action(app_handle, config, dmx_frame, &mut looks, action) {
+            eprintln!("Macro '{}': {}", found.name, e);
+        }
+    }
+    println!("Macro '{}' finished", found.name);
+}
+
+fn run_action(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    looks: &mut HashMap<String, CueLook>,
+    action: &MacroAction,
+) -> Result<(), String> {
+    match action {
+        MacroAction::SelectClip { monitor_id, clip } => {
+            change_look(app_handle, config, dmx_frame, looks, monitor_id, |look| look.clip = *clip)?;
+        }
+        MacroAction::SetDimmer { monitor_id, dimmer } => {
+            change_look(app_handle, config, dmx_frame, looks, monitor_id, |look| look.dimmer = *dimmer)?;
+        }
+        MacroAction::Wait { ms } => std::thread::sleep(Duration::from_millis(*ms)),
+        MacroAction::SendOsc { target, address, args } => {
+            trigger_output::send_osc(target, address, args)?;
+        }
+    }
+    Ok(())
+}
+
+/// Change one part of a monitor's look and show it. `looks` holds each
+/// monitor's look for the run; a monitor starts from what its playback
+/// state shows. Also used by scripts.
+pub fn change_look(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    looks: &mut HashMap<String, CueLook>,
+    monitor_id: &str,
+    change: impl FnOnce(&mut CueLook),
+) -> Result<(), String> {
+    // Read fresh so config edits apply
+    let monitor = config.lock().unwrap().monitor(monitor_id).cloned()
+        .ok_or_else(|| format!("Unknown monitor '{}'", monitor_id))?;
+    if !monitor.enabled {
+        return Err(format!("{} is disabled", monitor_id));
+    }
+
+    let look = looks.entry(monitor_id.to_string()).or_insert_with(|| {
+        let current = app_handle.state::<crate::AppState>().playback_states.lock().unwrap()
+            .get(monitor_id)
+            .map(|state| CueLook { clip: state.clip, dimmer: state.dimmer, playtype: state.playtype });
+        current.unwrap_or_else(|| {
+            let frame = dmx_frame.lock().unwrap();
+            let value = |channel: u16| frame.values.get(channel as usize).copied().unwrap_or(0);
+            CueLook {
+                clip: monitor.clip_value(&frame.values),
+                dimmer: value(monitor.dimmer_channel()),
+                playtype: value(monitor.playtype_channel()),
+            }
+        })
+    });
+    change(look);
+    cue_stack::show_look(app_handle, monitor_id, &monitor, *look);
+    Ok(())
+}
+
+/// Dim every enabled monitor to 0, keeping its clip
+pub fn blackout(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    looks: &mut HashMap<String, CueLook>,
+) {
+    for monitor_id in ["monitor1", "monitor2"] {
+        // A disabled monitor has nothing to black out
+        let _ = change_look(app_handle, config, dmx_frame, looks, monitor_id, |look| look.dimmer = 0);
+    }
+}
@@ -1,14 +1,60 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_info;
+mod artnet_listener;
+mod audio_fixture;
+mod audio_reactive;
+mod autostart;
+mod capture;
+mod clip_defaults;
 mod config;
+mod cue_stack;
+mod dimmer_smoothing;
+mod disk_space;
+mod dmx_recorder;
+mod explorer;
+mod failover;
+mod fallback;
+mod feedback;
+mod generator;
+mod glitch_filter;
+mod headless;
+mod gpu;
+mod hotkeys;
+mod http_api;
+mod kiosk;
+mod macros;
+mod media_import;
 mod media_scanner;
+mod midi_input;
+mod mqtt;
+mod osc_input;
+mod output_window;
+mod pixel_map;
+mod placement;
+mod playback;
+mod playback_state;
+mod plugins;
 mod sacn_listener;
 mod sacn_test_sender;
+mod scripting;
+mod self_test;
+mod snapshot;
+mod spout;
+mod stream_input;
+mod subtitles;
+mod timecode;
+mod timeline;
+mod trigger_output;
+mod updater;
 
 use config::{AppConfig, NetworkInterface, DmxUpdate};
-use sacn_listener::SacnListener;
-use sacn_test_sender::SacnTestSender;
+use sacn_listener::{DmxFrame, SacnListener};
+use output_window::OutputWindowSpec;
+use playback::{MonitorPlayback, PlaybackReport, PlaybackTracker};
+use sacn_test_sender::{SacnTestSender, TestSenderOptions};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicU32, Ordering};
 use tauri::{State, Emitter, Manager};
@@ -16,7 +62,28 @@ use tauri::{State, Emitter, Manager};
 struct AppState {
     config: Arc<Mutex<AppConfig>>,
     sacn_listener: Arc<Mutex<Option<SacnListener>>>,
+    /// Plugin input protocols running alongside the sACN listener
+    extra_inputs: Arc<Mutex<Vec<Box<dyn plugins::InputProtocol>>>>,
     test_sender: Arc<Mutex<Option<SacnTestSender>>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+    playback: Arc<Mutex<PlaybackTracker>>,
+    /// Started with --production (e.g. by the auto-start task)
+    launch_production: bool,
+    /// Output windows that should currently be open, by monitor id
+    output_windows: Arc<Mutex<HashMap<String, OutputWindowSpec>>>,
+    /// Production geometry of output windows while they are in windowed setup
+    setup_geometry: Arc<Mutex<HashMap<String, output_window::SetupGeometry>>>,
+    cue_state: Arc<Mutex<cue_stack::CueState>>,
+    timeline: Arc<Mutex<timeline::Transport>>,
+    /// Created when the first pixel-mapped frame arrives
+    pixel_map_sender: Arc<Mutex<Option<pixel_map::PixelMapSender>>>,
+    /// Spout senders by monitor id, created with the first frame shared
+    spout_senders: Arc<Mutex<HashMap<String, spout::SpoutSender>>>,
+    dmx_recorder: Arc<Mutex<Option<dmx_recorder::Recorder>>>,
+    dmx_replay: Arc<Mutex<Option<dmx_recorder::Replay>>>,
+    /// Clip, dimmer and playtype per monitor, rebuilt when the listener starts
+    playback_states: Arc<Mutex<playback_state::PlaybackStates>>,
+    dimmer_smoother: Arc<Mutex<dimmer_smoothing::DimmerSmoother>>,
 }
 
 #[tauri::command]
@@ -25,16 +92,123 @@ fn get_config(state: State<AppState>) -> AppConfig {
 }
 
 #[tauri::command]
-fn update_config(state: State<AppState>, config: AppConfig) -> Result<(), String> {
+fn update_config(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    mut config: AppConfig,
+) -> Result<(), String> {
+    // The picker history is kept by select_folder; the frontend's copy may be stale
+    config.last_folders = state.config.lock().unwrap().last_folders.clone();
+    
+    config.check_channel_patch()?;
+    hotkeys::check_hotkeys(&config.hotkeys)?;
+    
     // Save to file first
     config.save()?;
     
+    // Re-register global shortcuts if they were edited
+    let hotkeys_changed = state.config.lock().unwrap().hotkeys != config.hotkeys;
+    let hotkeys = config.hotkeys.clone();
+    
+    // Output windows sample with the pixel map they were given
+    let pixel_maps_changed = {
+        let current = state.config.lock().unwrap();
+        current.monitor1.pixel_map != config.monitor1.pixel_map
+            || current.monitor2.pixel_map != config.monitor2.pixel_map
+    };
+    let pixel_maps = [
+        ("monitor1", config.monitor1.pixel_map.clone()),
+        ("monitor2", config.monitor2.pixel_map.clone()),
+    ];
+    let spouts_changed = {
+        let current = state.config.lock().unwrap();
+        current.monitor1.spout != config.monitor1.spout
+            || current.monitor2.spout != config.monitor2.spout
+    };
+    let spouts = [
+        ("monitor1", config.monitor1.spout.clone()),
+        ("monitor2", config.monitor2.spout.clone()),
+    ];
+    let effects_changed = {
+        let current = state.config.lock().unwrap();
+        current.monitor1.effects_channel.is_some() != config.monitor1.effects_channel.is_some()
+            || current.monitor2.effects_channel.is_some() != config.monitor2.effects_channel.is_some()
+    };
+    let effects_enabled = [
+        ("monitor1", config.monitor1.effects_channel.is_some()),
+        ("monitor2", config.monitor2.effects_channel.is_some()),
+    ];
+    
+    let dimming_curves_changed = {
+        let current = state.config.lock().unwrap();
+        current.monitor1.dimming_curve != config.monitor1.dimming_curve
+            || current.monitor2.dimming_curve != config.monitor2.dimming_curve
+    };
+    let dimming_curves = [
+        ("monitor1", config.monitor1.dimming_curve),
+        ("monitor2", config.monitor2.dimming_curve),
+    ];
+    
+    let click_through_changed = state.config.lock().unwrap().output_click_through != config.output_click_through;
+    let click_through = config.output_click_through;
+    
     // Then update in-memory state
     *state.config.lock().unwrap() = config;
     
+    if pixel_maps_changed {
+        for (monitor_id, pixel_map) in &pixel_maps {
+            if let Err(e) = output_window::push_pixel_map(&app_handle, monitor_id, pixel_map) {
+                eprintln!("Failed to update pixel map on {}: {}", monitor_id, e);
+            }
+        }
+    }
+    
+    if spouts_changed {
+        for (monitor_id, spout) in &spouts {
+            // Renamed or switched off: the next frame registers it afresh
+            state.spout_senders.lock().unwrap().remove(*monitor_id);
+            if let Err(e) = output_window::push_spout(&app_handle, monitor_id, spout) {
+                eprintln!("Failed to update Spout output on {}: {}", monitor_id, e);
+            }
+        }
+    }
+    
+    if click_through_changed {
+        output_window::set_click_through_all(&app_handle, click_through);
+    }
+    
+    if effects_changed {
+        for (monitor_id, enabled) in effects_enabled {
+            if let Err(e) = output_window::push_effects_enabled(&app_handle, monitor_id, enabled) {
+                eprintln!("Failed to update effects on {}: {}", monitor_id, e);
+            }
+        }
+    }
+    
+    if dimming_curves_changed {
+        for (monitor_id, curve) in dimming_curves {
+            if let Err(e) = output_window::push_dimming_curve(&app_handle, monitor_id, curve) {
+                eprintln!("Failed to update dimming curve on {}: {}", monitor_id, e);
+            }
+        }
+    }
+    
+    // The config is saved by now; a shortcut another app holds only costs
+    // that shortcut, so it is logged rather than failing the save
+    if hotkeys_changed {
+        if let Err(e) = hotkeys::register_hotkeys(&app_handle, &hotkeys) {
+            eprintln!("{}", e);
+        }
+    }
+    
     Ok(())
 }
 
+#[tauri::command]
+fn trigger_panic_action(app_handle: tauri::AppHandle, action: hotkeys::PanicAction) {
+    hotkeys::apply_panic_action(&app_handle, action);
+}
+
 #[tauri::command]
 fn get_network_interfaces() -> Vec<NetworkInterface> {
     let mut interfaces = Vec::new();
@@ -42,12 +216,15 @@ fn get_network_interfaces() -> Vec<NetworkInterface> {
     if let Ok(addrs) = get_if_addrs::get_if_addrs() {
         for iface in addrs {
             if !iface.is_loopback() {
-                if let get_if_addrs::IfAddr::V4(addr) = iface.addr {
-                    interfaces.push(NetworkInterface {
-                        name: iface.name,
-                        ip_address: addr.ip.to_string(),
-                    });
-                }
+                // One entry per address; an interface can have IPv4 and IPv6
+                let ip_address = match iface.addr {
+                    get_if_addrs::IfAddr::V4(addr) => addr.ip.to_string(),
+                    get_if_addrs::IfAddr::V6(addr) => addr.ip.to_string(),
+                };
+                interfaces.push(NetworkInterface {
+                    name: iface.name,
+                    ip_address,
+                });
             }
         }
     }
@@ -55,59 +232,373 @@ fn get_network_interfaces() -> Vec<NetworkInterface> {
     interfaces
 }
 
+/// Every numbered file in a media folder, with type, size, duration and warnings
 #[tauri::command]
-fn scan_media_folder(path: String) -> Result<Vec<config::MediaFile>, String> {
-    let folder = std::path::Path::new(&path);
-    media_scanner::scan_media_folder(folder)
-        .map(|map| map.into_values().collect())
+async fn list_media(folder: String) -> Result<Vec<config::MediaEntry>, String> {
+    let ffprobe = find_ffprobe();
+    media_scanner::list_media(std::path::Path::new(&folder), ffprobe.as_deref())
         .map_err(|e| e.to_string())
 }
 
+/// Copy dropped files into a monitor's media folder under the next free
+/// DMX values, converting unsupported formats when `transcode` is set
 #[tauri::command]
-fn get_media_files(folder: String) -> Result<Vec<String>, String> {
-    let path = std::path::Path::new(&folder);
-    
-    if !path.exists() || !path.is_dir() {
-        return Ok(Vec::new());
+async fn import_media(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    monitor_id: String,
+    paths: Vec<String>,
+    transcode: bool,
+) -> Result<Vec<config::MediaFile>, String> {
+    let folder = monitor_media_folder(&state, &monitor_id)?;
+    if !folder.is_dir() {
+        return Err(format!("Set a media folder for {} before importing", monitor_id));
     }
     
-    let mut files = Vec::new();
+    let ffmpeg = if transcode { find_ffmpeg() } else { None };
+    let ffprobe = if transcode { find_ffprobe() } else { None };
+    let conversion = state.config.lock().unwrap().conversion.clone();
+    let sources: Vec<std::path::PathBuf> = paths.iter().map(std::path::PathBuf::from).collect();
+    let (imported, skipped) = media_import::import_files(&folder, &sources, ffmpeg.as_deref(), ffprobe.as_deref(), &conversion);
     
-    if let Ok(entries) = std::fs::read_dir(path) {
-        for entry in entries.flatten() {
-            if let Ok(file_type) = entry.file_type() {
-                if file_type.is_file() {
-                    if let Some(filename) = entry.file_name().to_str() {
-                        // Only include files matching ###_*.ext pattern
-                        if filename.len() >= 5 {
-                            let chars: Vec<char> = filename.chars().collect();
-                            if chars[0].is_ascii_digit() && 
-                               chars[1].is_ascii_digit() && 
-                               chars[2].is_ascii_digit() && 
-                               chars[3] == '_' {
-                                files.push(filename.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+    for reason in &skipped {
+        eprintln!("Import skipped {}", reason);
+    }
+    if imported.is_empty() && !skipped.is_empty() {
+        return Err(format!("Nothing imported: {}", skipped.join("; ")));
+    }
+    emit_media_changed(&app_handle, &monitor_id);
+    Ok(imported)
+}
+
+/// Give a monitor's clip a new DMX value by renaming its file
+#[tauri::command]
+fn renumber_media(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    monitor_id: String,
+    from: u16,
+    to: u16,
+) -> Result<(), String> {
+    let folder = monitor_media_folder(&state, &monitor_id)?;
+    media_import::renumber(&folder, from, to)?;
+    emit_media_changed(&app_handle, &monitor_id);
+    Ok(())
+}
+
+/// Exchange the DMX values of two of a monitor's clips
+#[tauri::command]
+fn swap_media(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    monitor_id: String,
+    a: u16,
+    b: u16,
+) -> Result<(), String> {
+    let folder = monitor_media_folder(&state, &monitor_id)?;
+    media_import::swap(&folder, a, b)?;
+    emit_media_changed(&app_handle, &monitor_id);
+    Ok(())
+}
+
+/// Move a monitor's clip to the recycle bin / trash
+#[tauri::command]
+fn delete_media(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    monitor_id: String,
+    dmx_value: u16,
+) -> Result<(), String> {
+    let folder = monitor_media_folder(&state, &monitor_id)?;
+    media_import::delete(&folder, dmx_value)?;
+    emit_media_changed(&app_handle, &monitor_id);
+    Ok(())
+}
+
+/// Free space on the drives behind the media and convert folders
+#[tauri::command]
+fn get_disk_space(state: State<AppState>) -> Vec<disk_space::DiskSpace> {
+    let config = state.config.lock().unwrap().clone();
+    disk_space::check(&config)
+}
+
+/// Show a configured folder, or a clip file within it, in the system file manager
+#[tauri::command]
+fn open_in_explorer(path: String) -> Result<(), String> {
+    explorer::open(std::path::Path::new(&path))
+}
+
+/// Version and environment details for the About panel
+#[tauri::command]
+async fn get_app_info() -> app_info::AppInfo {
+    app_info::collect()
+}
+
+/// Newer release on the configured update channel, if any
+#[tauri::command]
+async fn check_for_updates(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<updater::UpdateInfo>, String> {
+    let channel = state.config.lock().unwrap().updates.channel;
+    updater::check(&app_handle, channel).await
+}
+
+/// Install the newest release and restart, unless updates are deferred or
+/// production is running
+#[tauri::command]
+async fn install_update(app_handle: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let config = state.config.lock().unwrap().clone();
+    let listening = state.dmx_frame.lock().unwrap().listening_since.is_some();
+    if let Some(reason) = updater::install_blocked(&config, listening) {
+        return Err(reason);
+    }
+    updater::install(&app_handle, config.updates.channel).await
+}
+
+/// Loop test frames through a temporary listener and check each monitor's
+/// clip channel resolves to a file. Needs the regular listener stopped.
+#[tauri::command]
+async fn self_test(state: State<'_, AppState>) -> Result<self_test::SelfTestReport, String> {
+    if state.dmx_frame.lock().unwrap().listening_since.is_some() {
+        return Err("Stop the sACN listener (and production) before running the self-test".to_string());
+    }
+    // A stopped listener may not have released the port yet
+    let old_listener = state.sacn_listener.lock().unwrap().take();
+    if let Some(mut old) = old_listener {
+        old.stop();
+    }
+    
+    let config = state.config.lock().unwrap().clone();
+    Ok(self_test::run(&config))
+}
+
+/// Run the next cue in the cue stack
+#[tauri::command]
+fn cue_go(app_handle: tauri::AppHandle, state: State<AppState>) -> Result<(), String> {
+    cue_stack::go(&app_handle, &state.config, &state.cue_state)
+}
+
+/// Go back to before the first cue
+#[tauri::command]
+fn cue_reset(app_handle: tauri::AppHandle, state: State<AppState>) {
+    cue_stack::reset(&state.cue_state);
+    let _ = app_handle.emit("cue-changed", &*state.cue_state.lock().unwrap());
+}
+
+#[tauri::command]
+fn get_cue_state(state: State<AppState>) -> cue_stack::CueState {
+    state.cue_state.lock().unwrap().clone()
+}
+
+#[tauri::command]
+fn timeline_play(app_handle: tauri::AppHandle, state: State<AppState>) {
+    timeline::play(&app_handle, &state.timeline);
+}
+
+#[tauri::command]
+fn timeline_pause(app_handle: tauri::AppHandle, state: State<AppState>) {
+    timeline::pause(&app_handle, &state.timeline);
+}
+
+#[tauri::command]
+fn timeline_locate(app_handle: tauri::AppHandle, state: State<AppState>, position_secs: f64) {
+    timeline::locate(&app_handle, &state.timeline, position_secs);
+}
+
+#[tauri::command]
+fn get_timeline_state(state: State<AppState>) -> timeline::TimelineState {
+    timeline::state(&state.timeline)
+}
+
+/// Pixel map settings for an output window to sample with
+#[tauri::command]
+fn get_pixel_map(state: State<AppState>, monitor_id: String) -> Option<config::PixelMapConfig> {
+    state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .map(|m| m.pixel_map.clone())
+}
+
+/// Spout settings for an output window to share its picture with
+#[tauri::command]
+fn get_spout(state: State<AppState>, monitor_id: String) -> Option<config::SpoutConfig> {
+    state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .map(|m| m.spout.clone())
+}
+
+/// One frame of an output window's picture, sent as raw RGBA bytes with
+/// the monitor and size in headers, shared through the monitor's Spout sender
+#[tauri::command]
+fn send_spout_frame(state: State<AppState>, request: tauri::ipc::Request) -> Result<(), String> {
+    let tauri::ipc::InvokeBody::Raw(rgba) = request.body() else {
+        return Err("Spout frames must be sent as raw bytes".to_string());
+    };
+    let header = |name: &str| {
+        request.headers().get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| format!("Spout frame has no {} header", name))
+    };
+    let monitor_id = header("monitor-id")?;
+    let width: u32 = header("width")?.parse().map_err(|_| "Invalid Spout frame width".to_string())?;
+    let height: u32 = header("height")?.parse().map_err(|_| "Invalid Spout frame height".to_string())?;
+
+    let sender_name = {
+        let config = state.config.lock().unwrap();
+        let monitor = config.monitor(&monitor_id)
+            .ok_or_else(|| format!("Unknown monitor '{}'", monitor_id))?;
+        if !monitor.spout.enabled {
+            return Ok(());
+        }
+        if monitor.spout.sender_name.is_empty() {
+            spout::default_sender_name(&monitor.name)
+        } else {
+            monitor.spout.sender_name.clone()
         }
+    };
+
+    let mut senders = state.spout_senders.lock().unwrap();
+    if senders.get(&monitor_id).map(|sender| sender.name() != sender_name).unwrap_or(true) {
+        // Let go of the old name before registering the new one
+        senders.remove(&monitor_id);
+        senders.insert(monitor_id.clone(), spout::SpoutSender::new(&sender_name)?);
+    }
+    senders.get_mut(&monitor_id).unwrap().send(width, height, rgba)
+}
+
+/// Switch output windows to decorated, resizable windows for bench setup
+/// and content checks, or back to production geometry
+#[tauri::command]
+fn set_output_setup_mode(app_handle: tauri::AppHandle, state: State<AppState>, windowed: bool) -> Result<(), String> {
+    let mut saved = state.setup_geometry.lock().unwrap();
+    if windowed {
+        output_window::enter_setup_mode(&app_handle, &mut saved)
+    } else {
+        output_window::leave_setup_mode(&app_handle, &state.config, &mut saved)
+    }
+}
+
+/// Turn output window click-through on or off, e.g. to move them during setup
+#[tauri::command]
+fn set_output_click_through(app_handle: tauri::AppHandle, state: State<AppState>, enabled: bool) -> Result<(), String> {
+    let config = {
+        let mut config = state.config.lock().unwrap();
+        config.output_click_through = enabled;
+        config.clone()
+    };
+    config.save()?;
+    output_window::set_click_through_all(&app_handle, enabled);
+    println!("Output click-through {}", if enabled { "on" } else { "off" });
+    Ok(())
+}
+
+/// Whether an output window should prepare clips for shader effects
+#[tauri::command]
+fn get_effects_enabled(state: State<AppState>, monitor_id: String) -> bool {
+    state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .map(|m| m.effects_channel.is_some())
+        .unwrap_or(false)
+}
+
+/// Transform and other parameters an output window applies, as of the
+/// last DMX received
+#[tauri::command]
+fn get_output_params(state: State<AppState>, monitor_id: String) -> Option<playback_state::OutputParams> {
+    state.playback_states.lock().unwrap()
+        .get(&monitor_id)
+        .map(|playback_state| playback_state.output_params())
+}
+
+/// Dimmer curve for an output window to apply
+#[tauri::command]
+fn get_dimming_curve(state: State<AppState>, monitor_id: String) -> config::DimmingCurve {
+    state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .map(|m| m.dimming_curve)
+        .unwrap_or_default()
+}
+
+/// Whether an output window should show its caption overlay
+#[tauri::command]
+fn get_captions_visible(state: State<AppState>, monitor_id: String) -> bool {
+    let config = state.config.lock().unwrap().clone();
+    let values = state.dmx_frame.lock().unwrap().values;
+    subtitles::visible(&config, &values, &monitor_id)
+}
+
+/// Colors sampled by an output window, sent on as sACN
+#[tauri::command]
+fn report_pixel_samples(state: State<AppState>, monitor_id: String, colors: Vec<u8>) -> Result<(), String> {
+    let pixel_map = state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .map(|m| m.pixel_map.clone())
+        .ok_or_else(|| format!("Unknown monitor '{}'", monitor_id))?;
+    if !pixel_map.enabled {
+        return Ok(());
     }
     
-    files.sort();
-    Ok(files)
+    let mut sender = state.pixel_map_sender.lock().unwrap();
+    if sender.is_none() {
+        *sender = Some(pixel_map::PixelMapSender::new()?);
+    }
+    sender.as_mut().unwrap().send(&pixel_map, &colors)
+}
+
+/// Tell the UI to re-read a monitor's media folder
+fn emit_media_changed(app_handle: &tauri::AppHandle, monitor_id: &str) {
+    if let Err(e) = app_handle.emit("media-changed", monitor_id) {
+        eprintln!("Failed to emit media-changed: {}", e);
+    }
+    // The showing clip's file may have been renamed, replaced or deleted
+    let changed = app_handle.state::<AppState>().playback_states.lock().unwrap().refresh_media(monitor_id);
+    if let Some(playback_state) = changed {
+        let _ = app_handle.emit("playback-state", &playback_state);
+    }
+}
+
+fn monitor_media_folder(state: &AppState, monitor_id: &str) -> Result<std::path::PathBuf, String> {
+    state.config.lock().unwrap()
+        .monitor(monitor_id)
+        .map(|m| m.media_folder.clone())
+        .ok_or_else(|| format!("Unknown monitor '{}'", monitor_id))
 }
 
 #[tauri::command]
-async fn select_folder(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+async fn select_folder(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    purpose: config::FolderPurpose,
+) -> Result<Option<String>, String> {
     use tauri_plugin_dialog::DialogExt;
     
-    let folder = app_handle.dialog()
-        .file()
-        .set_title("Select Media Folder")
-        .blocking_pick_folder();
+    let title = match purpose {
+        config::FolderPurpose::Media => "Select Media Folder",
+        config::FolderPurpose::Convert => "Select Convert Folder",
+        config::FolderPurpose::Presentation => "Select Presentation Folder",
+    };
+    
+    // Start where the last pick for this purpose ended, if it still exists
+    let mut dialog = app_handle.dialog().file().set_title(title);
+    let last_folder = state.config.lock().unwrap().last_folders.get(purpose).cloned();
+    if let Some(last) = last_folder.filter(|p| p.is_dir()) {
+        dialog = dialog.set_directory(last);
+    }
+    
+    let Some(folder) = dialog.blocking_pick_folder() else {
+        return Ok(None);
+    };
+    let folder = folder.into_path()
+        .map_err(|e| format!("Invalid folder: {}", e))?;
+    
+    let mut config = state.config.lock().unwrap();
+    config.last_folders.set(purpose, folder.clone());
+    if let Err(e) = config.save() {
+        eprintln!("Failed to remember folder: {}", e);
+    }
     
-    Ok(folder.map(|p| p.to_string()))
+    Ok(Some(folder.to_string_lossy().into_owned()))
 }
 
 #[derive(serde::Serialize)]
@@ -117,6 +608,8 @@ struct DisplayInfo {
     is_primary: bool,
     width: u32,
     height: u32,
+    /// OS scaling for the display (1.0 = 100%)
+    scale_factor: f64,
 }
 
 #[tauri::command]
@@ -138,6 +631,7 @@ fn get_available_displays(app_handle: tauri::AppHandle) -> Result<Vec<DisplayInf
                     is_primary: true,
                     width: 1920,
                     height: 1080,
+                    scale_factor: 1.0,
                 }]);
             }
         }
@@ -155,6 +649,7 @@ fn get_available_displays(app_handle: tauri::AppHandle) -> Result<Vec<DisplayInf
             is_primary: index == 0, // First monitor is typically primary
             width: size.width,
             height: size.height,
+            scale_factor: monitor.scale_factor(),
         });
     }
     
@@ -166,15 +661,37 @@ fn get_available_displays(app_handle: tauri::AppHandle) -> Result<Vec<DisplayInf
             is_primary: true,
             width: 1920,
             height: 1080,
+            scale_factor: 1.0,
         });
     }
     
     Ok(displays)
 }
 
+#[tauri::command]
+fn get_gpu_adapters() -> Result<Vec<gpu::GpuAdapter>, String> {
+    gpu::list_adapters()
+}
+
+#[tauri::command]
+fn get_capture_devices() -> Result<Vec<capture::CaptureDevice>, String> {
+    capture::list_devices()
+}
+
+#[tauri::command]
+fn get_audio_inputs() -> Result<Vec<String>, String> {
+    audio_reactive::list_input_devices()
+}
+
+#[tauri::command]
+fn get_audio_devices() -> Result<Vec<String>, String> {
+    audio_fixture::list_output_devices()
+}
+
 #[tauri::command]
 async fn open_output_window(
     app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
     monitor_id: String,
     display_index: usize,
     width: u32,
@@ -182,93 +699,19 @@ async fn open_output_window(
     window_x: Option<i32>,
     window_y: Option<i32>,
 ) -> Result<(), String> {
-    use tauri::Manager;
-    use tauri::webview::WebviewWindowBuilder;
-    
-    let window_label = format!("output-{}", monitor_id);
-    
-    // Close existing window if it exists
-    if let Some(existing) = app_handle.get_webview_window(&window_label) {
-        let _ = existing.close();
-    }
-    
-    // Get available monitors
-    let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
-    
-    // Debug: print all available monitors
-    println!("Available monitors:");
-    for (i, mon) in monitors.iter().enumerate() {
-        println!("  Monitor {}: position=({}, {}), size={}x{}", 
-            i, mon.position().x, mon.position().y, mon.size().width, mon.size().height);
-    }
-    
-    // Validate display_index is within bounds
-    let actual_display_index = if display_index >= monitors.len() {
-        println!("Display index {} out of bounds (have {} monitors), using primary monitor (0)", display_index, monitors.len());
-        0
-    } else {
-        display_index
+    let spec = OutputWindowSpec {
+        monitor_id,
+        display_index,
+        width,
+        height,
+        window_x,
+        window_y,
     };
     
-    // Get the target monitor for positioning
-    let monitor = monitors.get(actual_display_index)
-        .ok_or_else(|| format!("Display index {} not found", actual_display_index))?;
-    
-    let position = monitor.position();
-    let size = monitor.size();
-    
-    // Use saved window position if available, otherwise use monitor default position
-    // Note: Frontend clears window_x/window_y when display_index changes, so saved positions
-    // are always for the currently selected monitor
-    let (final_x, final_y) = if let (Some(saved_x), Some(saved_y)) = (window_x, window_y) {
-        println!("Using saved window position: ({}, {})", saved_x, saved_y);
-        (saved_x, saved_y)
-    } else {
-        // Position window at exact top-left of the monitor
-        // Offset by -10 to compensate for Windows positioning quirk
-        let default_x = position.x - 10;
-        let default_y = position.y - 10;
-        println!("No saved position, using default monitor position: ({}, {})", default_x, default_y);
-        (default_x, default_y)
-    };
+    output_window::open_window(&app_handle, &state.config, &spec)?;
     
-    println!("Monitor {} info: position=({}, {}), size={}x{}", 
-        actual_display_index, position.x, position.y, size.width, size.height);
-    println!("Window position: using=({}, {})",
-         final_x, final_y);
-    println!("Opening output window '{}' on display {} at position ({}, {}) with resolution {}x{}", 
-        window_label, actual_display_index, final_x, final_y, width, height);
-    
-    // Build and create the window - borderless, positioned at exact monitor top-left
-    let window = WebviewWindowBuilder::new(
-        &app_handle,
-        &window_label,
-        tauri::WebviewUrl::App("output.html".into())
-    )
-    .title(format!("Output Window {}", monitor_id))
-    .inner_size(width as f64, height as f64)
-    .position(final_x as f64, final_y as f64)
-    .resizable(false)
-    .decorations(false)
-    .visible(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .build()
-    .map_err(|e| format!("Failed to build window: {}", e))?;
-    
-    println!("Output window '{}' created successfully", window_label);
-    
-    // Set exact position again after creation to ensure correctness
-    window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { 
-        x: final_x, 
-        y: final_y 
-    })).map_err(|e| format!("Failed to set position: {}", e))?;
-    
-    // Force window to front
-    window.show().map_err(|e| format!("Failed to show window: {}", e))?;
-    window.set_focus().map_err(|e| format!("Failed to focus window: {}", e))?;
-    
-    println!("Output window '{}' shown and focused", window_label);
+    // Remember it so kiosk mode can bring it back if it disappears
+    state.output_windows.lock().unwrap().insert(spec.monitor_id.clone(), spec);
     
     Ok(())
 }
@@ -304,6 +747,7 @@ async fn move_output_window(
 #[tauri::command]
 async fn close_output_window(
     app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
     monitor_id: String,
 ) -> Result<(), String> {
     use tauri::Manager;
@@ -312,9 +756,15 @@ async fn close_output_window(
     
     println!("close_output_window called for '{}'", window_label);
     
+    // Closed on purpose: kiosk mode must not bring it back
+    state.output_windows.lock().unwrap().remove(&monitor_id);
+    // Receivers should see the sender go rather than a frozen last frame
+    state.spout_senders.lock().unwrap().remove(&monitor_id);
+    
     if let Some(window) = app_handle.get_webview_window(&window_label) {
         println!("Found window '{}', closing it", window_label);
-        window.close().map_err(|e| {
+        // destroy() bypasses the kiosk-mode close guard
+        window.destroy().map_err(|e| {
             println!("Failed to close window '{}': {}", window_label, e);
             e.to_string()
         })?;
@@ -326,6 +776,51 @@ async fn close_output_window(
     Ok(())
 }
 
+/// Store an update in the shared frame and pass it to the frontend, which
+/// routes it to the outputs. Returns false if the event couldn't be sent.
+fn deliver_dmx_update(app_handle: &tauri::AppHandle, dmx_frame: &Mutex<DmxFrame>, update: &DmxUpdate) -> bool {
+    {
+        let mut frame = dmx_frame.lock().unwrap();
+        for &(channel, value) in &update.channels {
+            if let Some(slot) = frame.values.get_mut(channel as usize) {
+                *slot = value;
+            }
+        }
+        frame.last_received = Some(std::time::Instant::now());
+    }
+    // Unchanged packets only keep the signal alive
+    if update.channels.is_empty() {
+        return true;
+    }
+    app_handle.emit("dmx-update", update).is_ok()
+}
+
+/// Run a frame through the whole DMX pipeline: the shared frame and the
+/// UI's dmx-update, then each monitor's playback state and output window.
+/// Everything that plays DMX (listener, replay, audio-reactive) comes
+/// through here; returns false if the UI could not be reached.
+fn apply_frame(
+    app_handle: &tauri::AppHandle,
+    dmx_frame: &Mutex<DmxFrame>,
+    playback_states: &Mutex<playback_state::PlaybackStates>,
+    update: &DmxUpdate,
+) -> bool {
+    let delivered = deliver_dmx_update(app_handle, dmx_frame, update);
+    let changes = playback_states.lock().unwrap().apply(update);
+    for change in changes {
+        publish_state_change(app_handle, &change);
+    }
+    delivered
+}
+
+/// Hand a monitor's new playback state to its output window and the UI,
+/// which loads the clip from the playback-state event
+fn publish_state_change(app_handle: &tauri::AppHandle, change: &playback_state::StateChange) {
+    playback_state::push_output_params(app_handle, change);
+    playback_state::push_preload(app_handle, change);
+    let _ = app_handle.emit("playback-state", &change.after);
+}
+
 #[tauri::command]
 fn start_sacn_listener(
     app_handle: tauri::AppHandle,
@@ -357,7 +852,8 @@ fn start_sacn_listener(
     }
     
     // Create new listener
-    let mut listener = SacnListener::new(sacn_config);
+    let mut listener = SacnListener::new(sacn_config.clone());
+    listener.watch_channels(used_channels(&config));
     
     // Consecutive emit-failure counter.  Emit can fail transiently during
     // webview initialisation, but once the webview is destroyed every emit
@@ -370,9 +866,25 @@ fn start_sacn_listener(
     // signal_stop() without going through app_handle.state() (which has
     // lifetime issues inside a 'static move closure).
     let sacn_arc_cb: Arc<Mutex<Option<SacnListener>>> = Arc::clone(&state.sacn_listener);
+    let dmx_frame_cb = Arc::clone(&state.dmx_frame);
+
+    {
+        let mut frame = state.dmx_frame.lock().unwrap();
+        frame.last_received = None;
+        frame.listening_since = Some(std::time::Instant::now());
+    }
+
+    // Applied after inputs are merged, before anything reaches the outputs
+    let glitch_filter = glitch_filter::GlitchFilter::new(&sacn_config, clip_channels(&config)).map(Mutex::new);
+    *state.playback_states.lock().unwrap() = playback_state::PlaybackStates::new(&config);
+    let playback_states_cb = Arc::clone(&state.playback_states);
 
-    listener.start(move |update: DmxUpdate| {
-        if let Err(_) = app_handle_cb.emit("dmx-update", &update) {
+    let callback: plugins::InputCallback = Arc::new(move |update: DmxUpdate| {
+        let update = match &glitch_filter {
+            Some(filter) => filter.lock().unwrap().filter(update),
+            None => update,
+        };
+        if !apply_frame(&app_handle_cb, &dmx_frame_cb, &playback_states_cb, &update) {
             let n = consec_failures_cb.fetch_add(1, Ordering::Relaxed) + 1;
             if n >= 3 {
                 // Webview is gone — stop the listener to end the flood.
@@ -383,14 +895,95 @@ fn start_sacn_listener(
         } else {
             consec_failures_cb.store(0, Ordering::Relaxed);
         }
-    })?;
+    });
+    let app_handle_events = app_handle.clone();
+    listener.on_event(move |event| match event {
+        sacn_listener::ListenerEvent::SourceWarning(warning) => {
+            let _ = app_handle_events.emit("sacn-source-warning", &warning);
+        }
+        sacn_listener::ListenerEvent::SourceTerminated(terminated) => {
+            let _ = app_handle_events.emit("source-terminated", &terminated);
+        }
+        sacn_listener::ListenerEvent::SourceConflict(conflict) => {
+            let _ = app_handle_events.emit("source-conflict", &conflict);
+        }
+        sacn_listener::ListenerEvent::Sources { sources } => {
+            let _ = app_handle_events.emit("sacn-sources", &sources);
+        }
+        sacn_listener::ListenerEvent::InterfaceError(error) => {
+            let _ = app_handle_events.emit("sacn-interface-error", &error);
+        }
+        sacn_listener::ListenerEvent::InterfaceChanged(changed) => {
+            let _ = app_handle_events.emit("sacn-interface-changed", &changed);
+        }
+        sacn_listener::ListenerEvent::Stats(stats) => {
+            let _ = app_handle_events.emit("sacn-stats", &stats);
+        }
+    });
+    // With a failover input, sACN is the primary and the backup only
+    // drives the player while sACN is silent
+    let failover = (!sacn_config.failover_input.is_empty())
+        .then(|| failover::Failover::new(app_handle.clone(), &sacn_config));
+    let sacn_callback = match &failover {
+        Some(failover) => failover.primary(Arc::clone(&callback)),
+        None => Arc::clone(&callback),
+    };
+    listener.start(move |update| sacn_callback(update))?;
 
     *state.sacn_listener.lock().unwrap() = Some(listener);
 
+    // Plugin protocols feed the same frame; one failing doesn't stop sACN
+    let old_inputs: Vec<_> = state.extra_inputs.lock().unwrap().drain(..).collect();
+    for mut old in old_inputs {
+        old.stop();
+    }
+    let merged = sacn_config.extra_inputs.iter()
+        .filter(|name| !name.eq_ignore_ascii_case(&sacn_config.failover_input))
+        .map(|name| (name, Arc::clone(&callback)));
+    let backup = failover.as_ref()
+        .map(|failover| (&sacn_config.failover_input, failover.backup(Arc::clone(&callback))));
+    for (name, input_callback) in merged.chain(backup) {
+        let started = plugins::create_input(name, &sacn_config).and_then(|mut input| {
+            input.start(input_callback)?;
+            Ok(input)
+        });
+        match started {
+            Ok(input) => {
+                println!("Input protocol '{}' started", input.name());
+                state.extra_inputs.lock().unwrap().push(input);
+            }
+            Err(e) => eprintln!("Failed to start input protocol '{}': {}", name, e),
+        }
+    }
+
     println!("sACN listener started successfully and listening for packets");
     Ok(())
 }
 
+/// Clip channels of the enabled monitors and audio fixture
+fn clip_channels(config: &AppConfig) -> Vec<u16> {
+    let mut channels: Vec<u16> = [&config.monitor1, &config.monitor2].into_iter()
+        .filter(|monitor| monitor.enabled)
+        .flat_map(|monitor| {
+            let fine = monitor.clip_16bit.then(|| monitor.clip_fine_channel());
+            std::iter::once(monitor.clip_channel()).chain(fine)
+        })
+        .collect();
+    if config.audio_fixture.enabled {
+        channels.push(config.audio_fixture.clip_channel());
+    }
+    channels
+}
+
+/// Every channel the player responds to
+fn used_channels(config: &AppConfig) -> Vec<u16> {
+    let mut channels: Vec<u16> = config.channel_functions().into_iter().map(|(_, _, channel)| channel).collect();
+    channels.retain(|channel| (1..=512).contains(channel));
+    channels.sort_unstable();
+    channels.dedup();
+    channels
+}
+
 #[tauri::command]
 fn stop_sacn_listener(state: State<AppState>) -> Result<(), String> {
     // Signal the thread to stop but do NOT join here.
@@ -404,6 +997,12 @@ fn stop_sacn_listener(state: State<AppState>) -> Result<(), String> {
     // AppState.  start_sacn_listener always takes the old listener and calls
     // stop() (which joins) before creating a new one, guaranteeing the port is
     // free before it tries to bind.
+    state.dmx_frame.lock().unwrap().listening_since = None;
+    
+    for input in state.extra_inputs.lock().unwrap().iter_mut() {
+        input.signal_stop();
+    }
+    
     let mut guard = state.sacn_listener.lock().unwrap();
     if let Some(ref mut listener) = *guard {
         listener.signal_stop();
@@ -414,17 +1013,88 @@ fn stop_sacn_listener(state: State<AppState>) -> Result<(), String> {
     }
 }
 
+/// Switch the listened universe in place: the running listener joins the
+/// new multicast group before leaving the old one, so output never drops out
+#[tauri::command]
+fn set_listen_universe(state: State<AppState>, universe: u16) -> Result<(), String> {
+    if !(1..=63999).contains(&universe) {
+        return Err(format!("Invalid universe: {}. Must be 1-63999", universe));
+    }
+    {
+        let mut config = state.config.lock().unwrap();
+        config.sacn.universe = universe;
+        config.save()?;
+    }
+    if let Some(listener) = state.sacn_listener.lock().unwrap().as_mut() {
+        listener.set_universe(universe)?;
+    }
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct LaunchOptions {
+    production: bool,
+}
+
+#[tauri::command]
+fn get_launch_options(state: State<AppState>) -> LaunchOptions {
+    LaunchOptions {
+        production: state.launch_production,
+    }
+}
+
+#[tauri::command]
+fn get_autostart() -> bool {
+    autostart::is_enabled()
+}
+
+#[tauri::command]
+fn set_autostart(enabled: bool) -> Result<(), String> {
+    if enabled {
+        autostart::enable()
+    } else {
+        autostart::disable()
+    }
+}
+
 // ========== TEST SENDER COMMANDS ==========
 
+/// `priority` (default 100) and `preview` let the test traffic compete with
+/// the real console to check how the listener arbitrates. A `destination`
+/// IP unicasts to one fixture or player instead of multicasting; with
+/// `loopback` nothing is sent on the network and the running listener gets
+/// the packets directly. `interface_ip` picks the local address (and so the
+/// NIC) to send from and `ttl` how many routers the packets may cross.
 #[tauri::command]
-fn create_test_sender(state: State<AppState>, universe: u16) -> Result<(), String> {
+#[allow(clippy::too_many_arguments)]
+fn create_test_sender(
+    state: State<AppState>,
+    universe: u16,
+    priority: Option<u8>,
+    preview: Option<bool>,
+    destination: Option<String>,
+    loopback: Option<bool>,
+    interface_ip: Option<String>,
+    ttl: Option<u32>,
+) -> Result<(), String> {
     let mut sender_guard = state.test_sender.lock().unwrap();
     
     if sender_guard.is_some() {
         return Err("Test sender already exists. Stop it first.".to_string());
     }
     
-    let sender = SacnTestSender::new(universe, "MediaPlayer Test")?;
+    let interface_ip = match interface_ip.as_deref().map(str::trim).unwrap_or("") {
+        "" => None,
+        ip => Some(ip.parse().map_err(|_| format!("Invalid interface IP: {}", ip))?),
+    };
+    let sender = SacnTestSender::new(universe, "MediaPlayer Test", TestSenderOptions {
+        priority,
+        preview: preview.unwrap_or(false),
+        destination: parse_test_destination(destination.as_deref().unwrap_or(""))?,
+        loopback: loopback.unwrap_or(false),
+        interface_ip,
+        ttl,
+    })?;
     *sender_guard = Some(sender);
     
     println!("Test sACN sender created for universe {}", universe);
@@ -449,31 +1119,89 @@ fn send_test_dmx(
     state: State<AppState>,
     channel: u16,
     value: u8,
+    priority: Option<u8>,
+    preview: Option<bool>,
+    destination: Option<String>,
 ) -> Result<(), String> {
     let mut sender_guard = state.test_sender.lock().unwrap();
     
     let sender = sender_guard.as_mut()
         .ok_or_else(|| "Test sender not created. Call create_test_sender first.".to_string())?;
     
+    apply_test_options(sender, priority, preview, destination)?;
     sender.send_test_data(vec![(channel, value)])
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 fn send_test_three_channels(
     state: State<AppState>,
     start_channel: u16,
     clip_value: u8,
     dimmer_value: u8,
     playtype_value: u8,
+    priority: Option<u8>,
+    preview: Option<bool>,
+    destination: Option<String>,
 ) -> Result<(), String> {
     let mut sender_guard = state.test_sender.lock().unwrap();
     
     let sender = sender_guard.as_mut()
         .ok_or_else(|| "Test sender not created. Call create_test_sender first.".to_string())?;
     
+    apply_test_options(sender, priority, preview, destination)?;
     sender.send_three_channel_test(start_channel, clip_value, dimmer_value, playtype_value)
 }
 
+/// Send all 512 channels in one packet (`values[0]` is channel 1), e.g.
+/// from a fader grid, so setting one channel doesn't zero the others
+#[tauri::command]
+fn send_test_frame(
+    state: State<AppState>,
+    values: Vec<u8>,
+    priority: Option<u8>,
+    preview: Option<bool>,
+    destination: Option<String>,
+) -> Result<(), String> {
+    let mut sender_guard = state.test_sender.lock().unwrap();
+    
+    let sender = sender_guard.as_mut()
+        .ok_or_else(|| "Test sender not created. Call create_test_sender first.".to_string())?;
+    
+    apply_test_options(sender, priority, preview, destination)?;
+    sender.send_frame(&values)
+}
+
+/// Priority, preview and destination given with a send stay in effect for
+/// later sends; an empty destination goes back to multicast
+fn apply_test_options(
+    sender: &mut SacnTestSender,
+    priority: Option<u8>,
+    preview: Option<bool>,
+    destination: Option<String>,
+) -> Result<(), String> {
+    if let Some(priority) = priority {
+        sender.set_priority(priority)?;
+    }
+    if let Some(preview) = preview {
+        sender.set_preview(preview)?;
+    }
+    if let Some(destination) = destination {
+        sender.set_destination(parse_test_destination(&destination)?)?;
+    }
+    Ok(())
+}
+
+fn parse_test_destination(destination: &str) -> Result<Option<std::net::IpAddr>, String> {
+    let destination = destination.trim();
+    if destination.is_empty() {
+        return Ok(None);
+    }
+    destination.parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid destination IP: {}", destination))
+}
+
 #[tauri::command]
 fn send_test_sequence(
     state: State<AppState>,
@@ -490,40 +1218,297 @@ fn send_test_sequence(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn update_output_window(
     app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
     monitor_id: String,
     media_url: Option<String>,
     dimmer: u8,
     playtype: u8,
     orientation: String,
+    transition_ms: Option<u32>,
 ) -> Result<(), String> {
-    use tauri::Manager;
+    println!("update_output_window called for '{}' with media: {:?}, dimmer: {}, playtype: {}, orientation: {}", 
+        output_window::window_label(&monitor_id), media_url, dimmer, playtype, orientation);
     
-    let window_label = format!("output-{}", monitor_id);
+    // Fill in what the console leaves unset from the clip's own defaults
+    let (dimmer, playtype) = match media_url.as_deref() {
+        Some(url) => clip_defaults::resolve(&output_window::clip_defaults(&monitor_id, url), dimmer, playtype),
+        None => (dimmer, playtype),
+    };
     
-    println!("update_output_window called for '{}' with media: {:?}, dimmer: {}, playtype: {}, orientation: {}", 
-        window_label, media_url, dimmer, playtype, orientation);
+    let (slideshow, smoothing_ms) = {
+        let config = state.config.lock().unwrap();
+        let monitor = config.monitor(&monitor_id);
+        (
+            monitor.and_then(|monitor| output_window::slideshow_for(&monitor_id, monitor, media_url.as_deref(), playtype)),
+            monitor.map(|monitor| monitor.dimmer_smoothing_ms).unwrap_or(0),
+        )
+    };
+    // With smoothing the window keeps its current level; the smoother moves it on
+    let dimmer = state.dimmer_smoother.lock().unwrap().set_target(&monitor_id, dimmer, smoothing_ms).round() as u8;
     
-    if let Some(window) = app_handle.get_webview_window(&window_label) {
-        // Use evaluate_script to directly call updateMedia function in the window
-        let media_url_js = match &media_url {
-            Some(url) => format!("'{}'", url.replace("'", "\\'")),
-            None => "null".to_string()
-        };
-        
-        let script = format!("if (typeof updateMedia === 'function') {{ updateMedia({}, {}, {}, '{}'); console.log('updateMedia called with:', {}, {}, {}, '{}'); }} else {{ console.error('updateMedia function not found!'); }}", 
-            media_url_js, dimmer, playtype, orientation, media_url_js, dimmer, playtype, orientation);
-        
-        println!("Executing script in window '{}'", window_label);
-        window.eval(&script)
-            .map_err(|e| format!("Failed to execute script: {}", e))?;
-        println!("Script executed successfully");
-    } else {
-        println!("Window '{}' not found", window_label);
-    }
+    output_window::push_media_update(&app_handle, &monitor_id, media_url.as_deref(), dimmer, playtype, &orientation, slideshow.as_ref(), transition_ms)
+}
+
+#[tauri::command]
+async fn update_output_volume(
+    app_handle: tauri::AppHandle,
+    monitor_id: String,
+    volume: Option<u8>,
+) -> Result<(), String> {
+    output_window::push_volume(&app_handle, &monitor_id, volume)
+}
+
+/// Buffer a clip from the monitor's current bank in its output window, so
+/// the next change to it starts at once. Returns the clip's URL, or None
+/// if it has no file (which also lets go of the buffered clip).
+#[tauri::command]
+fn preload_clip(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    monitor_id: String,
+    clip: u16,
+) -> Result<Option<String>, String> {
+    let monitor = state.config.lock().unwrap()
+        .monitor(&monitor_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown monitor '{}'", monitor_id))?;
+    let bank = state.playback_states.lock().unwrap()
+        .get(&monitor_id)
+        .and_then(|playback_state| playback_state.bank);
+    let media_url = playback_state::clip_url(&monitor, clip, bank);
+    output_window::push_preload(&app_handle, &monitor_id, media_url.as_deref())?;
+    Ok(media_url)
+}
+
+#[tauri::command]
+async fn update_output_motion(
+    app_handle: tauri::AppHandle,
+    monitor_id: String,
+    pan: u8,
+    zoom: u8,
+    speed: u8,
+) -> Result<(), String> {
+    output_window::push_motion_update(&app_handle, &monitor_id, pan, zoom, speed)
+}
+
+#[tauri::command]
+async fn update_output_effects(
+    app_handle: tauri::AppHandle,
+    monitor_id: String,
+    pixelate: u8,
+    chroma_shift: u8,
+    vignette: u8,
+) -> Result<(), String> {
+    output_window::push_effects(&app_handle, &monitor_id, pixelate, chroma_shift, vignette)
+}
+
+/// Called periodically by each output window with what it is showing
+#[tauri::command]
+fn report_playback_status(state: State<AppState>, monitor_id: String, report: PlaybackReport) {
+    let clip_16bit = state.config.lock().unwrap().monitor(&monitor_id)
+        .is_some_and(|monitor| monitor.clip_16bit);
+    state.playback.lock().unwrap().record(&monitor_id, report, clip_16bit);
+}
+
+/// Called by an output window when a play-once clip reaches its end
+#[tauri::command]
+fn report_clip_finished(
+    app_handle: tauri::AppHandle,
+    state: State<AppState>,
+    monitor_id: String,
+    media_url: String,
+) {
+    let clip_16bit = state.config.lock().unwrap().monitor(&monitor_id)
+        .is_some_and(|monitor| monitor.clip_16bit);
+    let finished = trigger_output::ClipFinished {
+        clip: media_scanner::clip_value_from_url(&media_url, clip_16bit),
+        monitor_id,
+        media_url,
+    };
+    println!("Clip {:?} finished on {}", finished.clip, finished.monitor_id);
+    let _ = app_handle.emit("clip-finished", &finished);
     
-    Ok(())
+    let trigger = state.config.lock().unwrap().clip_finished_trigger.clone();
+    if let Err(e) = trigger_output::send_clip_finished(&trigger, &finished) {
+        eprintln!("{}", e);
+    }
+}
+
+#[tauri::command]
+fn get_playback_status(state: State<AppState>) -> Vec<MonitorPlayback> {
+    let tracker = state.playback.lock().unwrap();
+    vec![tracker.status("monitor1"), tracker.status("monitor2")]
+}
+
+/// Record every change of the incoming DMX to a file the user picks;
+/// returns the path, or None if the dialog was cancelled
+#[tauri::command]
+async fn start_dmx_recording(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    if state.dmx_recorder.lock().unwrap().is_some() {
+        return Err("Already recording".to_string());
+    }
+    let Some(path) = app_handle.dialog().file()
+        .set_title("Record DMX")
+        .set_file_name("recording.dmxrec")
+        .add_filter("DMX Recording", &["dmxrec"])
+        .blocking_save_file() else {
+        return Ok(None);
+    };
+    let path = path.into_path()
+        .map_err(|e| format!("Invalid path: {}", e))?;
+
+    let recorder = dmx_recorder::Recorder::start(&path, Arc::clone(&state.dmx_frame))?;
+    *state.dmx_recorder.lock().unwrap() = Some(recorder);
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[tauri::command]
+fn stop_dmx_recording(state: State<AppState>) -> Result<dmx_recorder::RecordingSummary, String> {
+    let recorder = state.dmx_recorder.lock().unwrap().take()
+        .ok_or_else(|| "Not recording".to_string())?;
+    recorder.stop()
+}
+
+/// Replay a recording the user picks through the normal DMX pipeline, as if
+/// the console were sending it; returns the path, or None if cancelled
+#[tauri::command]
+async fn play_dmx_recording(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    looped: bool,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let Some(path) = app_handle.dialog().file()
+        .set_title("Replay DMX Recording")
+        .add_filter("DMX Recording", &["dmxrec"])
+        .blocking_pick_file() else {
+        return Ok(None);
+    };
+    let path = path.into_path()
+        .map_err(|e| format!("Invalid path: {}", e))?;
+
+    // Stop a replay that is still going outside the lock
+    let old_replay = state.dmx_replay.lock().unwrap().take();
+    if let Some(mut old) = old_replay {
+        old.stop();
+    }
+
+    let universe = state.config.lock().unwrap().sacn.universe;
+    let dmx_frame = Arc::clone(&state.dmx_frame);
+    let playback_states = Arc::clone(&state.playback_states);
+    let app_handle_cb = app_handle.clone();
+    let replay = dmx_recorder::Replay::start(&path, universe, looped, move |update| {
+        apply_frame(&app_handle_cb, &dmx_frame, &playback_states, &update);
+    })?;
+    *state.dmx_replay.lock().unwrap() = Some(replay);
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+#[tauri::command]
+fn stop_dmx_replay(state: State<AppState>) {
+    let replay = state.dmx_replay.lock().unwrap().take();
+    if let Some(mut replay) = replay {
+        replay.stop();
+    }
+}
+
+/// Whether a recording or a replay is running, for the DMX tab
+#[tauri::command]
+fn get_dmx_recorder_status(state: State<AppState>) -> (bool, bool) {
+    let recording = state.dmx_recorder.lock().unwrap().is_some();
+    let replaying = state.dmx_replay.lock().unwrap().as_ref().is_some_and(|replay| replay.is_running());
+    (recording, replaying)
+}
+
+/// Every sACN source announcing itself with universe discovery; empty while
+/// the listener isn't running
+#[tauri::command]
+fn get_discovered_sources(state: State<AppState>) -> Vec<sacn_listener::DiscoveredSource> {
+    state.sacn_listener.lock().unwrap().as_ref()
+        .map(|listener| listener.discovered_sources())
+        .unwrap_or_default()
+}
+
+/// Registered input protocols; those named in `sacn.extra_inputs` (or as
+/// `sacn.failover_input`) are started with the listener
+#[tauri::command]
+fn get_available_inputs(state: State<AppState>) -> Vec<plugins::AvailableInput> {
+    let running: Vec<String> = state.extra_inputs.lock().unwrap().iter()
+        .map(|input| input.name().to_string())
+        .collect();
+    let sacn_running = state.sacn_listener.lock().unwrap().as_ref().is_some_and(|listener| listener.is_running());
+    plugins::input_protocol_names().into_iter()
+        .map(|name| plugins::AvailableInput {
+            running: if name == "sacn" { sacn_running } else { running.contains(&name) },
+            name,
+        })
+        .collect()
+}
+
+/// Sources sending the listened universe with their packet rates; empty
+/// while the listener isn't running
+#[tauri::command]
+fn get_active_sources(state: State<AppState>) -> Vec<sacn_listener::ActiveSource> {
+    state.sacn_listener.lock().unwrap().as_ref()
+        .map(|listener| listener.active_sources())
+        .unwrap_or_default()
+}
+
+/// Packet counters, rates and per-source counts for the listened universe;
+/// None while the listener isn't running. Also sent every second as "sacn-stats".
+#[tauri::command]
+fn get_sacn_stats(state: State<AppState>) -> Option<sacn_listener::SacnStats> {
+    state.sacn_listener.lock().unwrap().as_ref().map(|listener| listener.stats())
+}
+
+/// Current state of the whole player, for support and show reports
+#[tauri::command]
+fn get_state_snapshot(state: State<AppState>) -> snapshot::StateSnapshot {
+    let monitors = get_playback_status(state.clone());
+    let config = state.config.lock().unwrap().clone();
+    let cue = state.cue_state.lock().unwrap().clone();
+    let timeline = timeline::state(&state.timeline);
+    let frame = state.dmx_frame.lock().unwrap();
+    snapshot::collect(&config, &frame, monitors, cue, timeline)
+}
+
+/// Take a snapshot and save it as JSON where the user picks; returns the
+/// path, or None if the dialog was cancelled
+#[tauri::command]
+async fn save_state_snapshot(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    // Taken before the dialog opens, so it shows the moment of the click
+    let snapshot = get_state_snapshot(state.clone());
+    let json = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+
+    let Some(path) = app_handle.dialog().file()
+        .set_title("Save State Snapshot")
+        .set_file_name(format!("state-snapshot-{}.json", snapshot.taken_at))
+        .add_filter("JSON", &["json"])
+        .blocking_save_file() else {
+        return Ok(None);
+    };
+    let path = path.into_path()
+        .map_err(|e| format!("Invalid path: {}", e))?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    println!("Saved state snapshot to {:?}", path);
+    Ok(Some(path.to_string_lossy().into_owned()))
 }
 
 // ── FFmpeg helpers ────────────────────────────────────────────────────────────
@@ -638,13 +1623,23 @@ fn list_convert_files(folder: String) -> Result<Vec<String>, String> {
     Ok(files)
 }
 
+#[derive(serde::Serialize)]
+struct MediaProbe {
+    width: u32,
+    height: u32,
+    /// e.g. "bt709"; None if the file doesn't say
+    color_transfer: Option<String>,
+    /// PQ or HLG; tone-mapped when converted
+    hdr: bool,
+}
+
 #[tauri::command]
-fn probe_media(source_path: String) -> Result<(u32, u32), String> {
+fn probe_media(source_path: String) -> Result<MediaProbe, String> {
     let ffprobe = find_ffprobe()
         .ok_or_else(|| "FFprobe not found. Install FFmpeg from https://ffmpeg.org".to_string())?;
     let output = std::process::Command::new(&ffprobe)
         .args(["-v", "error", "-select_streams", "v:0",
-               "-show_entries", "stream=width,height",
+               "-show_entries", "stream=width,height,color_transfer",
                "-of", "csv=p=0", &source_path])
         .output()
         .map_err(|e| format!("Failed to run ffprobe: {}", e))?;
@@ -655,17 +1650,28 @@ fn probe_media(source_path: String) -> Result<(u32, u32), String> {
     }
     let w = parts[0].trim().parse::<u32>().map_err(|_| format!("Bad width value: '{}'", parts[0]))?;
     let h = parts[1].trim().parse::<u32>().map_err(|_| format!("Bad height value: '{}'", parts[1]))?;
-    Ok((w, h))
+    let color_transfer = parts.get(2)
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty() && *t != "unknown")
+        .map(str::to_string);
+    let hdr = color_transfer.as_deref().is_some_and(media_import::is_hdr_transfer);
+    Ok(MediaProbe { width: w, height: h, color_transfer, hdr })
 }
 
 #[tauri::command]
-async fn split_media(source_path: String, top_folder: String, bottom_folder: String) -> Result<(String, String), String> {
+async fn split_media(
+    state: State<'_, AppState>,
+    source_path: String,
+    top_folder: String,
+    bottom_folder: String,
+) -> Result<(String, String), String> {
     let ffmpeg = find_ffmpeg()
         .ok_or_else(|| "FFmpeg not found. Install from https://ffmpeg.org".to_string())?;
+    let conversion = state.config.lock().unwrap().conversion.clone();
 
-    let (w, h) = probe_media(source_path.clone())?;
-    if w != 1080 || h != 3840 {
-        return Err(format!("File dimensions are {}×{} — only 1080×3840 is supported.", w, h));
+    let probe = probe_media(source_path.clone())?;
+    if probe.width != 1080 || probe.height != 3840 {
+        return Err(format!("File dimensions are {}×{} — only 1080×3840 is supported.", probe.width, probe.height));
     }
 
     let src = std::path::Path::new(&source_path);
@@ -683,9 +1689,13 @@ async fn split_media(source_path: String, top_folder: String, bottom_folder: Str
 
     for (offset_y, out_path) in [("0", &top_path), ("1920", &bottom_path)] {
         let crop = format!("crop=1080:1920:0:{}", offset_y);
+        // Deinterlace / tone-map / frame rate steps run before the crop
+        let mut video_filters = media_import::video_filters(&conversion, probe.hdr);
+        video_filters.push(crop.clone());
+        let video_filter = video_filters.join(",");
         let mut args: Vec<&str> = vec!["-y", "-i", &source_path];
         if is_video {
-            args.extend(["-filter:v", &crop, "-c:a", "copy"]);
+            args.extend(["-filter:v", &video_filter, "-c:a", "copy"]);
         } else {
             args.extend(["-vf", &crop]);
         }
@@ -705,6 +1715,16 @@ async fn split_media(source_path: String, top_folder: String, bottom_folder: Str
     Ok((top_path, bottom_path))
 }
 
+/// Prefer the V4L2 hardware H.264 decoders (Raspberry Pi and similar SoCs)
+/// over software decoding in WebKitGTK's GStreamer pipeline.
+#[cfg(target_os = "linux")]
+fn enable_hardware_decode() {
+    if std::env::var_os("GST_PLUGIN_FEATURE_RANK").is_none() {
+        std::env::set_var("GST_PLUGIN_FEATURE_RANK", "v4l2h264dec:MAX,v4l2slh264dec:MAX");
+        println!("Lightweight playback: preferring V4L2 hardware H.264 decode");
+    }
+}
+
 fn main() {
     // Load configuration from file or create default
     let config = AppConfig::load().unwrap_or_else(|e| {
@@ -712,24 +1732,75 @@ fn main() {
         AppConfig::default()
     });
     
+    plugins::register_builtin();
+    
+    if std::env::args().any(|arg| arg == headless::HEADLESS_ARG) {
+        if let Err(e) = headless::run(config) {
+            eprintln!("Headless mode failed: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+    
+    // Must happen before the first webview starts GStreamer
+    #[cfg(target_os = "linux")]
+    if config.playback_profile == config::PlaybackProfile::Lightweight {
+        enable_hardware_decode();
+    }
+    
+    // Replay and audio-reactive mode play through these before any listener starts
+    let playback_states = playback_state::PlaybackStates::new(&config);
     let state = AppState {
         config: Arc::new(Mutex::new(config)),
         sacn_listener: Arc::new(Mutex::new(None)),
+        extra_inputs: Arc::new(Mutex::new(Vec::new())),
         test_sender: Arc::new(Mutex::new(None)),
+        dmx_frame: Arc::new(Mutex::new(DmxFrame::default())),
+        playback: Arc::new(Mutex::new(PlaybackTracker::default())),
+        launch_production: std::env::args().any(|arg| arg == autostart::PRODUCTION_ARG),
+        output_windows: Arc::new(Mutex::new(HashMap::new())),
+        setup_geometry: Arc::new(Mutex::new(HashMap::new())),
+        cue_state: Arc::new(Mutex::new(cue_stack::CueState::default())),
+        timeline: Arc::new(Mutex::new(timeline::Transport::default())),
+        pixel_map_sender: Arc::new(Mutex::new(None)),
+        spout_senders: Arc::new(Mutex::new(HashMap::new())),
+        dmx_recorder: Arc::new(Mutex::new(None)),
+        dmx_replay: Arc::new(Mutex::new(None)),
+        playback_states: Arc::new(Mutex::new(playback_states)),
+        dimmer_smoother: Arc::new(Mutex::new(dimmer_smoothing::DimmerSmoother::default())),
     };
     
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_updater::Builder::new().build())
         .manage(state)
         .setup(|app| {
+            let info = app_info::collect();
+            println!(
+                "StagePlayer DMX {} on {} (sacn {}, FFmpeg {})",
+                info.version,
+                info.os,
+                info.sacn_version,
+                info.ffmpeg_version.as_deref().unwrap_or("not found")
+            );
+            
             // Stop the sACN listener automatically when the main window is
             // destroyed (e.g. user closes the app).  React cleanup may not
             // fire in that case, so without this the listener thread runs
             // forever and floods the terminal with emit errors.
             let sacn_arc: Arc<Mutex<Option<SacnListener>>> = Arc::clone(&app.state::<AppState>().sacn_listener);
+            let kiosk_config: Arc<Mutex<AppConfig>> = Arc::clone(&app.state::<AppState>().config);
             if let Some(window) = app.get_webview_window("main") {
                 window.on_window_event(move |event| {
+                    // Kiosk mode: the app can only be closed after switching it off
+                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                        if kiosk_config.lock().unwrap().kiosk_mode {
+                            println!("Kiosk mode: blocked close request for main window");
+                            api.prevent_close();
+                        }
+                    }
                     if let tauri::WindowEvent::Destroyed = event {
                         if let Some(ref mut l) = *sacn_arc.lock().unwrap() {
                             l.signal_stop();
@@ -738,32 +1809,236 @@ fn main() {
                     }
                 });
             }
+            
+            // Play each monitor's fallback clip when sACN goes quiet
+            let app_state = app.state::<AppState>();
+            fallback::spawn_watchdog(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Ease stepped console dimmer fades on monitors that ask for it
+            dimmer_smoothing::spawn_smoother(
+                app.handle().clone(),
+                Arc::clone(&app_state.dimmer_smoother),
+            );
+            
+            // Drive generator clips (solid color etc.) from their channels
+            generator::spawn_generator_driver(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Warn before a full disk breaks conversions or imports
+            disk_space::spawn_disk_monitor(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+            );
+            
+            // Look for a newer release once the machine has settled
+            updater::spawn_startup_check(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Drive a monitor from music when there is no console
+            audio_reactive::spawn_audio_reactive(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+                Arc::clone(&app_state.playback_states),
+            );
+            
+            // Run the cue stack from its Go channel
+            cue_stack::spawn_cue_driver(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+                Arc::clone(&app_state.cue_state),
+            );
+            
+            // Show or hide captions from settings and the subtitle channel
+            subtitles::spawn_subtitle_switch(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Fire macros from the control channel
+            macros::spawn_macro_driver(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Take clip / dimmer changes from OSC
+            osc_input::spawn_osc_input(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Take clip / dimmer changes from a MIDI keyboard or controller
+            midi_input::spawn_midi_input(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Serve the HTTP control API
+            http_api::spawn_http_api(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+                Arc::clone(&app_state.playback),
+            );
+            
+            // Publish status and take commands over MQTT
+            mqtt::spawn_mqtt(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+                Arc::clone(&app_state.playback),
+            );
+            
+            // Run the integrator's script hooks
+            scripting::spawn_script_host(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Play the timeline while its clock runs
+            timeline::spawn_timeline_driver(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.timeline),
+            );
+            
+            // Chase incoming Art-Net or MIDI timecode
+            timecode::spawn_timecode_input(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.playback),
+                Arc::clone(&app_state.timeline),
+            );
+            
+            // Report playback state back to the console
+            feedback::spawn_feedback_sender(
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.playback),
+            );
+            
+            // Fire sound effects from the audio fixture's channels
+            audio_fixture::spawn_audio_fixture(
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.dmx_frame),
+            );
+            
+            // Serve RTSP / NDI clips to the output windows
+            stream_input::start_relay_server();
+            
+            // Keep outputs up and the machine awake in kiosk mode
+            kiosk::spawn_kiosk_guard(
+                app.handle().clone(),
+                Arc::clone(&app_state.config),
+                Arc::clone(&app_state.output_windows),
+            );
+            
+            // Blackout / freeze / restore shortcuts
+            let hotkey_config = app_state.config.lock().unwrap().hotkeys.clone();
+            if let Err(e) = hotkeys::register_hotkeys(app.handle(), &hotkey_config) {
+                eprintln!("{}", e);
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_config,
             update_config,
+            trigger_panic_action,
             get_network_interfaces,
-            scan_media_folder,
-            get_media_files,
+            list_media,
+            import_media,
+            renumber_media,
+            swap_media,
+            delete_media,
+            get_disk_space,
+            open_in_explorer,
+            get_app_info,
+            check_for_updates,
+            install_update,
+            self_test,
+            cue_go,
+            cue_reset,
+            get_cue_state,
+            timeline_play,
+            timeline_pause,
+            timeline_locate,
+            get_timeline_state,
+            get_pixel_map,
+            get_spout,
+            send_spout_frame,
+            get_effects_enabled,
+            get_dimming_curve,
+            get_output_params,
+            set_output_click_through,
+            set_output_setup_mode,
+            get_captions_visible,
+            report_pixel_samples,
             select_folder,
             get_available_displays,
+            get_gpu_adapters,
+            get_audio_devices,
+            get_audio_inputs,
+            get_capture_devices,
             open_output_window,
             close_output_window,
             update_output_window,
+            update_output_volume,
+            preload_clip,
+            update_output_motion,
+            update_output_effects,
             move_output_window,
+            report_playback_status,
+            report_clip_finished,
+            get_playback_status,
+            get_state_snapshot,
+            save_state_snapshot,
             start_sacn_listener,
             stop_sacn_listener,
+            set_listen_universe,
+            get_discovered_sources,
+            get_sacn_stats,
+            get_active_sources,
+            get_available_inputs,
+            start_dmx_recording,
+            stop_dmx_recording,
+            play_dmx_recording,
+            stop_dmx_replay,
+            get_dmx_recorder_status,
+            get_launch_options,
+            get_autostart,
+            set_autostart,
             create_test_sender,
             stop_test_sender,
             send_test_dmx,
             send_test_three_channels,
+            send_test_frame,
             send_test_sequence,
             check_ffmpeg,
             list_convert_files,
             probe_media,
             split_media
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_, event| {
+            // Kiosk mode may have silenced the user's notifications
+            if let tauri::RunEvent::Exit = event {
+                kiosk::restore_notifications();
+            }
+        });
 }
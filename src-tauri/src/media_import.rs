@@ -0,0 +1,299 @@
+use crate::config::{ConversionConfig, Deinterlace, MediaFile};
+use crate::media_scanner;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Copy dropped files into a media folder, each under the lowest free DMX
+/// value (1-254). With an FFmpeg path, formats the player can't show are
+/// converted: videos to H.264 MP4, images to PNG. With an FFprobe path too,
+/// HDR videos are found and tone-mapped. Returns the imported files and a
+/// reason for each file that was skipped.
+pub fn import_files(
+    folder: &Path,
+    sources: &[PathBuf],
+    ffmpeg: Option<&str>,
+    ffprobe: Option<&str>,
+    conversion: &ConversionConfig,
+) -> (Vec<MediaFile>, Vec<String>) {
+    let mut used: HashSet<u16> = media_scanner::list_media(folder, None)
+        .map(|entries| entries.into_iter().map(|e| e.dmx_value).collect())
+        .unwrap_or_default();
+
+    let mut imported = Vec::new();
+    let mut skipped = Vec::new();
+
+    for source in sources {
+        let display_name = source.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| source.display().to_string());
+
+        let Some(value) = (1..=254u16).find(|v| !used.contains(v)) else {
+            skipped.push(format!("{}: no free DMX values left in the folder", display_name));
+            continue;
+        };
+
+        match import_file(folder, source, value as u8, ffmpeg, ffprobe, conversion) {
+            Ok(media) => {
+                println!("Imported {} as {}", display_name, media.filename);
+                used.insert(value);
+                imported.push(media);
+            }
+            Err(e) => skipped.push(format!("{}: {}", display_name, e)),
+        }
+    }
+
+    (imported, skipped)
+}
+
+fn import_file(
+    folder: &Path,
+    source: &Path,
+    value: u8,
+    ffmpeg: Option<&str>,
+    ffprobe: Option<&str>,
+    conversion: &ConversionConfig,
+) -> Result<MediaFile, String> {
+    if !source.is_file() {
+        return Err("not a file".to_string());
+    }
+
+    let stem = source.file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| "cannot determine file name".to_string())?;
+    // Drop an existing "###_" prefix so names don't stack up
+    let stem = match media_scanner::parse_dmx_prefix(stem) {
+        Some(_) => &stem[4..],
+        None => stem,
+    };
+    let ext = source.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let conversion = match ext.as_str() {
+        "mp4" | "jpg" | "jpeg" | "png" | "url" | "capture" | "stream" => None,
+        "mov" | "mkv" | "avi" | "webm" | "m4v" | "wmv" => Some("mp4"),
+        "bmp" | "gif" | "webp" | "tif" | "tiff" => Some("png"),
+        _ => return Err(format!("unsupported file type \".{}\"", ext)),
+    };
+
+    let target_ext = conversion.unwrap_or(ext.as_str());
+    let target = folder.join(format!("{:03}_{}.{}", value, stem, target_ext));
+
+    match conversion {
+        None => {
+            std::fs::copy(source, &target)
+                .map_err(|e| format!("failed to copy: {}", e))?;
+        }
+        Some(format) => {
+            let ffmpeg = ffmpeg
+                .ok_or_else(|| format!("\".{}\" needs converting and FFmpeg was not found", ext))?;
+            let hdr = ffprobe
+                .and_then(|ffprobe| probe_color_transfer(ffprobe, source))
+                .is_some_and(|transfer| is_hdr_transfer(&transfer));
+            convert(ffmpeg, source, &target, format, &video_filters(conversion, hdr))?;
+        }
+    }
+
+    media_scanner::scan_media_folder(folder)
+        .map_err(|e| format!("failed to read folder: {}", e))?
+        .remove(&value)
+        .ok_or_else(|| "imported file could not be read back".to_string())
+}
+
+fn convert(ffmpeg: &str, source: &Path, target: &Path, format: &str, filters: &[String]) -> Result<(), String> {
+    let mut command = std::process::Command::new(ffmpeg);
+    command.arg("-y").arg("-i").arg(source);
+    if format == "mp4" {
+        if !filters.is_empty() {
+            command.arg("-vf").arg(filters.join(","));
+        }
+        command.args(["-c:v", "libx264", "-pix_fmt", "yuv420p", "-crf", "18",
+                      "-c:a", "aac", "-movflags", "+faststart"]);
+    } else {
+        // First frame only (animated GIF/WebP)
+        command.args(["-frames:v", "1"]);
+    }
+    command.arg(target);
+
+    let result = command.output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !result.status.success() {
+        let _ = std::fs::remove_file(target);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        let tail_start = stderr.char_indices()
+            .map(|(i, _)| i)
+            .find(|&i| i >= stderr.len().saturating_sub(500))
+            .unwrap_or(0);
+        return Err(format!("FFmpeg error: {}", stderr[tail_start..].trim()));
+    }
+    Ok(())
+}
+
+/// PQ (HDR10) and HLG, as ffprobe names them
+pub fn is_hdr_transfer(transfer: &str) -> bool {
+    matches!(transfer, "smpte2084" | "arib-std-b67")
+}
+
+/// Color transfer of a file's first video stream, e.g. "bt709"
+pub fn probe_color_transfer(ffprobe: &str, path: &Path) -> Option<String> {
+    let output = std::process::Command::new(ffprobe)
+        .args(["-v", "error", "-select_streams", "v:0",
+               "-show_entries", "stream=color_transfer", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .ok()?;
+    let transfer = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!transfer.is_empty() && transfer != "unknown").then_some(transfer)
+}
+
+/// FFmpeg video filters for the conversion settings, in the order they run.
+/// `hdr` is whether the source is HDR.
+pub fn video_filters(conversion: &ConversionConfig, hdr: bool) -> Vec<String> {
+    let mut filters = Vec::new();
+    match conversion.deinterlace {
+        Deinterlace::Off => {}
+        Deinterlace::Yadif => filters.push("yadif=mode=send_frame:deint=interlaced".to_string()),
+        Deinterlace::Bwdif => filters.push("bwdif=mode=send_frame:deint=interlaced".to_string()),
+    }
+    if hdr && conversion.tone_map {
+        // Linear light, Hable curve, back to BT.709 (needs FFmpeg with zimg)
+        filters.push("zscale=t=linear:npl=100,format=gbrpf32le,zscale=p=bt709,\
+                      tonemap=tonemap=hable:desat=0,zscale=t=bt709:m=bt709:r=tv,format=yuv420p".to_string());
+    }
+    if let Some(fps) = conversion.frame_rate.filter(|fps| *fps > 0) {
+        if conversion.interpolate {
+            filters.push(format!("minterpolate=fps={}:mi_mode=mci:mc_mode=aobmc:vsbmc=1", fps));
+        } else {
+            filters.push(format!("fps={}", fps));
+        }
+    }
+    filters
+}
+
+/// Move every file numbered `from` to `to`, e.g. 012_intro.mp4 → 045_intro.mp4.
+/// Fails without touching anything if `to` is already in use.
+pub fn renumber(folder: &Path, from: u16, to: u16) -> Result<(), String> {
+    check_slot(to)?;
+    if from == to {
+        return Err(format!("Clip is already numbered {:03}", to));
+    }
+    let sources = files_numbered(folder, from)?;
+    if sources.is_empty() {
+        return Err(format!("No file is numbered {:03}", from));
+    }
+    let taken = files_numbered(folder, to)?;
+    if let Some(existing) = taken.first() {
+        return Err(format!("{:03} is already used by {}", to, file_name(existing)));
+    }
+
+    rename_all(&sources, to)
+}
+
+/// Exchange the files numbered `a` and `b`. Either value may be empty, which
+/// makes this a plain renumber. If a rename fails, the ones already done are
+/// reverted.
+pub fn swap(folder: &Path, a: u16, b: u16) -> Result<(), String> {
+    check_slot(a)?;
+    check_slot(b)?;
+    if a == b {
+        return Err("Cannot swap a clip with itself".to_string());
+    }
+    let files_a = files_numbered(folder, a)?;
+    let files_b = files_numbered(folder, b)?;
+    if files_a.is_empty() && files_b.is_empty() {
+        return Err(format!("No file is numbered {:03} or {:03}", a, b));
+    }
+
+    // Park `a` under temporary names first so the two sets never collide
+    let parked: Vec<PathBuf> = files_a.iter()
+        .map(|path| path.with_file_name(format!(".swap-{}", file_name(path))))
+        .collect();
+    let mut done: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let result = (|| -> Result<(), String> {
+        for (from, to) in files_a.iter().zip(&parked) {
+            rename(from, to, &mut done)?;
+        }
+        for from in &files_b {
+            rename(from, &with_value(from, a), &mut done)?;
+        }
+        for (parked, original) in parked.iter().zip(&files_a) {
+            rename(parked, &with_value(original, b), &mut done)?;
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        for (from, to) in done.iter().rev() {
+            if let Err(e) = std::fs::rename(to, from) {
+                eprintln!("Failed to restore {}: {}", from.display(), e);
+            }
+        }
+    }
+    result
+}
+
+/// Move every file numbered `value` to the recycle bin / trash
+pub fn delete(folder: &Path, value: u16) -> Result<(), String> {
+    let files = files_numbered(folder, value)?;
+    if files.is_empty() {
+        return Err(format!("No file is numbered {:03}", value));
+    }
+    for path in &files {
+        trash::delete(path)
+            .map_err(|e| format!("Failed to move {} to the trash: {}", file_name(path), e))?;
+        println!("Moved {} to the trash", file_name(path));
+    }
+    Ok(())
+}
+
+/// Values a clip can be moved to: 000 shows nothing and 255 closes the output
+fn check_slot(value: u16) -> Result<(), String> {
+    if !(1..=254).contains(&value) {
+        return Err(format!("DMX value {} is not a clip slot (use 1-254)", value));
+    }
+    Ok(())
+}
+
+/// Every file in the folder whose name starts with this value, whatever its type
+fn files_numbered(folder: &Path, value: u16) -> Result<Vec<PathBuf>, String> {
+    let entries = media_scanner::list_media(folder, None)
+        .map_err(|e| format!("Failed to read folder: {}", e))?;
+    Ok(entries.into_iter()
+        .filter(|e| e.dmx_value == value)
+        .map(|e| e.path)
+        .collect())
+}
+
+fn rename_all(sources: &[PathBuf], value: u16) -> Result<(), String> {
+    let mut done = Vec::new();
+    for source in sources {
+        if let Err(e) = rename(source, &with_value(source, value), &mut done) {
+            for (from, to) in done.iter().rev() {
+                let _ = std::fs::rename(to, from);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn rename(from: &Path, to: &Path, done: &mut Vec<(PathBuf, PathBuf)>) -> Result<(), String> {
+    std::fs::rename(from, to)
+        .map_err(|e| format!("Failed to rename {}: {}", file_name(from), e))?;
+    println!("Renamed {} to {}", file_name(from), file_name(to));
+    done.push((from.to_path_buf(), to.to_path_buf()));
+    Ok(())
+}
+
+/// Same file name with the "###" prefix replaced
+fn with_value(path: &Path, value: u16) -> PathBuf {
+    let name = file_name(path);
+    path.with_file_name(format!("{:03}{}", value, &name[3..]))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
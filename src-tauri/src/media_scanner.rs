@@ -1,9 +1,46 @@
-use crate::config::{MediaFile, MediaType};
+use crate::clip_defaults::{self, ClipDefaults};
+use crate::config::{MediaEntry, MediaFile, MediaType};
+use crate::plugins;
+use crate::stream_input;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::Path;
 
 /// Scans a directory for media files in format: 001_filename.ext
 pub fn scan_media_folder(folder: &Path) -> Result<HashMap<u8, MediaFile>, std::io::Error> {
+    scan_folder(folder, parse_media_filename).map(narrow)
+}
+
+/// Scans a directory for media files in format: 00261_filename.ext, for
+/// monitors with 16-bit clip selection
+pub fn scan_media_folder_16bit(folder: &Path) -> Result<HashMap<u16, MediaFile>, std::io::Error> {
+    scan_folder(folder, parse_media_filename_16bit)
+}
+
+/// Scans a directory for a monitor's clips, keyed by clip value: five-digit
+/// prefixes with 16-bit clip selection, three-digit ones otherwise
+pub fn scan_clips(folder: &Path, clip_16bit: bool) -> Result<HashMap<u16, MediaFile>, std::io::Error> {
+    if clip_16bit {
+        scan_media_folder_16bit(folder)
+    } else {
+        scan_folder(folder, parse_media_filename)
+    }
+}
+
+/// Scans a directory for sound files in format: 001_filename.ext
+pub fn scan_audio_folder(folder: &Path) -> Result<HashMap<u8, MediaFile>, std::io::Error> {
+    scan_folder(folder, parse_audio_filename).map(narrow)
+}
+
+/// Key by 8-bit value; the three-digit parsers only accept 1-255
+fn narrow(media_map: HashMap<u16, MediaFile>) -> HashMap<u8, MediaFile> {
+    media_map.into_iter().map(|(value, media)| (value as u8, media)).collect()
+}
+
+fn scan_folder(
+    folder: &Path,
+    parse: fn(&str, std::path::PathBuf) -> Option<MediaFile>,
+) -> Result<HashMap<u16, MediaFile>, std::io::Error> {
     let mut media_map = HashMap::new();
     
     if !folder.exists() || !folder.is_dir() {
@@ -16,7 +53,7 @@ pub fn scan_media_folder(folder: &Path) -> Result<HashMap<u8, MediaFile>, std::i
         
         if path.is_file() {
             if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                if let Some(media_file) = parse_media_filename(filename, path.clone()) {
+                if let Some(media_file) = parse(filename, path.clone()) {
                     media_map.insert(media_file.dmx_value, media_file);
                 }
             }
@@ -26,42 +63,140 @@ pub fn scan_media_folder(folder: &Path) -> Result<HashMap<u8, MediaFile>, std::i
     Ok(media_map)
 }
 
-/// Parse filename: 001_clipname.mp4
-fn parse_media_filename(filename: &str, full_path: std::path::PathBuf) -> Option<MediaFile> {
-    // Must be at least 5 chars: 000_x.ext
-    if filename.len() < 5 {
-        return None;
+/// Defaults for the clips of a whole folder, keyed by file name
+pub const CLIP_LIST_FILE: &str = "clips.json";
+
+/// Read a folder's clips.json, e.g. `{ "001_intro.mp4": { "in_secs": 2.5 } }`;
+/// empty when there is none
+pub fn read_clip_list(folder: &Path) -> HashMap<String, ClipDefaults> {
+    let path = folder.join(CLIP_LIST_FILE);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        eprintln!("Ignoring clip list {:?}: {}", path, e);
+        HashMap::new()
+    })
+}
+
+/// Folder holding a bank's clips: bank 0 is the media folder itself and
+/// banks 1-255 are its subfolders numbered like clips, e.g. "002_act2".
+/// None if there is no subfolder for the bank.
+pub fn bank_folder(folder: &Path, bank: u8) -> Option<std::path::PathBuf> {
+    if bank == 0 {
+        return Some(folder.to_path_buf());
     }
+    std::fs::read_dir(folder).ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_dir()
+                && path.file_name().and_then(|n| n.to_str()).and_then(parse_dmx_prefix) == Some(bank)
+        })
+}
+
+/// Images numbered consecutively from `first` up to the first gap or
+/// non-image, e.g. 040, 041, 042 when 043 is missing.
+pub fn consecutive_images(folder: &Path, first: u16, clip_16bit: bool) -> Vec<MediaFile> {
+    let mut media_map = match scan_clips(folder, clip_16bit) {
+        Ok(map) => map,
+        Err(_) => return Vec::new(),
+    };
+    // The highest value is reserved for closing the output
+    let last = if clip_16bit { u16::MAX - 1 } else { u8::MAX as u16 - 1 };
     
-    let chars: Vec<char> = filename.chars().collect();
+    let mut images = Vec::new();
+    let mut value = first;
+    while let Some(media) = media_map.remove(&value) {
+        if media.media_type != MediaType::Image {
+            break;
+        }
+        images.push(media);
+        if value >= last {
+            break;
+        }
+        value += 1;
+    }
+    images
+}
+
+/// Parse the DMX value prefix of a filename: "042_clip.mp4" → 42.
+/// Returns None if the name doesn't start with three digits and an underscore
+/// or the value is outside 1-255.
+pub fn parse_dmx_prefix(filename: &str) -> Option<u8> {
+    let value = raw_dmx_prefix(filename)?;
     
-    // First 3 must be digits
-    if !chars[0].is_ascii_digit() || !chars[1].is_ascii_digit() || !chars[2].is_ascii_digit() {
+    // Must be 1-255
+    if value == 0 || value > 255 {
         return None;
     }
     
-    // Fourth must be underscore
-    if chars[3] != '_' {
+    Some(value as u8)
+}
+
+/// The three-digit prefix of "###_name.ext" as written (0-999)
+fn raw_dmx_prefix(filename: &str) -> Option<u16> {
+    digit_prefix(filename, 3).map(|value| value as u16)
+}
+
+/// Parse the five-digit clip prefix used with 16-bit clip selection:
+/// "00261_clip.mp4" → 261. None outside 1-65534; 65535 closes the output.
+pub fn parse_clip_prefix_16bit(filename: &str) -> Option<u16> {
+    digit_prefix(filename, 5)
+        .filter(|value| (1..65535).contains(value))
+        .map(|value| value as u16)
+}
+
+/// `digits` digits followed by an underscore and at least one more character
+fn digit_prefix(filename: &str, digits: usize) -> Option<u32> {
+    let bytes = filename.as_bytes();
+    if bytes.len() < digits + 2 || bytes[digits] != b'_' {
         return None;
     }
-    
-    // Parse DMX value
-    let dmx_str: String = chars[0..3].iter().collect();
-    let dmx_value = dmx_str.parse::<u8>().ok()?;
-    
-    // Must be 1-255
-    if dmx_value == 0 {
+    if !bytes[..digits].iter().all(u8::is_ascii_digit) {
         return None;
     }
+    filename[..digits].parse().ok()
+}
+
+/// What a monitor does with a file extension (lowercase)
+fn monitor_media_type(ext: &str) -> Option<MediaType> {
+    match ext {
+        "mp4" => Some(MediaType::Video),
+        "jpg" | "jpeg" | "png" => Some(MediaType::Image),
+        "url" => Some(MediaType::Web),
+        "capture" => Some(MediaType::Capture),
+        "stream" => Some(MediaType::Stream),
+        _ => plugins::media_source_for(ext).map(|_| MediaType::Plugin),
+    }
+}
+
+fn is_audio_extension(ext: &str) -> bool {
+    matches!(ext, "wav" | "mp3" | "ogg" | "flac")
+}
+
+/// Parse filename: 001_clipname.mp4
+fn parse_media_filename(filename: &str, full_path: std::path::PathBuf) -> Option<MediaFile> {
+    let dmx_value = parse_dmx_prefix(filename)?;
     
     // Get extension
     let ext = full_path.extension()?.to_str()?.to_lowercase();
+    let media_type = monitor_media_type(&ext)?;
     
-    let media_type = match ext.as_str() {
-        "mp4" => MediaType::Video,
-        "jpg" | "jpeg" | "png" => MediaType::Image,
-        _ => return None,
-    };
+    Some(MediaFile {
+        dmx_value: dmx_value as u16,
+        filename: filename.to_string(),
+        path: full_path,
+        media_type,
+    })
+}
+
+/// Parse filename: 00261_clipname.mp4
+fn parse_media_filename_16bit(filename: &str, full_path: std::path::PathBuf) -> Option<MediaFile> {
+    let dmx_value = parse_clip_prefix_16bit(filename)?;
+    
+    let ext = full_path.extension()?.to_str()?.to_lowercase();
+    let media_type = monitor_media_type(&ext)?;
     
     Some(MediaFile {
         dmx_value,
@@ -70,3 +205,336 @@ fn parse_media_filename(filename: &str, full_path: std::path::PathBuf) -> Option
         media_type,
     })
 }
+
+/// List every numbered file in a folder for the UI, including ones that
+/// won't play, with the reason in `warnings`. Video durations are read with
+/// ffprobe when it is available.
+pub fn list_media(folder: &Path, ffprobe: Option<&str>) -> Result<Vec<MediaEntry>, std::io::Error> {
+    let mut entries = Vec::new();
+    
+    if !folder.exists() || !folder.is_dir() {
+        return Ok(entries);
+    }
+    
+    for entry in std::fs::read_dir(folder)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(filename) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        // Five-digit prefixes are for monitors with 16-bit clip selection
+        let (dmx_value, wide) = match (raw_dmx_prefix(filename), digit_prefix(filename, 5)) {
+            (Some(value), _) => (value, false),
+            (None, Some(value)) if value <= 65535 => (value as u16, true),
+            _ => continue,
+        };
+        
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let ext = path.extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        // Subtitle and clip-default sidecars belong to the clip with the same name
+        if matches!(ext.as_str(), "srt" | "vtt" | "json") {
+            continue;
+        }
+        let media_type = monitor_media_type(&ext)
+            .or_else(|| is_audio_extension(&ext).then_some(MediaType::Audio));
+        
+        let mut warnings = Vec::new();
+        match media_type {
+            None => warnings.push(format!("Unsupported file type \".{}\"", ext)),
+            Some(MediaType::Audio) => warnings.push("Audio files only play on the audio fixture".to_string()),
+            Some(_) => {}
+        }
+        match (dmx_value, wide) {
+            (0, false) => warnings.push("DMX value 000 means no media; this file is never shown".to_string()),
+            (255, false) => warnings.push("DMX value 255 closes the output; this file is never shown".to_string()),
+            (256..=999, false) => warnings.push("DMX values only go up to 255; this file is never shown".to_string()),
+            (0, true) => warnings.push("Clip 00000 means no media; this file is never shown".to_string()),
+            (65535, true) => warnings.push("Clip 65535 closes the output; this file is never shown".to_string()),
+            (_, true) => warnings.push("Five-digit clips are only shown by monitors with 16-bit clip selection".to_string()),
+            _ => {}
+        }
+        if size == 0 {
+            warnings.push("File is empty".to_string());
+        }
+        
+        let duration = match (media_type.as_ref(), ffprobe) {
+            (Some(MediaType::Video), Some(ffprobe)) => probe_duration(ffprobe, &path),
+            _ => None,
+        };
+        if let Some(trim) = clip_defaults::for_path(&path).trim() {
+            match (trim.out_secs, duration) {
+                (Some(out), _) if out <= trim.in_secs => warnings.push(format!(
+                    "Out point {:.2}s is not after the in point {:.2}s; the clip plays to its end", out, trim.in_secs)),
+                (_, Some(length)) if trim.in_secs >= length => warnings.push(format!(
+                    "In point {:.2}s is past the end of the clip ({:.2}s)", trim.in_secs, length)),
+                _ => {}
+            }
+        }
+        
+        entries.push(MediaEntry {
+            dmx_value,
+            name: filename.to_string(),
+            path,
+            media_type,
+            size,
+            duration,
+            warnings,
+        });
+    }
+    
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    
+    // Only one file per value is ever played; 042_ and 00042_ don't clash
+    let key = |entry: &MediaEntry| (entry.dmx_value, raw_dmx_prefix(&entry.name).is_none());
+    let mut by_value: HashMap<(u16, bool), Vec<String>> = HashMap::new();
+    for entry in &entries {
+        by_value.entry(key(entry)).or_default().push(entry.name.clone());
+    }
+    for entry in &mut entries {
+        let others: Vec<&str> = by_value[&key(entry)].iter()
+            .map(String::as_str)
+            .filter(|name| *name != entry.name)
+            .collect();
+        if !others.is_empty() {
+            entry.warnings.push(format!("DMX value {:03} is also used by {}", entry.dmx_value, others.join(", ")));
+        }
+    }
+    
+    Ok(entries)
+}
+
+/// Length of a video in seconds
+fn probe_duration(ffprobe: &str, path: &Path) -> Option<f64> {
+    let output = std::process::Command::new(ffprobe)
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "csv=p=0"])
+        .arg(path)
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout).trim().parse::<f64>().ok()
+}
+
+/// Parse filename: 001_soundname.wav
+fn parse_audio_filename(filename: &str, full_path: std::path::PathBuf) -> Option<MediaFile> {
+    let dmx_value = parse_dmx_prefix(filename)?;
+    
+    let ext = full_path.extension()?.to_str()?.to_lowercase();
+    if !is_audio_extension(&ext) {
+        return None;
+    }
+    
+    Some(MediaFile {
+        dmx_value: dmx_value as u16,
+        filename: filename.to_string(),
+        path: full_path,
+        media_type: MediaType::Audio,
+    })
+}
+
+/// Build the asset-protocol URL for a media file, matching what the
+/// frontend's convertFileSrc() produces so output windows can load it.
+pub fn asset_url(path: &Path) -> String {
+    let mut encoded = String::new();
+    for byte in path.to_string_lossy().as_bytes() {
+        let c = *byte as char;
+        // Same unreserved set as JavaScript's encodeURIComponent
+        if c.is_ascii_alphanumeric() || "-_.!~*'()".contains(c) {
+            encoded.push(c);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    
+    if cfg!(windows) {
+        format!("http://asset.localhost/{}", encoded)
+    } else {
+        format!("asset://localhost/{}", encoded)
+    }
+}
+
+/// Recover the DMX value of the clip an asset URL points at, e.g.
+/// "http://asset.localhost/C%3A%5CShow%5C042_intro.mp4" → 42, or
+/// ".../00261_intro.mp4" → 261 with 16-bit clip selection.
+pub fn clip_value_from_url(url: &str, clip_16bit: bool) -> Option<u16> {
+    let decoded = percent_decode(url);
+    let filename = decoded.rsplit(['/', '\\']).next()?;
+    if clip_16bit {
+        parse_clip_prefix_16bit(filename)
+    } else {
+        parse_dmx_prefix(filename).map(u16::from)
+    }
+}
+
+/// Recover the file path behind an asset URL made by asset_url()
+pub fn path_from_asset_url(url: &str) -> Option<std::path::PathBuf> {
+    let encoded = url.strip_prefix("http://asset.localhost/")
+        .or_else(|| url.strip_prefix("asset://localhost/"))?;
+    Some(std::path::PathBuf::from(percent_decode(encoded)))
+}
+
+/// Target of a web clip (".url" file): either a Windows internet shortcut
+/// ("[InternetShortcut]" with a "URL=" line) or just the URL on its own line.
+pub fn read_url_file(path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let url = contents.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("URL=").or_else(|| {
+            (line.starts_with("http://") || line.starts_with("https://")).then_some(line)
+        }))?;
+    Some(url.trim().to_string())
+}
+
+/// Live content a clip entry can point at instead of a media file
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum LiveSource {
+    /// ".url" file: web page shown in the output
+    Web { url: String },
+    /// ".capture" file: camera or capture card, by device name
+    Capture { device: String },
+    /// ".stream" file: RTSP / NDI source, shown through the local relay
+    Stream { url: String },
+}
+
+/// Live source for a media URL, if it points at a ".url", ".capture", ".stream"
+/// or plugin clip
+pub fn live_source(media_url: &str) -> Option<LiveSource> {
+    let path = path_from_asset_url(media_url)?;
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    match ext.as_str() {
+        "url" => Some(LiveSource::Web { url: read_url_file(&path)? }),
+        "capture" => Some(LiveSource::Capture { device: read_capture_file(&path)? }),
+        "stream" => {
+            let source = read_capture_file(&path)?;
+            Some(LiveSource::Stream { url: stream_input::relay_url(&source)? })
+        }
+        _ => plugins::media_source_for(&ext)?.live_source(&path),
+    }
+}
+
+/// First non-empty line of a ".capture" (device name) or ".stream"
+/// (source URL, e.g. "rtsp://10.0.0.5/live" or "ndi://CAM-PC (Cam 1)") file
+pub fn read_capture_file(path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    contents.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Decode %XX escapes, as made by asset_url() and encodeURIComponent()
+pub fn percent_decode(url: &str) -> String {
+    let bytes = url.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Files holding a byte each in a fresh temporary folder
+    fn media_folder(name: &str, files: &[&str]) -> PathBuf {
+        let folder = std::env::temp_dir().join(format!("stageplayer-scanner-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&folder);
+        std::fs::create_dir_all(&folder).unwrap();
+        for file in files {
+            std::fs::write(folder.join(file), b"x").unwrap();
+        }
+        folder
+    }
+
+    #[test]
+    fn clip_prefixes() {
+        let cases = [
+            ("042_intro.mp4", Some(42), None),
+            ("255_close.mp4", Some(255), None),
+            ("000_none.mp4", None, None),
+            ("256_high.mp4", None, None),
+            ("00261_big.mp4", None, Some(261)),
+            ("00001_first.mp4", None, Some(1)),
+            ("65534_last.mp4", None, Some(65534)),
+            ("65535_close.mp4", None, None),
+            ("00000_none.mp4", None, None),
+            ("99999_high.mp4", None, None),
+            ("0042_four.mp4", None, None),
+            ("042intro.mp4", None, None),
+            ("042_", None, None),
+            ("00261_", None, None),
+        ];
+        for (filename, three, five) in cases {
+            assert_eq!(parse_dmx_prefix(filename), three, "{}", filename);
+            assert_eq!(parse_clip_prefix_16bit(filename), five, "{}", filename);
+        }
+    }
+
+    #[test]
+    fn clip_values_from_urls() {
+        let three = asset_url(Path::new("/show/042_intro.mp4"));
+        let five = asset_url(Path::new("/show/00261_big.mp4"));
+        assert_eq!(clip_value_from_url(&three, false), Some(42));
+        assert_eq!(clip_value_from_url(&three, true), None);
+        assert_eq!(clip_value_from_url(&five, false), None);
+        assert_eq!(clip_value_from_url(&five, true), Some(261));
+    }
+
+    #[test]
+    fn scans_by_prefix_width() {
+        let folder = media_folder("width", &["001_short.mp4", "00002_long.mp4", "readme.txt"]);
+
+        let clips = scan_clips(&folder, false).unwrap();
+        assert_eq!(clips.keys().copied().collect::<Vec<_>>(), vec![1]);
+        let clips = scan_clips(&folder, true).unwrap();
+        assert_eq!(clips.keys().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn listing_warns_about_unplayable_files() {
+        let folder = media_folder("list", &[
+            "001_a.mp4",
+            "001_b.jpg",
+            "00001_c.mp4",
+            "002_fine.mp4",
+            "255_close.mp4",
+            "256_high.mp4",
+            "65535_close.mp4",
+            "003_notes.xyz",
+        ]);
+        let entries = list_media(&folder, None).unwrap();
+        let warnings = |name: &str| entries.iter().find(|e| e.name == name).unwrap().warnings.join("; ");
+
+        assert!(warnings("001_a.mp4").contains("also used by 001_b.jpg"));
+        assert!(warnings("001_b.jpg").contains("also used by 001_a.mp4"));
+        // A five-digit prefix doesn't clash with the same three-digit value
+        assert!(!warnings("00001_c.mp4").contains("also used by"));
+        assert!(warnings("00001_c.mp4").contains("16-bit clip selection"));
+        assert_eq!(warnings("002_fine.mp4"), "");
+        assert!(warnings("255_close.mp4").contains("closes the output"));
+        assert!(warnings("256_high.mp4").contains("only go up to 255"));
+        assert!(warnings("65535_close.mp4").contains("closes the output"));
+        assert!(warnings("003_notes.xyz").contains("Unsupported file type"));
+
+        let values: Vec<u16> = entries.iter().map(|e| e.dmx_value).collect();
+        assert!(values.contains(&256) && values.contains(&65535));
+    }
+}
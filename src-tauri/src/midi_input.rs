@@ -0,0 +1,236 @@
+use crate::config::{AppConfig, CueLook, MidiInputConfig, MidiMapping};
+use crate::macros;
+use crate::sacn_listener::DmxFrame;
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// How often a connected device is checked for having been unplugged
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// One monitor change asked for by a MIDI message
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MidiChange {
+    Clip(u16),
+    Dimmer(u8),
+    Playtype(u8),
+}
+
+/// Spawn the MIDI input.
+///
+/// While enabled, notes and CCs on each monitor's MIDI channel change its
+/// clip, dimmer or playtype. Like OSC they set the playback state
+/// alongside the sACN listener; the console takes over again when it changes
+/// that monitor's channels. The device is reconnected when it is changed in
+/// the config or plugged back in.
+pub fn spawn_midi_input(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        // midir calls back on its own thread; messages are handled here
+        let (tx, rx) = mpsc::channel::<Vec<u8>>();
+        let mut connection: Option<(String, String, MidiInputConnection<()>)> = None;
+        let mut last_check = Instant::now();
+        let mut looks: HashMap<String, CueLook> = HashMap::new();
+
+        loop {
+            let midi = config.lock().unwrap().midi_input.clone();
+            if !midi.enabled {
+                if let Some((_, name, _)) = connection.take() {
+                    println!("MIDI input disconnected: {}", name);
+                }
+                looks.clear();
+                std::thread::sleep(Duration::from_millis(250));
+                continue;
+            }
+
+            // Drop the connection when the device setting changes or the
+            // device has gone
+            if let Some((device, name, _)) = connection.as_ref() {
+                let mut unplugged = false;
+                if last_check.elapsed() >= DEVICE_CHECK_INTERVAL {
+                    last_check = Instant::now();
+                    unplugged = !input_names().contains(name);
+                }
+                if *device != midi.device || unplugged {
+                    println!("MIDI input disconnected: {}", name);
+                    connection = None;
+                }
+            }
+            if connection.is_none() {
+                // Keep SysEx for MIDI Show Control
+                match connect(&midi.device, Ignore::TimeAndActiveSense, tx.clone()) {
+                    Ok((name, conn)) => {
+                        // Anything queued belonged to the old device
+                        while rx.try_recv().is_ok() {}
+                        connection = Some((midi.device.clone(), name, conn));
+                        last_check = Instant::now();
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::thread::sleep(Duration::from_secs(2));
+                        continue;
+                    }
+                }
+            }
+
+            let Ok(message) = rx.recv_timeout(Duration::from_millis(250)) else {
+                continue;
+            };
+            for (monitor_id, change) in match_message(&midi, &message) {
+                let result = macros::change_look(&app_handle, &config, &dmx_frame, &mut looks, monitor_id, |look| match change {
+                    MidiChange::Clip(clip) => look.clip = clip,
+                    MidiChange::Dimmer(dimmer) => look.dimmer = dimmer,
+                    MidiChange::Playtype(playtype) => look.playtype = playtype,
+                });
+                if let Err(e) = result {
+                    eprintln!("MIDI: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Connect to the first input whose name contains `device`, returning its
+/// full name with the connection. Messages of the `ignore` kinds are dropped.
+pub fn connect(device: &str, ignore: Ignore, tx: Sender<Vec<u8>>) -> Result<(String, MidiInputConnection<()>), String> {
+    let mut input = MidiInput::new("StagePlayer DMX")
+        .map_err(|e| format!("Failed to open MIDI: {}", e))?;
+    input.ignore(ignore);
+
+    let wanted = device.to_lowercase();
+    let port = input.ports().into_iter()
+        .find(|port| input.port_name(port).is_ok_and(|name| name.to_lowercase().contains(&wanted)))
+        .ok_or_else(|| format!("No MIDI input matching '{}' (found: {})", device, input_names().join(", ")))?;
+    let name = input.port_name(&port).unwrap_or_default();
+
+    let connection = input
+        .connect(&port, "stageplayer-midi-in", move |_, message, _| {
+            let _ = tx.send(message.to_vec());
+        }, ())
+        .map_err(|e| format!("Failed to connect to MIDI input '{}': {}", name, e))?;
+    println!("MIDI input connected: {}", name);
+    Ok((name, connection))
+}
+
+fn input_names() -> Vec<String> {
+    let Ok(input) = MidiInput::new("StagePlayer DMX") else {
+        return Vec::new();
+    };
+    input.ports().iter().filter_map(|port| input.port_name(port).ok()).collect()
+}
+
+/// Changes a message asks of each monitor: channel voice messages go to the
+/// monitors listening on that channel (both may share one), MIDI Show
+/// Control to the monitors following its cue list
+fn match_message(midi: &MidiInputConfig, message: &[u8]) -> Vec<(&'static str, MidiChange)> {
+    if message.first() == Some(&0xf0) {
+        return match parse_msc(midi.msc_device_id, message) {
+            Some(msc) => match_msc(midi, &msc),
+            None => Vec::new(),
+        };
+    }
+    let [status, data1, data2] = match message {
+        [status, data1, data2, ..] => [*status, *data1, *data2],
+        _ => return Vec::new(),
+    };
+    let channel = (status & 0x0f) + 1;
+    [("monitor1", &midi.monitor1), ("monitor2", &midi.monitor2)]
+        .into_iter()
+        .filter(|(_, mapping)| mapping.channel == channel)
+        .filter_map(|(monitor_id, mapping)| Some((monitor_id, match_change(mapping, status & 0xf0, data1, data2)?)))
+        .collect()
+}
+
+fn match_change(mapping: &MidiMapping, kind: u8, data1: u8, data2: u8) -> Option<MidiChange> {
+    match kind {
+        // Note-on with velocity 0 is a note-off; releasing a key keeps the clip
+        0x90 if data2 > 0 => Some(MidiChange::Clip(data1 as u16 + mapping.clip_offset as u16)),
+        0xb0 if mapping.dimmer_cc == Some(data1) => Some(MidiChange::Dimmer(scale(data2))),
+        0xb0 if mapping.playtype_cc == Some(data1) => Some(MidiChange::Playtype(scale(data2))),
+        _ => None,
+    }
+}
+
+/// 0-127 to 0-255, so a fader at the top is full
+fn scale(value: u8) -> u8 {
+    (value.min(127) as u16 * 255 / 127) as u8
+}
+
+/// MIDI Show Control commands the player acts on
+#[derive(Debug, Clone, PartialEq)]
+enum MscCommand {
+    Go,
+    Stop,
+    Resume,
+}
+
+/// A MIDI Show Control message with its optional cue number and cue list
+#[derive(Debug, Clone, PartialEq)]
+struct Msc {
+    command: MscCommand,
+    cue: Option<String>,
+    list: Option<String>,
+}
+
+/// Parse "F0 7F <device> 02 <format> <command> <cue> 00 <list> 00 <path> F7".
+/// Any command format (lighting, video, all types) is accepted.
+fn parse_msc(device_id: u8, message: &[u8]) -> Option<Msc> {
+    let [0xf0, 0x7f, device, 0x02, _format, command, data @ ..] = message else {
+        return None;
+    };
+    // 0x7F is the all-call; a device ID of 0x7F in the config answers everything
+    if *device != device_id && *device != 0x7f && device_id != 0x7f {
+        return None;
+    }
+    let command = match command {
+        0x01 => MscCommand::Go,
+        0x02 => MscCommand::Stop,
+        0x03 => MscCommand::Resume,
+        _ => return None,
+    };
+
+    let data = data.strip_suffix(&[0xf7]).unwrap_or(data);
+    let mut fields = data.split(|&b| b == 0x00)
+        .map(|field| String::from_utf8_lossy(field).trim().to_string())
+        .map(|field| (!field.is_empty()).then_some(field));
+    Some(Msc {
+        command,
+        cue: fields.next().flatten(),
+        list: fields.next().flatten(),
+    })
+}
+
+/// A message without a cue list goes to every monitor following MSC
+fn match_msc(midi: &MidiInputConfig, msc: &Msc) -> Vec<(&'static str, MidiChange)> {
+    let list = msc.list.as_deref().map(|list| list.parse::<f32>().ok().map(|list| list as u16));
+    [("monitor1", &midi.monitor1), ("monitor2", &midi.monitor2)]
+        .into_iter()
+        .filter(|(_, mapping)| match (mapping.msc_cue_list, list) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(own), Some(list)) => list == Some(own),
+        })
+        .filter_map(|(monitor_id, mapping)| {
+            let change = match msc.command {
+                MscCommand::Go => {
+                    // Cue 12 and cue 12.5 both select clip 12
+                    let clip = msc.cue.as_deref()?.parse::<f32>().ok()?;
+                    if !(1.0..65536.0).contains(&clip) {
+                        println!("MSC GO {} on {}: no clip for that cue", msc.cue.as_deref().unwrap_or_default(), monitor_id);
+                        return None;
+                    }
+                    MidiChange::Clip(clip as u16)
+                }
+                MscCommand::Stop => MidiChange::Playtype(mapping.msc_stop_playtype),
+                MscCommand::Resume => MidiChange::Playtype(mapping.msc_resume_playtype),
+            };
+            Some((monitor_id, change))
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+use crate::config::{AppConfig, CueLook, MqttConfig};
+use crate::http_api;
+use crate::macros;
+use crate::playback::PlaybackTracker;
+use crate::sacn_listener::DmxFrame;
+use rumqttc::{Client, Connection, Event, LastWill, MqttOptions, Packet, QoS};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// How long to wait for broker traffic before checking status and config
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Unchanged status is republished this often
+const REPUBLISH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Spawn the MQTT client.
+///
+/// While enabled it publishes the listener and playback state (as served on
+/// the HTTP API's /status) retained on the status topic whenever it
+/// changes, with "<status topic>/online" as a last will, and applies
+/// commands published below the command topic. Like macros, commands set
+/// the monitor's playback state until the console changes that monitor's
+/// channels. The connection is rebuilt when the settings change.
+pub fn spawn_mqtt(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+    playback: Arc<Mutex<PlaybackTracker>>,
+) {
+    std::thread::spawn(move || {
+        let mut session: Option<(MqttConfig, Client, Connection)> = None;
+        let mut last_published: Option<(String, Instant)> = None;
+        let mut looks: HashMap<String, CueLook> = HashMap::new();
+
+        loop {
+            let mqtt = config.lock().unwrap().mqtt.clone();
+            if session.as_ref().is_some_and(|(current, _, _)| *current != mqtt || !mqtt.enabled) {
+                // Dropped without a DISCONNECT, so the broker publishes the
+                // last will and the player shows as offline
+                if let Some((current, _, _)) = session.take() {
+                    println!("MQTT disconnected from {}:{}", current.host, current.port);
+                }
+            }
+            if !mqtt.enabled {
+                looks.clear();
+                std::thread::sleep(Duration::from_millis(250));
+                continue;
+            }
+
+            let (_, client, connection) = session.get_or_insert_with(|| {
+                last_published = None;
+                let (client, connection) = connect(&mqtt);
+                (mqtt.clone(), client, connection)
+            });
+
+            // Polling the connection also sends what was queued and
+            // reconnects after errors
+            match connection.recv_timeout(POLL_INTERVAL) {
+                Ok(Ok(Event::Incoming(Packet::ConnAck(_)))) => {
+                    println!("MQTT connected to {}:{}", mqtt.host, mqtt.port);
+                    // Subscriptions and the online flag don't survive a reconnect
+                    let _ = client.try_subscribe(format!("{}/#", mqtt.command_topic), QoS::AtLeastOnce);
+                    let _ = client.try_publish(online_topic(&mqtt), QoS::AtLeastOnce, true, "true");
+                    last_published = None;
+                }
+                Ok(Ok(Event::Incoming(Packet::Publish(publish)))) => {
+                    let payload = String::from_utf8_lossy(&publish.payload);
+                    if let Err(e) = apply(&app_handle, &config, &dmx_frame, &mut looks, &mqtt, &publish.topic, payload.trim()) {
+                        eprintln!("MQTT {}: {}", publish.topic, e);
+                    }
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => {
+                    eprintln!("MQTT connection to {}:{} failed: {}", mqtt.host, mqtt.port, e);
+                    std::thread::sleep(Duration::from_secs(2));
+                    continue;
+                }
+                Err(_) => {}
+            }
+
+            let json = serde_json::to_string(&http_api::status(&dmx_frame, &playback)).unwrap_or_default();
+            let due = match &last_published {
+                Some((published, at)) => *published != json || at.elapsed() >= REPUBLISH_INTERVAL,
+                None => true,
+            };
+            if due && client.try_publish(&mqtt.status_topic, QoS::AtMostOnce, true, json.clone()).is_ok() {
+                last_published = Some((json, Instant::now()));
+            }
+        }
+    });
+}
+
+fn connect(mqtt: &MqttConfig) -> (Client, Connection) {
+    let mut options = MqttOptions::new(&mqtt.client_id, &mqtt.host, mqtt.port);
+    options.set_keep_alive(Duration::from_secs(10));
+    if !mqtt.username.is_empty() {
+        options.set_credentials(&mqtt.username, &mqtt.password);
+    }
+    options.set_last_will(LastWill::new(online_topic(mqtt), "false", QoS::AtLeastOnce, true));
+    Client::new(options, 32)
+}
+
+fn online_topic(mqtt: &MqttConfig) -> String {
+    format!("{}/online", mqtt.status_topic)
+}
+
+/// Apply "<command topic>/monitor/<1|2>/<clip|dimmer|playtype>" with a
+/// 0-255 payload, or "<command topic>/blackout"
+fn apply(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    looks: &mut HashMap<String, CueLook>,
+    mqtt: &MqttConfig,
+    topic: &str,
+    payload: &str,
+) -> Result<(), String> {
+    let Some(command) = topic.strip_prefix(mqtt.command_topic.as_str()).and_then(|rest| rest.strip_prefix('/')) else {
+        return Ok(());
+    };
+    let segments: Vec<&str> = command.split('/').collect();
+    match segments.as_slice() {
+        ["blackout"] => {
+            macros::blackout(app_handle, config, dmx_frame, looks);
+            println!("MQTT: blackout");
+            Ok(())
+        }
+        ["monitor", monitor @ ("1" | "2"), field] => {
+            let monitor_id = format!("monitor{}", monitor);
+            // Clips go up to 65535 for 16-bit clip selection
+            let value = payload.parse::<u16>()
+                .map_err(|_| format!("'{}' is not a value from 0-65535", payload))?;
+            let level = || u8::try_from(value).map_err(|_| format!("{} {} must be 0-255", monitor_id, field));
+            match *field {
+                "clip" => macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.clip = value)?,
+                "dimmer" => {
+                    let level = level()?;
+                    macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.dimmer = level)?
+                }
+                "playtype" => {
+                    let level = level()?;
+                    macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id, |look| look.playtype = level)?
+                }
+                _ => return Err("expected clip, dimmer or playtype".to_string()),
+            }
+            println!("MQTT: {} {} = {}", monitor_id, field, value);
+            Ok(())
+        }
+        _ => Err("unknown command".to_string()),
+    }
+}
@@ -0,0 +1,216 @@
+use crate::config::{AppConfig, CueLook, OscInputConfig};
+use crate::macros;
+use crate::sacn_listener::DmxFrame;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// What an incoming OSC message asks for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OscCommand {
+    Clip { monitor: u8, value: u16 },
+    Dimmer { monitor: u8, value: u8 },
+    Playtype { monitor: u8, value: u8 },
+    Blackout,
+}
+
+/// Spawn the OSC server.
+///
+/// While enabled, messages on the configured addresses change a monitor's
+/// clip, dimmer or playtype the way its DMX channels would. Like macros they
+/// set the monitor's playback state, and the console takes over again when
+/// it changes that monitor's channels. The socket is reopened when the port
+/// changes.
+pub fn spawn_osc_input(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        let mut socket: Option<(u16, UdpSocket)> = None;
+        let mut looks: HashMap<String, CueLook> = HashMap::new();
+        let mut buf = [0u8; 1536];
+
+        loop {
+            let osc = config.lock().unwrap().osc_input.clone();
+            if !osc.enabled {
+                if socket.take().is_some() {
+                    println!("OSC server stopped");
+                }
+                looks.clear();
+                std::thread::sleep(Duration::from_millis(250));
+                continue;
+            }
+
+            if socket.as_ref().map(|(port, _)| *port) != Some(osc.port) {
+                socket = match bind(osc.port) {
+                    Ok(bound) => {
+                        println!("OSC server listening on UDP port {}", osc.port);
+                        Some((osc.port, bound))
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to open OSC port {}: {}", osc.port, e);
+                        std::thread::sleep(Duration::from_secs(2));
+                        continue;
+                    }
+                };
+            }
+            let Some((_, bound)) = socket.as_ref() else {
+                continue;
+            };
+
+            // Times out every 100 ms so config changes are noticed
+            let len = match bound.recv(&mut buf) {
+                Ok(len) => len,
+                Err(e) => {
+                    if e.kind() != std::io::ErrorKind::WouldBlock &&
+                       e.kind() != std::io::ErrorKind::TimedOut {
+                        eprintln!("OSC IO error: {}", e);
+                    }
+                    continue;
+                }
+            };
+
+            let mut messages = Vec::new();
+            parse_packet(&buf[..len], &mut messages);
+            for (address, arg) in messages {
+                let Some(command) = match_command(&osc, &address, arg) else {
+                    continue;
+                };
+                if let Err(e) = apply(&app_handle, &config, &dmx_frame, &mut looks, command) {
+                    eprintln!("OSC {}: {}", address, e);
+                }
+            }
+        }
+    });
+}
+
+fn bind(port: u16) -> std::io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+    socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+    Ok(socket)
+}
+
+fn apply(
+    app_handle: &AppHandle,
+    config: &Mutex<AppConfig>,
+    dmx_frame: &Mutex<DmxFrame>,
+    looks: &mut HashMap<String, CueLook>,
+    command: OscCommand,
+) -> Result<(), String> {
+    match command {
+        OscCommand::Clip { monitor, value } => {
+            macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id(monitor), |look| look.clip = value)
+        }
+        OscCommand::Dimmer { monitor, value } => {
+            macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id(monitor), |look| look.dimmer = value)
+        }
+        OscCommand::Playtype { monitor, value } => {
+            macros::change_look(app_handle, config, dmx_frame, looks, &monitor_id(monitor), |look| look.playtype = value)
+        }
+        OscCommand::Blackout => {
+            macros::blackout(app_handle, config, dmx_frame, looks);
+            Ok(())
+        }
+    }
+}
+
+fn monitor_id(monitor: u8) -> String {
+    format!("monitor{}", monitor)
+}
+
+/// Map an address and its first argument to a command. Values are clamped
+/// to 0-255 (clips to 0-65535); floats from faders are taken as-is, not scaled.
+fn match_command(osc: &OscInputConfig, address: &str, arg: Option<f32>) -> Option<OscCommand> {
+    if address == osc.blackout_address {
+        return match arg {
+            Some(v) if v == 0.0 => None, // button release
+            _ => Some(OscCommand::Blackout),
+        };
+    }
+    let arg = arg?.round();
+    if let Some(monitor) = match_monitor(&osc.clip_address, address) {
+        return Some(OscCommand::Clip { monitor, value: arg.clamp(0.0, 65535.0) as u16 });
+    }
+    let value = arg.clamp(0.0, 255.0) as u8;
+    if let Some(monitor) = match_monitor(&osc.dimmer_address, address) {
+        return Some(OscCommand::Dimmer { monitor, value });
+    }
+    if let Some(monitor) = match_monitor(&osc.playtype_address, address) {
+        return Some(OscCommand::Playtype { monitor, value });
+    }
+    None
+}
+
+/// Monitor number from an address matching a pattern like "/clip/{monitor}"
+fn match_monitor(pattern: &str, address: &str) -> Option<u8> {
+    let (prefix, suffix) = pattern.split_once("{monitor}")?;
+    let number = address.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    match number.parse::<u8>().ok()? {
+        monitor @ 1..=2 => Some(monitor),
+        _ => None,
+    }
+}
+
+/// Collect (address, first numeric argument) from a message or bundle
+fn parse_packet(packet: &[u8], messages: &mut Vec<(String, Option<f32>)>) {
+    if packet.starts_with(b"#bundle\0") {
+        // Skip the time tag; bundled messages are applied immediately
+        let mut pos = 16;
+        while let Some(size) = packet.get(pos..pos + 4) {
+            let size = i32::from_be_bytes([size[0], size[1], size[2], size[3]]).max(0) as usize;
+            let Some(element) = packet.get(pos + 4..pos + 4 + size) else {
+                return;
+            };
+            parse_packet(element, messages);
+            pos += 4 + size;
+        }
+    } else if let Some(message) = parse_message(packet) {
+        messages.push(message);
+    }
+}
+
+fn parse_message(packet: &[u8]) -> Option<(String, Option<f32>)> {
+    let (address, pos) = read_string(packet, 0)?;
+    if !address.starts_with('/') {
+        return None;
+    }
+    // Type tags are optional in old senders
+    let Some((tags, mut pos)) = read_string(packet, pos).filter(|(tags, _)| tags.starts_with(',')) else {
+        return Some((address, None));
+    };
+
+    let mut arg = None;
+    for tag in tags.chars().skip(1) {
+        let word = packet.get(pos..pos + 4).map(|b| [b[0], b[1], b[2], b[3]]);
+        let value = match tag {
+            'i' => Some(i32::from_be_bytes(word?) as f32),
+            'f' => Some(f32::from_be_bytes(word?)),
+            'T' => Some(1.0),
+            'F' => Some(0.0),
+            _ => None,
+        };
+        if value.is_some() {
+            arg = value;
+            break;
+        }
+        // Only fixed-size arguments can be skipped safely
+        match tag {
+            'c' | 'r' | 'm' => pos += 4,
+            'h' | 'd' | 't' => pos += 8,
+            'N' | 'I' => {}
+            _ => break,
+        }
+    }
+    Some((address, arg))
+}
+
+/// OSC strings are null-terminated and padded to a multiple of 4 bytes
+fn read_string(packet: &[u8], pos: usize) -> Option<(String, usize)> {
+    let rest = packet.get(pos..)?;
+    let len = rest.iter().position(|&b| b == 0)?;
+    let value = std::str::from_utf8(&rest[..len]).ok()?.to_string();
+    Some((value, pos + (len / 4 + 1) * 4))
+}
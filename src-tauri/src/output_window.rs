@@ -0,0 +1,669 @@
+use crate::capture;
+use crate::clip_defaults::{self, ClipDefaults};
+use crate::config::{AppConfig, DimmingCurve, DpiSizing, MonitorConfig, PixelMapConfig, PlayType, SpoutConfig};
+use crate::gpu;
+use crate::media_scanner;
+use crate::placement::{self, PlacementStrategy};
+use crate::playback_state::OutputParams;
+use crate::subtitles;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::webview::WebviewWindowBuilder;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+/// What push_media_update last sent each monitor's window, keyed by monitor id
+static SHOWN_MEDIA: Mutex<Vec<(String, ShownMedia)>> = Mutex::new(Vec::new());
+
+/// The clip a window is showing, with the details read from disk for it.
+/// They are read again only when the clip changes.
+#[derive(Clone)]
+struct ShownMedia {
+    media_url: Option<String>,
+    playtype: u8,
+    orientation: String,
+    slideshow: Option<Slideshow>,
+    defaults: ClipDefaults,
+    source_js: String,
+}
+
+fn shown_media(monitor_id: &str) -> Option<ShownMedia> {
+    SHOWN_MEDIA.lock().unwrap().iter()
+        .find(|(id, _)| id == monitor_id)
+        .map(|(_, shown)| shown.clone())
+}
+
+fn set_shown_media(monitor_id: &str, shown: Option<ShownMedia>) {
+    let mut all = SHOWN_MEDIA.lock().unwrap();
+    all.retain(|(id, _)| id != monitor_id);
+    if let Some(shown) = shown {
+        all.push((monitor_id.to_string(), shown));
+    }
+}
+
+/// Defaults of a clip, from the window's cache while it is the clip shown
+pub fn clip_defaults(monitor_id: &str, media_url: &str) -> ClipDefaults {
+    match shown_media(monitor_id) {
+        Some(shown) if shown.media_url.as_deref() == Some(media_url) => shown.defaults,
+        _ => clip_defaults::for_url(media_url),
+    }
+}
+
+/// resolve_slideshow(), reusing the slideshow already shown for the same
+/// clip and playtype instead of scanning the folder again
+pub fn slideshow_for(monitor_id: &str, monitor: &MonitorConfig, media_url: Option<&str>, playtype: u8) -> Option<Slideshow> {
+    match shown_media(monitor_id) {
+        Some(shown) if shown.media_url.as_deref() == media_url && shown.playtype == playtype => shown.slideshow,
+        _ => resolve_slideshow(monitor, media_url, playtype),
+    }
+}
+
+/// Label used for a monitor's output window
+pub fn window_label(monitor_id: &str) -> String {
+    format!("output-{}", monitor_id)
+}
+
+/// Everything needed to (re)open a monitor's output window
+#[derive(Debug, Clone)]
+pub struct OutputWindowSpec {
+    pub monitor_id: String,
+    pub display_index: usize,
+    pub width: u32,
+    pub height: u32,
+    pub window_x: Option<i32>,
+    pub window_y: Option<i32>,
+}
+
+/// Create a borderless, always-on-top output window on the requested display,
+/// replacing any window already open for that monitor.
+pub fn open_window(
+    app_handle: &AppHandle,
+    config: &Arc<Mutex<AppConfig>>,
+    spec: &OutputWindowSpec,
+) -> Result<WebviewWindow, String> {
+    let window_label = window_label(&spec.monitor_id);
+    
+    // Close existing window if it exists
+    if let Some(existing) = app_handle.get_webview_window(&window_label) {
+        let _ = existing.destroy();
+    }
+    // The new window starts out showing nothing
+    set_shown_media(&spec.monitor_id, None);
+    
+    // Get available monitors
+    let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
+    
+    // Debug: print all available monitors
+    println!("Available monitors:");
+    for (i, mon) in monitors.iter().enumerate() {
+        println!("  Monitor {}: position=({}, {}), size={}x{}", 
+            i, mon.position().x, mon.position().y, mon.size().width, mon.size().height);
+    }
+    
+    // Validate display_index is within bounds
+    let actual_display_index = if spec.display_index >= monitors.len() {
+        println!("Display index {} out of bounds (have {} monitors), using primary monitor (0)", spec.display_index, monitors.len());
+        0
+    } else {
+        spec.display_index
+    };
+    
+    // Get the target monitor for positioning
+    let monitor = monitors.get(actual_display_index)
+        .ok_or_else(|| format!("Display index {} not found", actual_display_index))?;
+    
+    let position = monitor.position();
+    let size = monitor.size();
+    let scale_factor = monitor.scale_factor();
+    
+    // Work out the window size in physical pixels for the target display.
+    // Builder sizes are logical, and Windows rescales a window when it moves
+    // to a display with a different DPI, so the physical size is applied
+    // again after the window has been positioned.
+    let (dpi_sizing, gpu_preference) = config.lock().unwrap()
+        .monitor(&spec.monitor_id)
+        .map(|m| (m.dpi_sizing.clone(), m.gpu.clone()))
+        .unwrap_or_default();
+    let physical_size = match dpi_sizing {
+        DpiSizing::Physical => tauri::PhysicalSize::new(spec.width, spec.height),
+        DpiSizing::Logical => tauri::LogicalSize::new(spec.width as f64, spec.height as f64)
+            .to_physical::<u32>(scale_factor),
+    };
+    println!("DPI sizing {:?}: {}x{} at scale {} -> {}x{} physical pixels",
+        dpi_sizing, spec.width, spec.height, scale_factor, physical_size.width, physical_size.height);
+    
+    // Use saved window position if available, otherwise use monitor default position
+    // Note: Frontend clears window_x/window_y when display_index changes, so saved positions
+    // are always for the currently selected monitor
+    let (final_x, final_y) = if let (Some(saved_x), Some(saved_y)) = (spec.window_x, spec.window_y) {
+        println!("Using saved window position: ({}, {})", saved_x, saved_y);
+        (saved_x, saved_y)
+    } else {
+        // Position window at exact top-left of the monitor
+        // Offset by -10 to compensate for Windows positioning quirk
+        let quirk_offset = if cfg!(windows) { 10 } else { 0 };
+        let default_x = position.x - quirk_offset;
+        let default_y = position.y - quirk_offset;
+        println!("No saved position, using default monitor position: ({}, {})", default_x, default_y);
+        (default_x, default_y)
+    };
+    
+    println!("Monitor {} info: position=({}, {}), size={}x{}", 
+        actual_display_index, position.x, position.y, size.width, size.height);
+    println!("Window position: using=({}, {})",
+         final_x, final_y);
+    println!("Opening output window '{}' on display {} at position ({}, {}) with resolution {}x{}", 
+        window_label, actual_display_index, final_x, final_y, spec.width, spec.height);
+    
+    // Tell the output page which rendering path to use
+    let playback_profile = config.lock().unwrap().playback_profile.clone();
+    let profile_script = format!("window.__PLAYBACK_PROFILE__ = '{:?}';", playback_profile);
+    
+    // Build and create the window - borderless, positioned at exact monitor top-left
+    let mut builder = WebviewWindowBuilder::new(
+        app_handle,
+        &window_label,
+        tauri::WebviewUrl::App("output.html".into())
+    )
+    .title(format!("Output Window {}", spec.monitor_id))
+    .inner_size(physical_size.width as f64 / scale_factor, physical_size.height as f64 / scale_factor)
+    .position(final_x as f64 / scale_factor, final_y as f64 / scale_factor)
+    .resizable(false)
+    .decorations(false)
+    .visible(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .initialization_script(profile_script);
+    
+    // Pin rendering to the chosen GPU. WebView2 only accepts different
+    // browser arguments in a separate user data folder, so each output
+    // window with a GPU preference gets its own.
+    if let Some(args) = gpu::browser_args(&gpu_preference) {
+        let data_dir = app_handle.path().app_local_data_dir()
+            .map_err(|e| format!("Failed to resolve app data directory: {}", e))?
+            .join(format!("webview-{}", window_label));
+        println!("Output window '{}' GPU preference {:?}: {}", window_label, gpu_preference, args);
+        builder = builder.additional_browser_args(&args).data_directory(data_dir);
+    }
+    
+    let window = builder.build()
+        .map_err(|e| format!("Failed to build window: {}", e))?;
+    
+    if config.lock().unwrap().output_click_through {
+        if let Err(e) = window.set_ignore_cursor_events(true) {
+            eprintln!("Failed to make output window click-through: {}", e);
+        }
+    }
+    
+    // ".capture" clips need camera access without a prompt
+    if let Err(e) = capture::allow_capture(&window) {
+        eprintln!("{}", e);
+    }
+    
+    println!("Output window '{}' created successfully", window_label);
+    
+    // Kiosk mode: ignore Alt+F4 and other OS close requests.
+    // Programmatic closes use destroy() and are not affected.
+    let kiosk_config = Arc::clone(config);
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+            if kiosk_config.lock().unwrap().kiosk_mode {
+                println!("Kiosk mode: blocked close request for output window");
+                api.prevent_close();
+            }
+        }
+    });
+    
+    let strategy = placement::resolve(&config.lock().unwrap().output_placement);
+    println!("Placing output window '{}' using {:?}", window_label, strategy);
+    
+    match strategy {
+        PlacementStrategy::Coordinates => {
+            // Set exact position again after creation to ensure correctness
+            window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { 
+                x: final_x, 
+                y: final_y 
+            })).map_err(|e| format!("Failed to set position: {}", e))?;
+            window.set_size(tauri::Size::Physical(physical_size))
+                .map_err(|e| format!("Failed to set size: {}", e))?;
+        }
+        PlacementStrategy::FullscreenOnOutput => {
+            // The compositor sizes the window to the output
+            window.set_position(tauri::Position::Physical(*position))
+                .map_err(|e| format!("Failed to set position: {}", e))?;
+            placement::fullscreen_on_output(&window, actual_display_index)?;
+        }
+    }
+    
+    // Lift the window above the menu bar and Dock, and re-assert it whenever
+    // focus changes since Spaces/Mission Control can reorder windows
+    #[cfg(target_os = "macos")]
+    {
+        apply_macos_window_level(&window)?;
+        
+        let reassert = window.clone();
+        window.on_window_event(move |event| {
+            if let tauri::WindowEvent::Focused(_) = event {
+                if let Err(e) = apply_macos_window_level(&reassert) {
+                    eprintln!("{}", e);
+                }
+            }
+        });
+    }
+    
+    // Force window to front
+    window.show().map_err(|e| format!("Failed to show window: {}", e))?;
+    window.set_focus().map_err(|e| format!("Failed to focus window: {}", e))?;
+    
+    println!("Output window '{}' shown and focused", window_label);
+    
+    Ok(window)
+}
+
+/// Images an output window cycles through for the slideshow playtype
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Slideshow {
+    pub urls: Vec<String>,
+    pub interval_ms: u32,
+    /// 0 = hard cut
+    pub crossfade_ms: u32,
+}
+
+/// Build the slideshow for a clip: the selected image and the images numbered
+/// consecutively after it in the same folder (so within the active bank).
+/// None unless the playtype asks for a slideshow and there are at least two
+/// images.
+pub fn resolve_slideshow(monitor: &MonitorConfig, media_url: Option<&str>, playtype: u8) -> Option<Slideshow> {
+    if PlayType::from_dmx(playtype) != PlayType::Slideshow {
+        return None;
+    }
+    let media_url = media_url?;
+    let first = media_scanner::clip_value_from_url(media_url, monitor.clip_16bit)?;
+    let folder = media_scanner::path_from_asset_url(media_url)
+        .and_then(|path| path.parent().map(|parent| parent.to_path_buf()))
+        .unwrap_or_else(|| monitor.media_folder.clone());
+    let images = media_scanner::consecutive_images(&folder, first, monitor.clip_16bit);
+    if images.len() < 2 {
+        return None;
+    }
+    
+    Some(Slideshow {
+        urls: images.iter().map(|media| media_scanner::asset_url(&media.path)).collect(),
+        interval_ms: (monitor.slideshow_interval_secs.max(0.5) * 1000.0) as u32,
+        crossfade_ms: monitor.slideshow_crossfade_ms,
+    })
+}
+
+/// Call updateMedia() inside a monitor's output window. `transition_ms`
+/// from the console's crossfade channel overrides the clip's default.
+/// When only the dimmer differs from the last update, just the dimmer is
+/// pushed. Does nothing if the window is not open.
+#[allow(clippy::too_many_arguments)]
+pub fn push_media_update(
+    app_handle: &AppHandle,
+    monitor_id: &str,
+    media_url: Option<&str>,
+    dimmer: u8,
+    playtype: u8,
+    orientation: &str,
+    slideshow: Option<&Slideshow>,
+    transition_ms: Option<u32>,
+) -> Result<(), String> {
+    let window_label = window_label(monitor_id);
+    
+    if let Some(window) = app_handle.get_webview_window(&window_label) {
+        let shown = shown_media(monitor_id);
+        if let Some(shown) = &shown {
+            if shown.media_url.as_deref() == media_url
+                && shown.playtype == playtype
+                && shown.orientation == orientation
+                && shown.slideshow.as_ref() == slideshow
+            {
+                return push_dimmer(app_handle, monitor_id, dimmer as f32);
+            }
+        }
+        
+        // Use evaluate_script to directly call updateMedia function in the window
+        let media_url_js = match media_url {
+            Some(url) => format!("'{}'", url.replace("'", "\\'")),
+            None => "null".to_string()
+        };
+        let slideshow_js = serde_json::to_string(&slideshow)
+            .map_err(|e| format!("Failed to encode slideshow: {}", e))?;
+        
+        let (defaults, source_js, captions_js) = match shown {
+            Some(shown) if shown.media_url.as_deref() == media_url => (shown.defaults, shown.source_js, None),
+            _ => {
+                // ".url" / ".capture" clips are shown as the live source they point at
+                let source = media_url.and_then(media_scanner::live_source);
+                let source_js = serde_json::to_string(&source)
+                    .map_err(|e| format!("Failed to encode live source: {}", e))?;
+                let captions = media_url.map(subtitles::captions_for_url).unwrap_or_default();
+                let captions_js = serde_json::to_string(&captions)
+                    .map_err(|e| format!("Failed to encode captions: {}", e))?;
+                
+                // Volume, fade-in and in/out points come from the clip's defaults, if any
+                let defaults = media_url.map(clip_defaults::for_url).unwrap_or_default();
+                let volume_js = defaults.volume.map(|v| v.min(100).to_string()).unwrap_or_else(|| "null".to_string());
+                let transition_ms = transition_ms.or(defaults.transition_ms).unwrap_or(0);
+                let trim_js = serde_json::to_string(&defaults.trim())
+                    .map_err(|e| format!("Failed to encode trim: {}", e))?;
+                window.eval(&format!("if (typeof setClipOptions === 'function') {{ setClipOptions({}, {}, {}, {}); }}",
+                    media_url_js, volume_js, transition_ms, trim_js))
+                    .map_err(|e| format!("Failed to execute script: {}", e))?;
+                (defaults, source_js, Some(captions_js))
+            }
+        };
+        
+        // The window acts on the decoded play type; the raw value only goes back in its status reports
+        let play_type = PlayType::from_dmx(playtype);
+        let script = format!("if (typeof updateMedia === 'function') {{ updateMedia({}, {}, {}, '{}', {}, {}, '{:?}'); console.log('updateMedia called with:', {}, {}, {}, '{}'); }} else {{ console.error('updateMedia function not found!'); }}", 
+            media_url_js, dimmer, playtype, orientation, slideshow_js, source_js, play_type, media_url_js, dimmer, playtype, orientation);
+        
+        println!("Executing script in window '{}'", window_label);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+        if let Some(captions_js) = captions_js {
+            window.eval(&format!("if (typeof setCaptions === 'function') {{ setCaptions({}); }}", captions_js))
+                .map_err(|e| format!("Failed to execute script: {}", e))?;
+        }
+        println!("Script executed successfully");
+        
+        set_shown_media(monitor_id, Some(ShownMedia {
+            media_url: media_url.map(str::to_string),
+            playtype,
+            orientation: orientation.to_string(),
+            slideshow: slideshow.cloned(),
+            defaults,
+            source_js,
+        }));
+    } else {
+        println!("Window '{}' not found", window_label);
+        set_shown_media(monitor_id, None);
+    }
+    
+    Ok(())
+}
+
+/// Push Ken Burns pan/zoom/speed values to an output window. They only
+/// affect still images; speed 0 holds the image still.
+pub fn push_motion_update(
+    app_handle: &AppHandle,
+    monitor_id: &str,
+    pan: u8,
+    zoom: u8,
+    speed: u8,
+) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof setMotion === 'function') {{ setMotion({}, {}, {}); }}", pan, zoom, speed);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Push the volume channel's level (0-255); None hands volume back to the
+/// clip's defaults file
+pub fn push_volume(app_handle: &AppHandle, monitor_id: &str, volume: Option<u8>) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let volume_js = volume.map(|v| v.to_string()).unwrap_or_else(|| "null".to_string());
+        window.eval(&format!("if (typeof setVolume === 'function') {{ setVolume({}); }}", volume_js))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Set the dimmer level (0-255, fractions allowed) of whatever the window
+/// shows, without touching the clip
+pub fn push_dimmer(app_handle: &AppHandle, monitor_id: &str, level: f32) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        window.eval(&format!("if (typeof setDimmer === 'function') {{ setDimmer({}); }}", level))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Have the window buffer a clip so selecting it next starts at once;
+/// None lets go of the buffered clip
+pub fn push_preload(app_handle: &AppHandle, monitor_id: &str, media_url: Option<&str>) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let media_url_js = serde_json::to_string(&media_url)
+            .map_err(|e| format!("Failed to encode preload clip: {}", e))?;
+        window.eval(&format!("if (typeof preloadClip === 'function') {{ preloadClip({}); }}", media_url_js))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Push the picture transform and other parameters the window applies to
+/// whatever it shows
+pub fn push_output_params(app_handle: &AppHandle, monitor_id: &str, params: &OutputParams) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let params_js = serde_json::to_string(params)
+            .map_err(|e| format!("Failed to encode output parameters: {}", e))?;
+        window.eval(&format!("if (typeof setOutputParams === 'function') {{ setOutputParams({}); }}", params_js))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Push shader effect amounts (pixelate, chroma shift, vignette; 0 = off)
+pub fn push_effects(
+    app_handle: &AppHandle,
+    monitor_id: &str,
+    pixelate: u8,
+    chroma_shift: u8,
+    vignette: u8,
+) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof setEffects === 'function') {{ setEffects({}, {}, {}); }}", pixelate, chroma_shift, vignette);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Tell an output window whether shader effects are in use, so clips are
+/// loaded in a way the effects can read
+pub fn push_effects_enabled(app_handle: &AppHandle, monitor_id: &str, enabled: bool) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof setEffectsEnabled === 'function') {{ setEffectsEnabled({}); }}", enabled);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Change how an output window maps the dimmer to opacity
+pub fn push_dimming_curve(app_handle: &AppHandle, monitor_id: &str, curve: DimmingCurve) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof setDimmingCurve === 'function') {{ setDimmingCurve('{:?}'); }}", curve);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Show or hide the caption overlay
+pub fn push_captions_visible(app_handle: &AppHandle, monitor_id: &str, visible: bool) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof setCaptionsVisible === 'function') {{ setCaptionsVisible({}); }}", visible);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Fade the whole output to `opacity` (0-1) over `duration_ms`, on top of
+/// the clip's own dimmer
+pub fn push_fade(app_handle: &AppHandle, monitor_id: &str, opacity: f32, duration_ms: u32) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof fadeOutput === 'function') {{ fadeOutput({}, {}); }}", opacity, duration_ms);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Jump the playing video to a time in seconds
+pub fn push_seek(app_handle: &AppHandle, monitor_id: &str, position_secs: f64) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let script = format!("if (typeof seekMedia === 'function') {{ seekMedia({}); }}", position_secs);
+        window.eval(&script)
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Give an output window new pixel map settings to sample with
+pub fn push_pixel_map(app_handle: &AppHandle, monitor_id: &str, pixel_map: &PixelMapConfig) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let pixel_map_js = serde_json::to_string(pixel_map)
+            .map_err(|e| format!("Failed to encode pixel map: {}", e))?;
+        window.eval(&format!("if (typeof setPixelMap === 'function') {{ setPixelMap({}); }}", pixel_map_js))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Start or stop sending the window's picture to its Spout sender
+pub fn push_spout(app_handle: &AppHandle, monitor_id: &str, spout: &SpoutConfig) -> Result<(), String> {
+    if let Some(window) = app_handle.get_webview_window(&window_label(monitor_id)) {
+        let spout_js = serde_json::to_string(spout)
+            .map_err(|e| format!("Failed to encode Spout settings: {}", e))?;
+        window.eval(&format!("if (typeof setSpout === 'function') {{ setSpout({}); }}", spout_js))
+            .map_err(|e| format!("Failed to execute script: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Where an output window was before it was switched to windowed setup
+pub struct SetupGeometry {
+    position: tauri::PhysicalPosition<i32>,
+    size: tauri::PhysicalSize<u32>,
+}
+
+/// Turn every open output window into an ordinary decorated, resizable
+/// window at half size, remembering where it was. The page keeps playing.
+pub fn enter_setup_mode(app_handle: &AppHandle, saved: &mut HashMap<String, SetupGeometry>) -> Result<(), String> {
+    for label in open_output_labels(app_handle) {
+        let Some(window) = app_handle.get_webview_window(&label) else {
+            continue;
+        };
+        if saved.contains_key(&label) {
+            continue;
+        }
+        let position = window.outer_position().map_err(|e| e.to_string())?;
+        let size = window.inner_size().map_err(|e| e.to_string())?;
+        saved.insert(label.clone(), SetupGeometry { position, size });
+        
+        let _ = window.set_ignore_cursor_events(false);
+        window.set_always_on_top(false).map_err(|e| e.to_string())?;
+        window.set_decorations(true).map_err(|e| e.to_string())?;
+        window.set_resizable(true).map_err(|e| e.to_string())?;
+        window.set_skip_taskbar(false).map_err(|e| e.to_string())?;
+        window.set_size(tauri::Size::Physical(tauri::PhysicalSize::new(size.width / 2, size.height / 2)))
+            .map_err(|e| e.to_string())?;
+        window.set_position(tauri::Position::Physical(tauri::PhysicalPosition::new(
+            position.x + (size.width / 4) as i32,
+            position.y + (size.height / 4) as i32,
+        ))).map_err(|e| e.to_string())?;
+        println!("Output window '{}' switched to windowed setup", label);
+    }
+    Ok(())
+}
+
+/// Put output windows back to borderless, always-on-top production geometry
+pub fn leave_setup_mode(app_handle: &AppHandle, config: &Mutex<AppConfig>, saved: &mut HashMap<String, SetupGeometry>) -> Result<(), String> {
+    let click_through = config.lock().unwrap().output_click_through;
+    for (label, geometry) in saved.drain() {
+        let Some(window) = app_handle.get_webview_window(&label) else {
+            continue;
+        };
+        window.set_decorations(false).map_err(|e| e.to_string())?;
+        window.set_resizable(false).map_err(|e| e.to_string())?;
+        window.set_skip_taskbar(true).map_err(|e| e.to_string())?;
+        window.set_always_on_top(true).map_err(|e| e.to_string())?;
+        window.set_position(tauri::Position::Physical(geometry.position)).map_err(|e| e.to_string())?;
+        window.set_size(tauri::Size::Physical(geometry.size)).map_err(|e| e.to_string())?;
+        if click_through {
+            let _ = window.set_ignore_cursor_events(true);
+        }
+        #[cfg(target_os = "macos")]
+        apply_macos_window_level(&window)?;
+        println!("Output window '{}' back to production geometry", label);
+    }
+    Ok(())
+}
+
+/// Make every open output window ignore the mouse, or take it again
+pub fn set_click_through_all(app_handle: &AppHandle, enabled: bool) {
+    for label in open_output_labels(app_handle) {
+        if let Some(window) = app_handle.get_webview_window(&label) {
+            if let Err(e) = window.set_ignore_cursor_events(enabled) {
+                eprintln!("Failed to set click-through on '{}': {}", label, e);
+            }
+        }
+    }
+}
+
+/// Labels of every output window currently open
+pub fn open_output_labels(app_handle: &AppHandle) -> Vec<String> {
+    app_handle.webview_windows()
+        .into_keys()
+        .filter(|label| label.starts_with("output-"))
+        .collect()
+}
+
+/// Call setOverride() inside every open output window.
+/// `mode` is "blackout", "freeze" or "none".
+pub fn set_override_all(app_handle: &AppHandle, mode: &str) {
+    let script = format!("if (typeof setOverride === 'function') {{ setOverride('{}'); }}", mode);
+    
+    for label in open_output_labels(app_handle) {
+        if let Some(window) = app_handle.get_webview_window(&label) {
+            if let Err(e) = window.eval(&script) {
+                eprintln!("Failed to set override '{}' on '{}': {}", mode, label, e);
+            }
+        }
+    }
+}
+
+/// Raise an output window above the macOS menu bar and Dock.
+///
+/// The window joins every Space (so switching Spaces never strands it),
+/// is hidden while Mission Control is showing, and is skipped by Cmd+`
+/// window cycling. AppKit calls must run on the main thread.
+#[cfg(target_os = "macos")]
+pub fn apply_macos_window_level(window: &tauri::WebviewWindow) -> Result<(), String> {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+    
+    // NSMainMenuWindowLevel (24) + 1: above the menu bar and the Dock (20)
+    const OUTPUT_WINDOW_LEVEL: isize = 25;
+    // NSWindowCollectionBehavior flags
+    const CAN_JOIN_ALL_SPACES: usize = 1 << 0;
+    const TRANSIENT: usize = 1 << 3;
+    const IGNORES_CYCLE: usize = 1 << 6;
+    const FULL_SCREEN_AUXILIARY: usize = 1 << 8;
+    
+    let target = window.clone();
+    window.run_on_main_thread(move || {
+        let ns_window = match target.ns_window() {
+            Ok(ptr) => ptr as *mut AnyObject,
+            Err(e) => {
+                eprintln!("Failed to get NSWindow for '{}': {}", target.label(), e);
+                return;
+            }
+        };
+        
+        unsafe {
+            let _: () = msg_send![ns_window, setLevel: OUTPUT_WINDOW_LEVEL];
+            let _: () = msg_send![ns_window, setCollectionBehavior: CAN_JOIN_ALL_SPACES | TRANSIENT | IGNORES_CYCLE | FULL_SCREEN_AUXILIARY];
+            // Stay visible when the control window or another app is focused
+            let _: () = msg_send![ns_window, setHidesOnDeactivate: false];
+        }
+        
+        println!("Set macOS window level for above-menu-bar display on '{}'", target.label());
+    }).map_err(|e| format!("Failed to set macOS window level: {}", e))
+}
@@ -0,0 +1,65 @@
+use crate::config::PixelMapConfig;
+use sacn::source::SacnSource;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::SocketAddr;
+
+/// sACN source for pixel-mapped colors, shared by both monitors
+pub struct PixelMapSender {
+    source: SacnSource,
+    universes: BTreeSet<u16>,
+}
+
+impl PixelMapSender {
+    pub fn new() -> Result<Self, String> {
+        let source = SacnSource::with_ip("StagePlayer DMX Pixel Map", SocketAddr::from(([0, 0, 0, 0], 0)))
+            .map_err(|e| format!("Failed to create sACN source: {}", e))?;
+        println!("Created pixel map sACN sender");
+        Ok(Self { source, universes: BTreeSet::new() })
+    }
+
+    /// Send one frame of sampled colors: RGB triplets for every pixel of
+    /// every region, in region order.
+    pub fn send(&mut self, config: &PixelMapConfig, colors: &[u8]) -> Result<(), String> {
+        for (universe, dmx_data) in universe_frames(config, colors) {
+            if self.universes.insert(universe) {
+                self.source.register_universe(universe)
+                    .map_err(|e| format!("Failed to register universe {}: {}", universe, e))?;
+            }
+            self.source.send(&[universe], &dmx_data, Some(config.priority), None, None)
+                .map_err(|e| format!("Failed to send universe {}: {}", universe, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lay the colors out in DMX frames per universe. Channels past 512 are
+/// dropped, so a long strip needs splitting into one region per universe.
+fn universe_frames(config: &PixelMapConfig, colors: &[u8]) -> BTreeMap<u16, [u8; 513]> {
+    let mut frames: BTreeMap<u16, [u8; 513]> = BTreeMap::new();
+    let mut rgb = colors.chunks_exact(3);
+
+    for region in &config.regions {
+        // index 0 is the DMX start code
+        let frame = frames.entry(region.universe).or_insert([0u8; 513]);
+        for pixel in 0..region.pixels as usize {
+            let Some(color) = rgb.next() else {
+                return frames;
+            };
+            let first = region.start_channel as usize + pixel * 3;
+            if first == 0 || first + 2 > 512 {
+                continue;
+            }
+            frame[first..first + 3].copy_from_slice(color);
+        }
+    }
+    frames
+}
+
+impl Drop for PixelMapSender {
+    fn drop(&mut self) {
+        for universe in &self.universes {
+            let _ = self.source.terminate_stream(*universe, 0);
+        }
+        println!("Pixel map sACN sender terminated");
+    }
+}
@@ -0,0 +1,67 @@
+use crate::config::OutputPlacement;
+use tauri::WebviewWindow;
+
+/// How an output window is put onto its display
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlacementStrategy {
+    /// Move the window to absolute desktop coordinates (Windows, macOS, X11)
+    Coordinates,
+    /// Ask the compositor to make the window fullscreen on a given output.
+    /// Wayland ignores client-side positioning, so this is the only way to
+    /// choose the screen there.
+    FullscreenOnOutput,
+}
+
+/// Resolve the configured placement, detecting the session type for Auto
+pub fn resolve(placement: &OutputPlacement) -> PlacementStrategy {
+    match placement {
+        OutputPlacement::Coordinates => PlacementStrategy::Coordinates,
+        OutputPlacement::FullscreenOnOutput => PlacementStrategy::FullscreenOnOutput,
+        OutputPlacement::Auto if is_wayland_session() => PlacementStrategy::FullscreenOnOutput,
+        OutputPlacement::Auto => PlacementStrategy::Coordinates,
+    }
+}
+
+/// True when running natively under a Wayland compositor (not via XWayland)
+fn is_wayland_session() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+    let forced_x11 = std::env::var("GDK_BACKEND")
+        .map(|backend| backend.starts_with("x11"))
+        .unwrap_or(false);
+    !forced_x11 && std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Make the window fullscreen on the display with the given index
+#[cfg(target_os = "linux")]
+pub fn fullscreen_on_output(window: &WebviewWindow, display_index: usize) -> Result<(), String> {
+    use gtk::prelude::*;
+
+    // GTK calls must run on the main thread
+    let target = window.clone();
+    window.run_on_main_thread(move || {
+        let gtk_window = match target.gtk_window() {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("Failed to get GTK window for '{}': {}", target.label(), e);
+                return;
+            }
+        };
+        match gtk_window.screen() {
+            Some(screen) => {
+                gtk_window.fullscreen_on_monitor(&screen, display_index as i32);
+                println!("Output window '{}' fullscreen on output {}", target.label(), display_index);
+            }
+            None => eprintln!("No GDK screen for '{}'", target.label()),
+        }
+    }).map_err(|e| format!("Failed to place window: {}", e))
+}
+
+/// Other platforms can position windows, so move to the display first and
+/// then go fullscreen there.
+#[cfg(not(target_os = "linux"))]
+pub fn fullscreen_on_output(window: &WebviewWindow, _display_index: usize) -> Result<(), String> {
+    window.set_fullscreen(true)
+        .map_err(|e| format!("Failed to make window fullscreen: {}", e))
+}
@@ -0,0 +1,114 @@
+use crate::media_scanner;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Output windows report at 4 Hz; older than this means the window is gone or hung
+const STALE_AFTER: Duration = Duration::from_secs(2);
+
+/// Playback state reported by an output window (see output.html)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PlaybackReport {
+    pub media_url: Option<String>,
+    pub dimmer: u8,
+    pub playtype: u8,
+    /// Seconds into the current clip (0 for images)
+    pub position: f64,
+    /// Clip length in seconds (0 for images or while loading)
+    pub duration: f64,
+    pub paused: bool,
+    pub error: bool,
+}
+
+/// Overall health of a monitor's output
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum PlaybackHealth {
+    /// Media is on screen
+    Playing,
+    /// Window is open but showing black
+    Idle,
+    /// The media element reported an error
+    Error,
+    /// No report received recently (window closed or unresponsive)
+    NoOutput,
+}
+
+/// Per-monitor playback state, as shown to the UI and sent as sACN feedback
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorPlayback {
+    pub monitor_id: String,
+    pub clip: u16,
+    pub dimmer: u8,
+    pub playtype: u8,
+    pub position: f64,
+    pub duration: f64,
+    pub paused: bool,
+    pub health: PlaybackHealth,
+}
+
+/// Latest report from each output window, with the clip value it shows
+#[derive(Default)]
+pub struct PlaybackTracker {
+    reports: HashMap<String, (PlaybackReport, u16, Instant)>,
+}
+
+impl PlaybackTracker {
+    /// `clip_16bit` says how the clip value is read from the file name
+    pub fn record(&mut self, monitor_id: &str, report: PlaybackReport, clip_16bit: bool) {
+        let clip = report.media_url.as_deref()
+            .and_then(|url| media_scanner::clip_value_from_url(url, clip_16bit))
+            .unwrap_or(0);
+        self.reports.insert(monitor_id.to_string(), (report, clip, Instant::now()));
+    }
+
+    /// Position and length of the video a window is playing, with the time
+    /// since its last report added on; None without a fresh report of one
+    pub fn position_now(&self, monitor_id: &str) -> Option<(f64, f64)> {
+        let (report, _, at) = self.reports.get(monitor_id)
+            .filter(|(report, _, at)| at.elapsed() < STALE_AFTER && report.duration > 0.0 && !report.error)?;
+        let position = if report.paused {
+            report.position
+        } else {
+            report.position + at.elapsed().as_secs_f64()
+        };
+        Some((position, report.duration))
+    }
+
+    pub fn status(&self, monitor_id: &str) -> MonitorPlayback {
+        let fresh = self.reports.get(monitor_id)
+            .filter(|(_, _, at)| at.elapsed() < STALE_AFTER);
+
+        match fresh {
+            Some((report, clip, _)) => {
+                let health = if report.error {
+                    PlaybackHealth::Error
+                } else if report.media_url.is_some() {
+                    PlaybackHealth::Playing
+                } else {
+                    PlaybackHealth::Idle
+                };
+
+                MonitorPlayback {
+                    monitor_id: monitor_id.to_string(),
+                    clip: *clip,
+                    dimmer: report.dimmer,
+                    playtype: report.playtype,
+                    position: report.position,
+                    duration: report.duration,
+                    paused: report.paused,
+                    health,
+                }
+            }
+            None => MonitorPlayback {
+                monitor_id: monitor_id.to_string(),
+                clip: 0,
+                dimmer: 0,
+                playtype: 0,
+                position: 0.0,
+                duration: 0.0,
+                paused: false,
+                health: PlaybackHealth::NoOutput,
+            },
+        }
+    }
+}
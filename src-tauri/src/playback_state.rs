@@ -0,0 +1,484 @@
+use crate::clip_defaults;
+use crate::config::{AppConfig, CueLook, DmxUpdate, MonitorConfig, PlayType};
+use crate::media_scanner;
+use crate::output_window;
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Position channel levels (X offset, Y offset, scale). 0 leaves that part
+/// alone, so unpatched channels don't move the picture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Position {
+    pub x: u8,
+    pub y: u8,
+    pub scale: u8,
+}
+
+impl Position {
+    /// 1-255 moves from half a frame left/up to half right/down (128 =
+    /// centred) and scales from almost nothing to 200% (128 = 100%)
+    fn transform(&self) -> Transform {
+        let offset = |level: u8| if level == 0 { 0.0 } else { (level as f32 - 128.0) / 254.0 };
+        Transform {
+            x: offset(self.x),
+            y: offset(self.y),
+            scale: if self.scale == 0 { 1.0 } else { self.scale as f32 / 128.0 },
+        }
+    }
+}
+
+/// Tint channel levels, multiplied into the output color. All three at 0
+/// counts as untinted, so unpatched channels don't black out the picture.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Tint {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Tint {
+    fn is_neutral(&self) -> bool {
+        *self == Tint::default() || *self == (Tint { r: 255, g: 255, b: 255 })
+    }
+}
+
+/// Picture transform for the output window; offsets are fractions of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Transform {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+}
+
+/// What the output window applies to whatever it shows, straight from the
+/// backend rather than through update_output_window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutputParams {
+    pub transform: Transform,
+    /// Clockwise degrees from the rotation channel; None keeps the
+    /// monitor's configured orientation
+    pub rotation: Option<u16>,
+    /// Color multiplied over the picture; None leaves it untinted
+    pub tint: Option<Tint>,
+    /// Blink rate from the strobe channel; None shows the picture steadily
+    pub strobe_hz: Option<f32>,
+}
+
+/// Payload of the playback-state event: what one monitor's channels ask for
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlaybackState {
+    pub monitor_id: String,
+    /// 1-254, or 1-65534 with 16-bit clip selection
+    pub clip: u16,
+    /// The clip value asks for the output window to close (255, or 65535 in 16-bit)
+    pub close_output: bool,
+    pub dimmer: u8,
+    pub playtype: u8,
+    /// What playtype asks for, as the output windows read it
+    pub play_type: PlayType,
+    /// Volume channel level; None when the monitor has no volume channel
+    pub volume: Option<u8>,
+    /// Clip bank from the bank channel; None when the monitor has no bank channel
+    pub bank: Option<u8>,
+    /// Transition time for the next clip change, from the crossfade channel;
+    /// None when the monitor has no crossfade channel
+    pub crossfade_ms: Option<u32>,
+    /// Crossfade into the current clip: the crossfade channel, else the
+    /// clip's `transition_ms` default, else the monitor's clip crossfade
+    pub transition_ms: u32,
+    /// None when the monitor has no position channels
+    pub position: Option<Position>,
+    /// Degrees clockwise; None when the monitor has no rotation channel
+    pub rotation: Option<u16>,
+    /// None when the monitor has no tint channels
+    pub tint: Option<Tint>,
+    /// Strobe channel level (0 = off); None when the monitor has no strobe channel
+    pub strobe: Option<u8>,
+    /// Numbered file the clip value selects, None for no clip, close or a missing file
+    pub media_file: Option<String>,
+    /// Asset URL of media_file, ready for the output window
+    pub media_url: Option<String>,
+    /// Clip the preload channel asks to buffer; None when the monitor has no preload channel
+    pub preload: Option<u8>,
+    /// Asset URL of the preload clip in the current bank, if it has a file
+    pub preload_url: Option<String>,
+}
+
+impl PlaybackState {
+    fn new(monitor_id: &str, monitor: &MonitorConfig) -> Self {
+        Self {
+            monitor_id: monitor_id.to_string(),
+            clip: 0,
+            close_output: false,
+            // Full until the console says otherwise, as the output windows start
+            dimmer: 255,
+            playtype: 0,
+            play_type: PlayType::default(),
+            volume: monitor.volume_control.then_some(255),
+            bank: monitor.bank_channel.map(|_| 0),
+            crossfade_ms: monitor.crossfade_channel.map(|_| 0),
+            transition_ms: if monitor.crossfade_channel.is_some() { 0 } else { monitor.clip_crossfade_ms },
+            position: monitor.position_channel.map(|_| Position::default()),
+            rotation: monitor.rotation_channel.map(|_| 0),
+            tint: monitor.tint_channel.map(|_| Tint::default()),
+            strobe: monitor.strobe_channel.map(|_| 0),
+            media_file: None,
+            media_url: None,
+            preload: monitor.preload_channel.map(|_| 0),
+            preload_url: None,
+        }
+    }
+
+    pub fn output_params(&self) -> OutputParams {
+        OutputParams {
+            transform: self.position.unwrap_or_default().transform(),
+            rotation: self.rotation,
+            tint: self.tint.filter(|tint| !tint.is_neutral()),
+            strobe_hz: self.strobe.filter(|&level| level > 0).map(strobe_hz),
+        }
+    }
+}
+
+/// A monitor's state before and after a packet changed it
+pub struct StateChange {
+    pub before: PlaybackState,
+    pub after: PlaybackState,
+}
+
+/// Turns channel updates into per-monitor playback states, so the frontend
+/// no longer has to reassemble clip, dimmer and playtype itself
+#[derive(Default)]
+pub struct PlaybackStates {
+    monitors: Vec<(MonitorConfig, PlaybackState)>,
+}
+
+impl PlaybackStates {
+    pub fn new(config: &AppConfig) -> Self {
+        let monitors = [("monitor1", &config.monitor1), ("monitor2", &config.monitor2)]
+            .into_iter()
+            .filter(|(_, monitor)| monitor.enabled)
+            .map(|(monitor_id, monitor)| (monitor.clone(), PlaybackState::new(monitor_id, monitor)))
+            .collect();
+        Self { monitors }
+    }
+
+    /// Apply one packet; returns each monitor it changed
+    pub fn apply(&mut self, update: &DmxUpdate) -> Vec<StateChange> {
+        let mut changed = Vec::new();
+        for (monitor, state) in &mut self.monitors {
+            let mut next = state.clone();
+            for &(channel, value) in &update.channels {
+                if channel == monitor.clip_channel() && monitor.clip_16bit {
+                    next.clip = ((value as u16) << 8) | (next.clip & 0x00ff);
+                } else if channel == monitor.clip_channel() {
+                    next.clip = value as u16;
+                } else if monitor.clip_16bit && channel == monitor.clip_fine_channel() {
+                    next.clip = (next.clip & 0xff00) | value as u16;
+                } else if channel == monitor.dimmer_channel() {
+                    next.dimmer = value;
+                } else if channel == monitor.playtype_channel() {
+                    next.playtype = value;
+                    next.play_type = PlayType::from_dmx(value);
+                } else if monitor.volume_control && channel == monitor.volume_channel() {
+                    next.volume = Some(value);
+                } else if monitor.crossfade_channel == Some(channel) {
+                    next.crossfade_ms = Some(crossfade_ms(value));
+                } else if monitor.bank_channel == Some(channel) {
+                    next.bank = Some(value);
+                } else if monitor.preload_channel == Some(channel) {
+                    next.preload = Some(value);
+                } else if monitor.strobe_channel == Some(channel) {
+                    next.strobe = Some(value);
+                } else if monitor.rotation_channel == Some(channel) {
+                    // Quarter turns in four equal bands
+                    next.rotation = Some((value / 64) as u16 * 90);
+                } else if let (Some(first), Some(position)) = (monitor.position_channel, next.position.as_mut()) {
+                    match channel.checked_sub(first) {
+                        Some(0) => position.x = value,
+                        Some(1) => position.y = value,
+                        Some(2) => position.scale = value,
+                        _ => {}
+                    }
+                }
+                if let (Some(first), Some(tint)) = (monitor.tint_channel, next.tint.as_mut()) {
+                    match channel.checked_sub(first) {
+                        Some(0) => tint.r = value,
+                        Some(1) => tint.g = value,
+                        Some(2) => tint.b = value,
+                        _ => {}
+                    }
+                }
+            }
+            next.close_output = next.clip == monitor.close_value();
+            changed.extend(settle(monitor, state, next));
+        }
+        changed
+    }
+
+    /// Show a look from a cue, the timeline or a remote control: clip,
+    /// dimmer and playtype change as if the monitor's channels had, and the
+    /// bank, volume and crossfade stay as the console left them. The close
+    /// value shows nothing rather than closing the window. None if the
+    /// monitor is disabled or already shows the look.
+    pub fn show_look(&mut self, monitor_id: &str, look: &CueLook) -> Option<StateChange> {
+        let (monitor, state) = self.monitors.iter_mut().find(|(_, state)| state.monitor_id == monitor_id)?;
+        let mut next = state.clone();
+        next.clip = look.clip;
+        next.dimmer = look.dimmer;
+        next.playtype = look.playtype;
+        next.play_type = PlayType::from_dmx(look.playtype);
+        next.close_output = false;
+        settle(monitor, state, next)
+    }
+
+    pub fn get(&self, monitor_id: &str) -> Option<&PlaybackState> {
+        self.monitors.iter().map(|(_, state)| state).find(|state| state.monitor_id == monitor_id)
+    }
+
+    /// Resolve the monitor's clip again after its media folder changed;
+    /// returns the new state if the file it points at is different
+    pub fn refresh_media(&mut self, monitor_id: &str) -> Option<PlaybackState> {
+        let (monitor, state) = self.monitors.iter_mut().find(|(_, state)| state.monitor_id == monitor_id)?;
+        let media = resolve_media(monitor, state.clip, state.bank);
+        let preload = preload_url(monitor, state);
+        if (&state.media_file, &state.media_url, &state.preload_url) == (&media.0, &media.1, &preload) {
+            return None;
+        }
+        (state.media_file, state.media_url) = media;
+        state.transition_ms = transition_ms(monitor, state);
+        state.preload_url = preload;
+        Some(state.clone())
+    }
+}
+
+/// Move a monitor to its next state, resolving whatever the change touched
+fn settle(monitor: &MonitorConfig, state: &mut PlaybackState, mut next: PlaybackState) -> Option<StateChange> {
+    if next == *state {
+        return None;
+    }
+    // Only a new clip or bank needs the folder read again
+    if (next.clip, next.bank) != (state.clip, state.bank) {
+        (next.media_file, next.media_url) = resolve_media(monitor, next.clip, next.bank);
+    }
+    if next.media_url != state.media_url || next.crossfade_ms != state.crossfade_ms {
+        next.transition_ms = transition_ms(monitor, &next);
+    }
+    if (next.preload, next.bank) != (state.preload, state.bank) {
+        next.preload_url = preload_url(monitor, &next);
+    }
+    let change = StateChange { before: state.clone(), after: next.clone() };
+    *state = next;
+    Some(change)
+}
+
+/// Send a change in the output parameters straight to the monitor's window
+pub fn push_output_params(app_handle: &AppHandle, change: &StateChange) {
+    let params = change.after.output_params();
+    if params == change.before.output_params() {
+        return;
+    }
+    if let Err(e) = output_window::push_output_params(app_handle, &change.after.monitor_id, &params) {
+        eprintln!("Failed to update output parameters on {}: {}", change.after.monitor_id, e);
+    }
+}
+
+/// Send a new preload clip straight to the monitor's window
+pub fn push_preload(app_handle: &AppHandle, change: &StateChange) {
+    if change.after.preload_url == change.before.preload_url {
+        return;
+    }
+    if let Err(e) = output_window::push_preload(app_handle, &change.after.monitor_id, change.after.preload_url.as_deref()) {
+        eprintln!("Failed to preload clip on {}: {}", change.after.monitor_id, e);
+    }
+}
+
+/// Asset URL of a clip in the given bank, None if it has no file
+pub fn clip_url(monitor: &MonitorConfig, clip: u16, bank: Option<u8>) -> Option<String> {
+    resolve_media(monitor, clip, bank).1
+}
+
+fn preload_url(monitor: &MonitorConfig, state: &PlaybackState) -> Option<String> {
+    clip_url(monitor, state.preload? as u16, state.bank)
+}
+
+/// Crossfade channel level to milliseconds: 0 = cut, 255 = 10 s
+fn crossfade_ms(level: u8) -> u32 {
+    level as u32 * 10_000 / 255
+}
+
+fn transition_ms(monitor: &MonitorConfig, state: &PlaybackState) -> u32 {
+    state.crossfade_ms
+        .or_else(|| state.media_url.as_deref().and_then(|url| clip_defaults::for_url(url).transition_ms))
+        .unwrap_or(monitor.clip_crossfade_ms)
+}
+
+/// Strobe channel level 1-255 to 1-25 Hz, the same scale as the strobe generator
+fn strobe_hz(level: u8) -> f32 {
+    1.0 + (level as f32 - 1.0) / 254.0 * 24.0
+}
+
+/// 0 = no clip and the highest value closes the output, so neither has a
+/// file. The clip is looked up in the bank's folder, if the monitor has banks.
+fn resolve_media(monitor: &MonitorConfig, clip: u16, bank: Option<u8>) -> (Option<String>, Option<String>) {
+    if clip == 0 || clip == monitor.close_value() {
+        return (None, None);
+    }
+    let Some(folder) = media_scanner::bank_folder(&monitor.media_folder, bank.unwrap_or(0)) else {
+        return (None, None);
+    };
+    match media_scanner::scan_clips(&folder, monitor.clip_16bit) {
+        Ok(mut media) => match media.remove(&clip) {
+            Some(file) => (Some(file.filename), Some(media_scanner::asset_url(&file.path))),
+            None => (None, None),
+        },
+        Err(e) => {
+            eprintln!("Failed to read media folder {:?}: {}", folder, e);
+            (None, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Empty files (and bank subfolders) in a fresh temporary folder
+    fn media_folder(name: &str, files: &[&str]) -> PathBuf {
+        let folder = std::env::temp_dir().join(format!("stageplayer-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&folder);
+        for file in files {
+            let path = folder.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"").unwrap();
+        }
+        folder
+    }
+
+    fn states(monitor: &MonitorConfig) -> PlaybackStates {
+        PlaybackStates {
+            monitors: vec![(monitor.clone(), PlaybackState::new("monitor1", monitor))],
+        }
+    }
+
+    fn update(channels: &[(u16, u8)]) -> DmxUpdate {
+        DmxUpdate { universe: 1, channels: channels.to_vec() }
+    }
+
+    #[test]
+    fn clip_changes_resolve_the_file() {
+        let monitor = MonitorConfig {
+            media_folder: media_folder("clips", &["001_intro.mp4", "002_still.jpg"]),
+            ..Default::default()
+        };
+        let mut states = states(&monitor);
+
+        let changed = states.apply(&update(&[(1, 1)]));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].before.clip, 0);
+        assert_eq!(changed[0].after.media_file.as_deref(), Some("001_intro.mp4"));
+
+        states.apply(&update(&[(1, 2)]));
+        assert_eq!(states.get("monitor1").unwrap().media_file.as_deref(), Some("002_still.jpg"));
+
+        // No file for 3; 255 closes the output
+        states.apply(&update(&[(1, 3)]));
+        assert_eq!(states.get("monitor1").unwrap().media_url, None);
+        states.apply(&update(&[(1, 255)]));
+        let state = states.get("monitor1").unwrap();
+        assert!(state.close_output);
+        assert_eq!(state.media_url, None);
+
+        states.apply(&update(&[(1, 0)]));
+        assert!(!states.get("monitor1").unwrap().close_output);
+    }
+
+    #[test]
+    fn unchanged_values_report_nothing() {
+        let mut states = states(&MonitorConfig::default());
+        assert_eq!(states.apply(&update(&[(2, 128)])).len(), 1);
+        assert!(states.apply(&update(&[(2, 128)])).is_empty());
+        // Channels outside the monitor's layout
+        assert!(states.apply(&update(&[(200, 7)])).is_empty());
+    }
+
+    #[test]
+    fn playtype_changes_decode_the_play_type() {
+        let mut states = states(&MonitorConfig::default());
+        for (value, expected) in [(160, PlayType::PlayOnce), (159, PlayType::FreezeLastFrame), (0, PlayType::Loop)] {
+            let changed = states.apply(&update(&[(3, value)]));
+            assert_eq!(changed.len(), 1);
+            assert_eq!(changed[0].after.playtype, value);
+            assert_eq!(changed[0].after.play_type, expected);
+        }
+    }
+
+    #[test]
+    fn bank_changes_switch_folders() {
+        let monitor = MonitorConfig {
+            media_folder: media_folder("banks", &["001_root.mp4", "001_act2/001_scene.mp4"]),
+            bank_channel: Some(20),
+            ..Default::default()
+        };
+        let mut states = states(&monitor);
+
+        states.apply(&update(&[(1, 1)]));
+        assert_eq!(states.get("monitor1").unwrap().media_file.as_deref(), Some("001_root.mp4"));
+
+        let changed = states.apply(&update(&[(20, 1)]));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].after.bank, Some(1));
+        assert_eq!(changed[0].after.media_file.as_deref(), Some("001_scene.mp4"));
+
+        // A bank without a subfolder has no clips
+        states.apply(&update(&[(20, 2)]));
+        assert_eq!(states.get("monitor1").unwrap().media_file, None);
+    }
+
+    #[test]
+    fn looks_use_the_current_bank_and_never_close() {
+        let monitor = MonitorConfig {
+            media_folder: media_folder("looks", &["001_root.mp4", "001_act2/00300_scene.mp4"]),
+            bank_channel: Some(20),
+            clip_16bit: true,
+            ..Default::default()
+        };
+        let mut states = states(&monitor);
+        states.apply(&update(&[(20, 1)]));
+
+        let change = states.show_look("monitor1", &CueLook { clip: 300, dimmer: 200, playtype: 160 }).unwrap();
+        assert_eq!(change.after.media_file.as_deref(), Some("00300_scene.mp4"));
+        assert_eq!((change.after.dimmer, change.after.play_type), (200, PlayType::PlayOnce));
+        assert!(states.show_look("monitor1", &CueLook { clip: 300, dimmer: 200, playtype: 160 }).is_none());
+
+        let change = states.show_look("monitor1", &CueLook { clip: u16::MAX, dimmer: 200, playtype: 160 }).unwrap();
+        assert!(!change.after.close_output);
+        assert_eq!(change.after.media_url, None);
+
+        // The console's next clip change takes over again
+        states.apply(&update(&[(1, 0), (2, 0)]));
+        assert_eq!(states.get("monitor1").unwrap().clip, 0);
+    }
+
+    #[test]
+    fn sixteen_bit_clips_combine_coarse_and_fine() {
+        let monitor = MonitorConfig {
+            media_folder: media_folder("clips16", &["00261_big.mp4"]),
+            clip_16bit: true,
+            ..Default::default()
+        };
+        let mut states = states(&monitor);
+
+        states.apply(&update(&[(1, 1), (2, 5)]));
+        let state = states.get("monitor1").unwrap();
+        assert_eq!(state.clip, 261);
+        assert_eq!(state.media_file.as_deref(), Some("00261_big.mp4"));
+
+        // The fine channel alone keeps the coarse value
+        states.apply(&update(&[(2, 6)]));
+        assert_eq!(states.get("monitor1").unwrap().clip, 262);
+
+        states.apply(&update(&[(1, 255), (2, 255)]));
+        assert!(states.get("monitor1").unwrap().close_output);
+    }
+}
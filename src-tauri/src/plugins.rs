@@ -0,0 +1,121 @@
+use crate::artnet_listener::ArtNetListener;
+use crate::config::{DmxUpdate, SacnConfig};
+use crate::media_scanner::LiveSource;
+use crate::sacn_listener::SacnListener;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Receives the levels of each packet from an input protocol
+pub type InputCallback = Arc<dyn Fn(DmxUpdate) + Send + Sync>;
+
+/// Builds an input protocol for the listener settings
+pub type InputFactory = fn(&SacnConfig) -> Box<dyn InputProtocol>;
+
+/// A network protocol that delivers DMX levels (sACN, KiNET, ...)
+pub trait InputProtocol: Send {
+    fn name(&self) -> &str;
+    /// Start delivering updates for the configured universe
+    fn start(&mut self, callback: InputCallback) -> Result<(), String>;
+    /// Ask the protocol to stop without waiting for it
+    fn signal_stop(&mut self);
+    /// Stop and wait until its sockets are released
+    fn stop(&mut self);
+}
+
+/// A kind of clip file the player doesn't handle itself. The output window
+/// shows whatever live source it resolves the file to.
+pub trait MediaSource: Send + Sync {
+    fn name(&self) -> &str;
+    /// File extensions handled, lowercase and without the dot
+    fn extensions(&self) -> Vec<String>;
+    fn live_source(&self, path: &Path) -> Option<LiveSource>;
+}
+
+static INPUT_PROTOCOLS: Mutex<Vec<(String, InputFactory)>> = Mutex::new(Vec::new());
+static MEDIA_SOURCES: Mutex<Vec<Arc<dyn MediaSource>>> = Mutex::new(Vec::new());
+
+/// Make an input protocol available under `name` (lowercase). Registering a
+/// name again replaces the earlier factory.
+pub fn register_input_protocol(name: &str, factory: InputFactory) {
+    let mut protocols = INPUT_PROTOCOLS.lock().unwrap();
+    protocols.retain(|(existing, _)| existing != name);
+    protocols.push((name.to_string(), factory));
+    println!("Registered input protocol '{}'", name);
+}
+
+/// Make a media source available for its file extensions
+pub fn register_media_source(source: Arc<dyn MediaSource>) {
+    println!("Registered media source '{}' for {:?}", source.name(), source.extensions());
+    MEDIA_SOURCES.lock().unwrap().push(source);
+}
+
+/// An input protocol the UI can offer, as returned by get_available_inputs
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailableInput {
+    pub name: String,
+    /// Currently started alongside the sACN listener
+    pub running: bool,
+}
+
+pub fn input_protocol_names() -> Vec<String> {
+    INPUT_PROTOCOLS.lock().unwrap().iter().map(|(name, _)| name.clone()).collect()
+}
+
+pub fn create_input(name: &str, config: &SacnConfig) -> Result<Box<dyn InputProtocol>, String> {
+    let protocols = INPUT_PROTOCOLS.lock().unwrap();
+    let (_, factory) = protocols.iter()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown input protocol '{}'", name))?;
+    Ok(factory(config))
+}
+
+/// Registered media source for a file extension (lowercase)
+pub fn media_source_for(ext: &str) -> Option<Arc<dyn MediaSource>> {
+    MEDIA_SOURCES.lock().unwrap().iter()
+        .find(|source| source.extensions().iter().any(|e| e == ext))
+        .cloned()
+}
+
+/// Register what ships with the player. Add-on crates register theirs after
+/// this, before the app starts.
+pub fn register_builtin() {
+    register_input_protocol("sacn", |config| Box::new(SacnListener::new(config.clone())));
+    register_input_protocol("artnet", |config| Box::new(ArtNetListener::new(config.clone())));
+}
+
+impl InputProtocol for SacnListener {
+    fn name(&self) -> &str {
+        "sacn"
+    }
+
+    fn start(&mut self, callback: InputCallback) -> Result<(), String> {
+        SacnListener::start(self, move |update| callback(update))
+    }
+
+    fn signal_stop(&mut self) {
+        SacnListener::signal_stop(self);
+    }
+
+    fn stop(&mut self) {
+        SacnListener::stop(self);
+    }
+}
+
+impl InputProtocol for ArtNetListener {
+    fn name(&self) -> &str {
+        "artnet"
+    }
+
+    fn start(&mut self, callback: InputCallback) -> Result<(), String> {
+        ArtNetListener::start(self, move |update| callback(update))
+    }
+
+    fn signal_stop(&mut self) {
+        ArtNetListener::signal_stop(self);
+    }
+
+    fn stop(&mut self) {
+        ArtNetListener::stop(self);
+    }
+}
@@ -1,14 +1,708 @@
-use crate::config::{DmxUpdate, SacnConfig, SacnMode};
+use crate::config::{DmxUpdate, MergeMode, SacnConfig, SacnMode, TerminationAction};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use std::net::{SocketAddr, IpAddr};
-use sacn::packet::ACN_SDT_MULTICAST_PORT;
-use sacn::receive::SacnReceiver;
+use std::time::{Duration, Instant};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use sacn::packet::{
+    AcnRootLayerProtocol, E131RootLayerData, ACN_SDT_MULTICAST_PORT, E131_DISCOVERY_UNIVERSE,
+    E131_NETWORK_DATA_LOSS_TIMEOUT, E131_UNIVERSE_DISCOVERY_INTERVAL,
+};
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Packets handed to the running listener in-process, for testing on
+/// networks that block multicast loopback
+static LOOPBACK: Mutex<Option<Sender<Vec<u8>>>> = Mutex::new(None);
+
+/// Give the running listener an E1.31 packet as if it had arrived from
+/// 127.0.0.1; it goes through the same tracking and merge as network data
+pub fn inject_loopback(packet: Vec<u8>) -> Result<(), String> {
+    LOOPBACK.lock().unwrap().as_ref()
+        .ok_or_else(|| "No sACN listener is running".to_string())?
+        .send(packet)
+        .map_err(|_| "No sACN listener is running".to_string())
+}
+
+/// Most recent DMX values for the listened universe, shared with background
+/// tasks that need to know the live look (e.g. the signal-loss watchdog).
+pub struct DmxFrame {
+    /// Channel N lives at index N; index 0 is unused
+    pub values: [u8; 513],
+    pub last_received: Option<Instant>,
+    /// Set while a listener is running, cleared when it is stopped
+    pub listening_since: Option<Instant>,
+}
+
+impl Default for DmxFrame {
+    fn default() -> Self {
+        DmxFrame {
+            values: [0u8; 513],
+            last_received: None,
+            listening_since: None,
+        }
+    }
+}
+
+/// A device sending sACN on the listened universe
+#[derive(Debug, Clone, Serialize)]
+pub struct SacnSource {
+    pub cid: String,
+    pub name: String,
+    pub ip: String,
+    pub priority: u8,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum SourceWarningKind {
+    /// Two sources at the same priority; their levels mix packet by packet
+    EqualPriority,
+    /// A higher-priority source appeared and now has control
+    HigherPriority,
+}
+
+/// Why control of the universe may have just changed hands
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceWarning {
+    pub kind: SourceWarningKind,
+    pub universe: u16,
+    /// The source that appeared or changed priority
+    pub source: SacnSource,
+    /// The source that was already sending
+    pub other: SacnSource,
+    pub message: String,
+}
+
+/// A source sending the listened universe, as reported every second
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveSource {
+    #[serde(flatten)]
+    pub source: SacnSource,
+    pub packets_per_sec: f64,
+    pub secs_since_seen: f64,
+    /// Data packets since the source was first heard
+    pub packets: u64,
+}
+
+/// A source stopped sending the universe with stream-terminated (E1.31 §6.2.6)
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceTerminated {
+    pub universe: u16,
+    pub source: SacnSource,
+    /// Sources still sending the universe
+    pub remaining: usize,
+    /// What the outputs did; only applied when no source remains
+    pub action: TerminationAction,
+    pub message: String,
+}
+
+/// Several sources at the same priority are controlling channels the
+/// player uses, so those channels jump between their levels
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceConflict {
+    pub universe: u16,
+    pub sources: Vec<SacnSource>,
+    /// Watched channels the sources are fighting over, ascending
+    pub channels: Vec<u16>,
+    pub message: String,
+}
+
+/// The configured network interface is missing or lost its address
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceError {
+    pub interface: String,
+    pub message: String,
+}
+
+/// The configured network interface came up on a new address and the
+/// listener moved over to it
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceChanged {
+    pub interface: String,
+    pub old_ip: String,
+    pub new_ip: String,
+    pub message: String,
+}
+
+/// Reported by the listener thread alongside the DMX data
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum ListenerEvent {
+    SourceWarning(SourceWarning),
+    SourceTerminated(SourceTerminated),
+    /// When a conflict starts or the sources in it change
+    SourceConflict(SourceConflict),
+    /// Every second while listening, highest priority first
+    Sources { sources: Vec<ActiveSource> },
+    InterfaceError(InterfaceError),
+    InterfaceChanged(InterfaceChanged),
+    /// Every second while listening
+    Stats(SacnStats),
+}
+
+type EventHandler = Arc<dyn Fn(ListenerEvent) + Send + Sync>;
+
+/// A source announcing itself with E1.31 universe discovery (§8)
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredSource {
+    pub cid: String,
+    pub name: String,
+    pub ip: String,
+    /// Every universe the source says it is sending, ascending
+    pub universes: Vec<u16>,
+    pub secs_since_seen: f64,
+}
+
+struct DiscoveryEntry {
+    name: String,
+    ip: String,
+    /// Universe lists by discovery page
+    pages: HashMap<u8, Vec<u16>>,
+    last_seen: Instant,
+}
+
+type Discovered = Arc<Mutex<HashMap<String, DiscoveryEntry>>>;
+
+/// Counters for the listened universe since the listener started, with
+/// rates and timing over the last second
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SacnStats {
+    /// Data packets for the universe, including dropped ones
+    pub packets: u64,
+    /// Duplicated or late packets dropped by the sequence check
+    pub out_of_sequence: u64,
+    /// Packets missing from sources' sequence numbers, presumed lost
+    pub sequence_gaps: u64,
+    /// Unicast packets dropped because the sender isn't an allowed source
+    pub rejected: u64,
+    pub packets_per_sec: f64,
+    /// None until the first data packet
+    pub secs_since_last_packet: Option<f64>,
+    /// Longest wait between two packets
+    pub max_gap_ms: f64,
+    /// Standard deviation of the time between packets
+    pub jitter_ms: f64,
+    /// Per-source counts, highest priority first
+    pub sources: Vec<ActiveSource>,
+}
+
+/// Time between data packets over one stats period
+#[derive(Default)]
+struct ArrivalTimes {
+    last: Option<Instant>,
+    count: u32,
+    sum_ms: f64,
+    sum_sq_ms: f64,
+    max_ms: f64,
+}
+
+impl ArrivalTimes {
+    fn record(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last {
+            let gap_ms = now.duration_since(last).as_secs_f64() * 1000.0;
+            self.count += 1;
+            self.sum_ms += gap_ms;
+            self.sum_sq_ms += gap_ms * gap_ms;
+            self.max_ms = self.max_ms.max(gap_ms);
+        }
+        self.last = Some(now);
+    }
+
+    /// (longest gap, jitter) since the last call, then start a new period
+    fn take(&mut self) -> (f64, f64) {
+        let jitter = if self.count > 1 {
+            let mean = self.sum_ms / self.count as f64;
+            (self.sum_sq_ms / self.count as f64 - mean * mean).max(0.0).sqrt()
+        } else {
+            0.0
+        };
+        let max_gap = self.max_ms;
+        *self = ArrivalTimes { last: self.last, ..Default::default() };
+        (max_gap, jitter)
+    }
+}
+
+/// DMX start codes the listener understands
+const START_CODE_LEVELS: u8 = 0x00;
+const START_CODE_PER_ADDRESS_PRIORITY: u8 = 0xDD;
+
+struct TrackedSource {
+    info: SacnSource,
+    last_seen: Instant,
+    sequence: u8,
+    /// Last levels, start code at index 0; None until the first one arrives
+    levels: Option<Vec<u8>>,
+    /// Per-address priorities from 0xDD packets; 0 means the source isn't
+    /// controlling that channel. None means every channel uses `info.priority`.
+    priorities: Option<Vec<u8>>,
+    /// When the last 0xDD packet arrived
+    priorities_updated: Instant,
+    /// When each channel's level last changed, for LTP merging
+    changed: Vec<Instant>,
+    /// Data packets since the source was first heard
+    packets: u64,
+    /// `packets` at the last sources report
+    reported_packets: u64,
+}
+
+impl TrackedSource {
+    /// A source that stops sending 0xDD packets falls back to its packet
+    /// priority after the data loss timeout
+    fn priority_for(&self, channel: usize) -> u8 {
+        match &self.priorities {
+            Some(priorities) if self.priorities_updated.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT => {
+                priorities.get(channel).copied().unwrap_or(0)
+            }
+            _ => self.info.priority,
+        }
+    }
+}
+
+/// Sources heard on the universe within the E1.31 data loss timeout, by CID
+#[derive(Default)]
+struct SourceTracker {
+    sources: HashMap<String, TrackedSource>,
+}
+
+impl SourceTracker {
+    /// Record a data packet; returns any warning for the operator
+    fn record(&mut self, universe: u16, info: SacnSource, sequence: u8) -> Option<SourceWarning> {
+        let now = Instant::now();
+        self.sources.retain(|_, s| now.duration_since(s.last_seen) < E131_NETWORK_DATA_LOSS_TIMEOUT);
+
+        // Only a new source or a priority change can change who is in control
+        let warning = match self.sources.get(&info.cid).map(|s| s.info.priority) {
+            Some(priority) if priority == info.priority => None,
+            _ => self.warning_for(universe, &info),
+        };
+        match self.sources.get_mut(&info.cid) {
+            Some(source) => {
+                source.info = info;
+                source.last_seen = now;
+                source.sequence = sequence;
+                source.packets += 1;
+            }
+            None => {
+                self.sources.insert(info.cid.clone(), TrackedSource {
+                    info,
+                    last_seen: now,
+                    sequence,
+                    levels: None,
+                    priorities: None,
+                    priorities_updated: now,
+                    changed: Vec::new(),
+                    packets: 1,
+                    reported_packets: 0,
+                });
+            }
+        }
+        warning
+    }
+
+    fn warning_for(&self, universe: u16, source: &SacnSource) -> Option<SourceWarning> {
+        let other = self.sources.values()
+            .map(|s| &s.info)
+            .filter(|s| s.cid != source.cid)
+            .max_by_key(|s| s.priority)?;
+        let (kind, message) = if source.priority > other.priority {
+            (SourceWarningKind::HigherPriority, format!(
+                "'{}' ({}) at priority {} took over universe {} from '{}' ({}) at priority {}",
+                source.name, source.ip, source.priority, universe, other.name, other.ip, other.priority))
+        } else if source.priority == other.priority {
+            (SourceWarningKind::EqualPriority, format!(
+                "'{}' ({}) and '{}' ({}) are both sending universe {} at priority {}",
+                source.name, source.ip, other.name, other.ip, universe, source.priority))
+        } else {
+            return None;
+        };
+        Some(SourceWarning { kind, universe, source: source.clone(), other: other.clone(), message })
+    }
+
+    /// Store a source's levels (0x00 start code packet), noting which
+    /// channels changed
+    fn set_levels(&mut self, cid: &str, values: &[u8]) {
+        if let Some(source) = self.sources.get_mut(cid) {
+            let now = Instant::now();
+            // Channels the source hasn't sent before count as changed now
+            source.changed.resize(values.len(), now);
+            for (channel, value) in values.iter().enumerate() {
+                let previous = source.levels.as_ref().and_then(|levels| levels.get(channel));
+                if previous != Some(value) {
+                    source.changed[channel] = now;
+                }
+            }
+            source.levels = Some(values.to_vec());
+        }
+    }
+
+    /// Store a source's per-address priorities (0xDD start code packet)
+    fn set_priorities(&mut self, cid: &str, values: &[u8]) {
+        if let Some(source) = self.sources.get_mut(cid) {
+            source.priorities = Some(values.to_vec());
+            source.priorities_updated = Instant::now();
+        }
+    }
+
+    /// One level per channel from the sources that sent levels: the highest
+    /// priority for that channel wins; equal priorities are merged HTP or LTP
+    /// (the source that changed the channel last). Channel N is at index N;
+    /// index 0 is the start code.
+    fn merged(&self, merge_mode: MergeMode) -> [u8; 513] {
+        let mut values = [0u8; 513];
+        for (channel, value) in values.iter_mut().enumerate().skip(1) {
+            let contenders = self.sources.values()
+                .filter_map(|s| s.levels.as_ref().map(|levels| (s.priority_for(channel), s, levels)))
+                .filter(|(priority, _, _)| *priority > 0);
+            let Some(top) = contenders.clone().map(|(priority, _, _)| priority).max() else {
+                continue;
+            };
+            let level_of = |levels: &Vec<u8>| levels.get(channel).copied().unwrap_or(0);
+            let at_top = contenders.filter(|(priority, _, _)| *priority == top);
+            *value = match merge_mode {
+                MergeMode::Htp => at_top.map(|(_, _, levels)| level_of(levels)).max().unwrap_or(0),
+                MergeMode::Ltp => at_top
+                    .max_by_key(|(_, source, _)| source.changed.get(channel).copied())
+                    .map(|(_, _, levels)| level_of(levels))
+                    .unwrap_or(0),
+            };
+        }
+        values
+    }
+
+    /// Sources still sending, with their packet rate since the last report
+    fn report(&mut self, elapsed: Duration) -> Vec<ActiveSource> {
+        let secs = elapsed.as_secs_f64().max(0.001);
+        let mut active: Vec<ActiveSource> = self.sources.values_mut()
+            .filter(|s| s.last_seen.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT)
+            .map(|s| {
+                let packets_per_sec = (s.packets - s.reported_packets) as f64 / secs;
+                s.reported_packets = s.packets;
+                ActiveSource {
+                    source: s.info.clone(),
+                    packets_per_sec,
+                    secs_since_seen: s.last_seen.elapsed().as_secs_f64(),
+                    packets: s.packets,
+                }
+            })
+            .collect();
+        active.sort_by(|a, b| b.source.priority.cmp(&a.source.priority).then_with(|| a.source.name.cmp(&b.source.name)));
+        active
+    }
+
+    /// Sources sharing the top priority on any of `channels` (all channels
+    /// if empty), with the channels they share, ordered by name
+    fn conflicts(&self, channels: &[u16]) -> (Vec<SacnSource>, Vec<u16>) {
+        let all: Vec<u16> = (1..=512).collect();
+        let channels = if channels.is_empty() { &all[..] } else { channels };
+        let mut sources: Vec<SacnSource> = Vec::new();
+        let mut contested = Vec::new();
+        for &channel in channels {
+            let contenders: Vec<(u8, &TrackedSource)> = self.sources.values()
+                .filter(|s| s.levels.is_some() && s.last_seen.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT)
+                .map(|s| (s.priority_for(channel as usize), s))
+                .filter(|(priority, _)| *priority > 0)
+                .collect();
+            let Some(top) = contenders.iter().map(|(priority, _)| *priority).max() else {
+                continue;
+            };
+            let at_top: Vec<&TrackedSource> = contenders.iter()
+                .filter(|(priority, _)| *priority == top)
+                .map(|(_, s)| *s)
+                .collect();
+            if at_top.len() < 2 {
+                continue;
+            }
+            contested.push(channel);
+            for source in at_top {
+                if !sources.iter().any(|s| s.cid == source.info.cid) {
+                    sources.push(source.info.clone());
+                }
+            }
+        }
+        sources.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.cid.cmp(&b.cid)));
+        contested.sort_unstable();
+        contested.dedup();
+        (sources, contested)
+    }
+
+    fn last_sequence(&self, cid: &str) -> Option<u8> {
+        self.sources.get(cid).map(|s| s.sequence)
+    }
+
+    /// True if a data packet is a duplicate or arrived late and should be
+    /// dropped (E1.31 §6.7.2): its sequence number is 0-19 behind the
+    /// source's last one. Dropped packets still count towards the source.
+    fn out_of_sequence(&mut self, cid: &str, sequence: u8) -> bool {
+        let Some(source) = self.sources.get_mut(cid)
+            .filter(|s| s.last_seen.elapsed() < E131_NETWORK_DATA_LOSS_TIMEOUT) else {
+            return false;
+        };
+        let diff = sequence.wrapping_sub(source.sequence) as i8;
+        if diff <= 0 && diff > -20 {
+            source.packets += 1;
+            return true;
+        }
+        false
+    }
+
+    fn has_levels(&self) -> bool {
+        self.sources.values().any(|s| s.levels.is_some())
+    }
+
+    /// Forget a source that sent stream-terminated
+    fn remove(&mut self, cid: &str) -> Option<SacnSource> {
+        self.sources.remove(cid).map(|s| s.info)
+    }
+
+    fn len(&self) -> usize {
+        self.sources.len()
+    }
+}
+
+/// Levels held back until the sync packet for their synchronization address
+struct PendingFrame {
+    cid: String,
+    sync_address: u16,
+    values: Vec<u8>,
+    received: Instant,
+}
+
+#[derive(Default)]
+struct SyncState {
+    /// At most one held frame per source
+    pending: Vec<PendingFrame>,
+    /// Sync universe whose multicast group has been joined
+    joined: Option<u16>,
+    /// Sync packets stopped arriving; data is acted on as it comes until
+    /// the next one shows up
+    lost: bool,
+}
+
+impl SyncState {
+    fn hold(&mut self, frame: PendingFrame) {
+        self.pending.retain(|pending| pending.cid != frame.cid);
+        self.pending.push(frame);
+    }
+
+    /// The held frames this sync packet is for
+    fn release(&mut self, sync_address: u16) -> Vec<PendingFrame> {
+        self.lost = false;
+        let (released, kept) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|frame| frame.sync_address == sync_address);
+        self.pending = kept;
+        released
+    }
+
+    /// Held frames whose sync packet hasn't come within the data loss
+    /// timeout (E1.31 §11.2.2)
+    fn expire(&mut self) -> Vec<PendingFrame> {
+        let (expired, kept): (Vec<PendingFrame>, Vec<PendingFrame>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|frame| frame.received.elapsed() >= E131_NETWORK_DATA_LOSS_TIMEOUT);
+        self.pending = kept;
+        if !expired.is_empty() {
+            self.lost = true;
+        }
+        expired
+    }
+}
+
+/// After this long without a packet other inputs (a backup, a replay) may
+/// have set the channels, so the next packet is sent in full
+const RESEND_AFTER_GAP: Duration = Duration::from_secs(1);
+
+/// Remembers the levels last sent downstream so a packet only lists the
+/// channels that changed. A static look then costs nothing downstream,
+/// while every packet is still delivered so signal-loss timers see the
+/// input is alive. Shared with the Art-Net listener.
+#[derive(Default)]
+pub struct LevelCache {
+    last: Option<([u8; 512], Instant)>,
+}
+
+impl LevelCache {
+    /// (channel, level) for each of channels 1-512 that differs from what was last sent
+    pub fn changes(&mut self, levels: [u8; 512]) -> Vec<(u16, u8)> {
+        let sent = self.last
+            .filter(|(_, at)| at.elapsed() < RESEND_AFTER_GAP)
+            .map(|(sent, _)| sent);
+        self.last = Some((levels, Instant::now()));
+        (0..levels.len())
+            .filter(|&i| sent.is_none_or(|sent| sent[i] != levels[i]))
+            .map(|i| (i as u16 + 1, levels[i]))
+            .collect()
+    }
+}
+
+/// Send the levels of one packet or synchronized frame downstream.
+/// values[0] is the DMX start code; channel N is at index N.
+fn emit_levels(universe: u16, values: &[u8], cache: &mut LevelCache, callback: &impl Fn(DmxUpdate)) {
+    let mut levels = [0u8; 512];
+    for (i, level) in levels.iter_mut().enumerate() {
+        *level = values.get(i + 1).copied().unwrap_or(0);
+    }
+    callback(DmxUpdate { universe, channels: cache.changes(levels) });
+}
+
+/// Multicast group for a universe (E1.31 §9.3.1): 239.255.hi.lo
+fn multicast_group(universe: u16) -> Ipv4Addr {
+    let [hi, lo] = universe.to_be_bytes();
+    Ipv4Addr::new(239, 255, hi, lo)
+}
+
+/// IPv6 multicast group for a universe (E1.31 §9.3.2): FF18::83:00:hi:lo
+fn multicast_group_v6(universe: u16) -> Ipv6Addr {
+    Ipv6Addr::new(0xff18, 0, 0, 0, 0, 0, 0x8300, universe)
+}
+
+/// Join a universe's group in the interface's address family. IPv6 joins
+/// by interface index; :: uses the system's default multicast interface.
+fn join_universe(socket: &UdpSocket, universe: u16, interface_ip: IpAddr) -> std::io::Result<()> {
+    match interface_ip {
+        IpAddr::V4(ip) => socket.join_multicast_v4(&multicast_group(universe), &ip),
+        IpAddr::V6(ip) => socket.join_multicast_v6(&multicast_group_v6(universe), ipv6_interface_index(ip)?),
+    }
+}
+
+/// Leave the sync universe's multicast group, unless it is one of the data
+/// universes in `keep`
+fn leave_sync_universe(socket: &UdpSocket, sync: &SyncState, keep: &[u16], interface_ip: IpAddr) {
+    let Some(joined) = sync.joined.filter(|joined| !keep.contains(joined)) else {
+        return;
+    };
+    match leave_universe(socket, joined, interface_ip) {
+        Ok(()) => println!("Left sync universe {}", joined),
+        Err(e) => eprintln!("Failed to leave sync universe {}: {}", joined, e),
+    }
+}
+
+fn leave_universe(socket: &UdpSocket, universe: u16, interface_ip: IpAddr) -> std::io::Result<()> {
+    match interface_ip {
+        IpAddr::V4(ip) => socket.leave_multicast_v4(&multicast_group(universe), &ip),
+        IpAddr::V6(ip) => socket.leave_multicast_v6(&multicast_group_v6(universe), ipv6_interface_index(ip)?),
+    }
+}
+
+/// Index of the interface holding an IPv6 address, 0 for ::
+fn ipv6_interface_index(ip: Ipv6Addr) -> std::io::Result<u32> {
+    if ip.is_unspecified() {
+        return Ok(0);
+    }
+    get_if_addrs::get_if_addrs()?
+        .into_iter()
+        .find(|iface| iface.ip() == IpAddr::V6(ip))
+        .and_then(|iface| interface_index(&iface.name))
+        .ok_or_else(|| std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no interface index for {}", ip),
+        ))
+}
+
+#[cfg(unix)]
+fn interface_index(name: &str) -> Option<u32> {
+    let name = std::ffi::CString::new(name).ok()?;
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    (index != 0).then_some(index)
+}
+
+/// Windows names interfaces by adapter GUID, e.g. "{4D36E972-E325-...}"
+#[cfg(windows)]
+fn interface_index(name: &str) -> Option<u32> {
+    use windows::core::GUID;
+    use windows::Win32::NetworkManagement::IpHelper::{ConvertInterfaceGuidToLuid, ConvertInterfaceLuidToIndex};
+    use windows::Win32::NetworkManagement::Ndis::NET_LUID_LH;
+
+    let hex: String = name.trim_start_matches('{').trim_end_matches('}')
+        .chars()
+        .filter(|c| *c != '-')
+        .collect();
+    if hex.len() != 32 {
+        return None;
+    }
+    let guid = GUID::from_u128(u128::from_str_radix(&hex, 16).ok()?);
+    let mut luid = NET_LUID_LH::default();
+    let mut index = 0u32;
+    unsafe {
+        ConvertInterfaceGuidToLuid(&guid, &mut luid).ok().ok()?;
+        ConvertInterfaceLuidToIndex(&luid, &mut index).ok().ok()?;
+    }
+    Some(index)
+}
+
+/// Current address of a named interface in the family of `configured`,
+/// keeping `configured` if the interface still has it (DHCP may have moved it)
+fn resolve_interface(name: &str, configured: &str) -> Option<IpAddr> {
+    let configured: Option<IpAddr> = configured.parse().ok();
+    let want_v6 = configured.is_some_and(|ip| ip.is_ipv6());
+    let addresses: Vec<IpAddr> = get_if_addrs::get_if_addrs().ok()?
+        .into_iter()
+        .filter(|iface| iface.name == name)
+        .map(|iface| iface.ip())
+        .filter(|ip| ip.is_ipv6() == want_v6)
+        .collect();
+    match configured {
+        Some(ip) if addresses.contains(&ip) => Some(ip),
+        _ => addresses.first().copied(),
+    }
+}
+
+/// Unspecified address in the same family as `ip`
+fn unspecified_like(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    }
+}
+
+/// Bind port 5568 shared with other sACN software on the machine
+fn bind_socket(bind_ip: IpAddr) -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::for_address(SocketAddr::new(bind_ip, 0)), Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    if bind_ip.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    socket.bind(&SocketAddr::new(bind_ip, ACN_SDT_MULTICAST_PORT).into())?;
+    let socket: UdpSocket = socket.into();
+    socket.set_read_timeout(Some(Duration::from_millis(100)))?;
+    Ok(socket)
+}
+
+/// A fresh socket on `interface_ip` with the universe, discovery and sync
+/// groups joined, for when the interface's address changes under us
+fn reopen_socket(mode: &SacnMode, interface_ip: IpAddr, universes: &[u16]) -> std::io::Result<UdpSocket> {
+    let bind_ip = if *mode == SacnMode::Multicast && cfg!(windows) && interface_ip.is_ipv4() {
+        interface_ip
+    } else {
+        unspecified_like(interface_ip)
+    };
+    let socket = bind_socket(bind_ip)?;
+    for (i, &universe) in universes.iter().enumerate() {
+        match join_universe(&socket, universe, interface_ip) {
+            Ok(()) => {}
+            // Only the listened universe is required, as at start
+            Err(e) if i == 0 && *mode == SacnMode::Multicast => return Err(e),
+            Err(e) => eprintln!("Failed to join universe {} on {}: {}", universe, interface_ip, e),
+        }
+    }
+    Ok(socket)
+}
 
 pub struct SacnListener {
     config: SacnConfig,
     running: Arc<Mutex<bool>>,
     thread_handle: Option<std::thread::JoinHandle<()>>,
+    event_handler: Option<EventHandler>,
+    discovered: Discovered,
+    stats: Arc<Mutex<SacnStats>>,
+    active_sources: Arc<Mutex<Vec<ActiveSource>>>,
+    /// Channels checked for conflicting sources; empty checks all 512
+    watched_channels: Vec<u16>,
+    /// Tells the running thread to switch universes
+    universe_change: Option<Sender<u16>>,
 }
 
 impl SacnListener {
@@ -17,9 +711,72 @@ impl SacnListener {
             config,
             running: Arc::new(Mutex::new(false)),
             thread_handle: None,
+            event_handler: None,
+            discovered: Arc::new(Mutex::new(HashMap::new())),
+            stats: Arc::new(Mutex::new(SacnStats::default())),
+            active_sources: Arc::new(Mutex::new(Vec::new())),
+            watched_channels: Vec::new(),
+            universe_change: None,
         }
     }
     
+    /// Sources sending the listened universe as of the last report
+    pub fn active_sources(&self) -> Vec<ActiveSource> {
+        self.active_sources.lock().unwrap().clone()
+    }
+    
+    pub fn stats(&self) -> SacnStats {
+        self.stats.lock().unwrap().clone()
+    }
+    
+    /// Sources that announced themselves on the network within the last two
+    /// discovery intervals, on any universe
+    pub fn discovered_sources(&self) -> Vec<DiscoveredSource> {
+        let discovered = self.discovered.lock().unwrap();
+        let mut sources: Vec<DiscoveredSource> = discovered.iter()
+            .filter(|(_, entry)| entry.last_seen.elapsed() < E131_UNIVERSE_DISCOVERY_INTERVAL * 2)
+            .map(|(cid, entry)| {
+                let mut universes: Vec<u16> = entry.pages.values().flatten().copied().collect();
+                universes.sort_unstable();
+                universes.dedup();
+                DiscoveredSource {
+                    cid: cid.clone(),
+                    name: entry.name.clone(),
+                    ip: entry.ip.clone(),
+                    universes,
+                    secs_since_seen: entry.last_seen.elapsed().as_secs_f64(),
+                }
+            })
+            .collect();
+        sources.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.cid.cmp(&b.cid)));
+        sources
+    }
+    
+    /// Listen to another universe without rebinding the socket. In multicast
+    /// the new group is joined before the old one is left, so there is no
+    /// gap; levels stay as they were until the new universe's data arrives.
+    pub fn set_universe(&mut self, universe: u16) -> Result<(), String> {
+        if !(1..=63999).contains(&universe) {
+            return Err(format!("Invalid universe: {}. Must be 1-63999", universe));
+        }
+        self.config.universe = universe;
+        // A stopped thread has dropped its end; the next start() uses the config
+        if let Some(tx) = &self.universe_change {
+            let _ = tx.send(universe);
+        }
+        Ok(())
+    }
+    
+    /// Only report source conflicts on these channels; set before start()
+    pub fn watch_channels(&mut self, channels: Vec<u16>) {
+        self.watched_channels = channels;
+    }
+    
+    /// Receive source warnings and other listener events; set before start()
+    pub fn on_event(&mut self, handler: impl Fn(ListenerEvent) + Send + Sync + 'static) {
+        self.event_handler = Some(Arc::new(handler));
+    }
+    
     pub fn start(&mut self, callback: impl Fn(DmxUpdate) + Send + 'static) -> Result<(), String> {
         if *self.running.lock().unwrap() {
             return Err("Listener already running".to_string());
@@ -29,29 +786,64 @@ impl SacnListener {
         let mode = self.config.mode.clone();
         let unicast_ip = self.config.unicast_ip.clone();
         let ip_address = self.config.ip_address.clone();
+        let network_interface = self.config.network_interface.clone();
+        let allowed_sources: Vec<IpAddr> = if mode == SacnMode::Unicast {
+            self.config.allowed_sources.iter()
+                .map(|ip| ip.trim().parse().map_err(|e| format!("Invalid allowed source '{}': {}", ip, e)))
+                .collect::<Result<_, String>>()?
+        } else {
+            Vec::new()
+        };
+        let merge_mode = self.config.merge_mode;
+        let on_termination = self.config.on_termination;
         
         println!("=== sACN Listener Starting ===");
         println!("Universe: {}", universe);
         println!("Mode: {:?}", mode);
+        println!("Merge: {:?}", merge_mode);
         println!("Port: {}", ACN_SDT_MULTICAST_PORT);
         
-        // Create and configure receiver based on mode
-        let mut receiver = match mode {
+        // The selected interface is looked up by name so a changed address
+        // still works. An IPv6 address selects IPv6 reception (E1.31 §9.3.2),
+        // otherwise IPv4. Fall back to 0.0.0.0 if none is configured or the
+        // interface is gone.
+        let interface_ip: IpAddr = if !network_interface.is_empty() {
+            match resolve_interface(&network_interface, &ip_address) {
+                Some(ip) => {
+                    if ip.to_string() != ip_address {
+                        println!("Interface {} is now at {} (configured {})", network_interface, ip, ip_address);
+                    }
+                    ip
+                }
+                None => {
+                    let message = format!("Network interface '{}' not found; listening on all interfaces", network_interface);
+                    eprintln!("{}", message);
+                    if let Some(handler) = &self.event_handler {
+                        handler(ListenerEvent::InterfaceError(InterfaceError { interface: network_interface.clone(), message }));
+                    }
+                    IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+                }
+            }
+        } else if !ip_address.is_empty() && ip_address != "0.0.0.0" {
+            ip_address.parse()
+                .map_err(|e| format!("Invalid ip_address '{}': {}", ip_address, e))?
+        } else {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        };
+        
+        // The socket is ours rather than the sacn crate's receiver so each
+        // packet's sender address and source name are visible
+        let socket = match mode {
             SacnMode::Multicast => {
-                // Use the configured IP address for the bind/multicast-join interface.
+                // Use the configured IP address for the multicast-join interface.
                 // Binding to a specific interface IP (e.g. 192.168.0.175) is required on
-                // Windows when there are multiple NICs so the OS joins the right multicast group.
-                // Fall back to 0.0.0.0 if none is configured.
-                let bind_ip: IpAddr = if !ip_address.is_empty() && ip_address != "0.0.0.0" {
-                    ip_address.parse()
-                        .map_err(|e| format!("Invalid ip_address '{}': {}", ip_address, e))?
-                } else {
-                    IpAddr::from([0, 0, 0, 0])
-                };
+                // Windows when there are multiple NICs so the OS joins the right multicast group;
+                // elsewhere binding a unicast address would filter out the multicast traffic.
+                let bind_ip = if cfg!(windows) && interface_ip.is_ipv4() { interface_ip } else { unspecified_like(interface_ip) };
                 let bind_addr = SocketAddr::new(bind_ip, ACN_SDT_MULTICAST_PORT);
                 println!("Multicast: binding to {}", bind_addr);
                 
-                let mut rcv = SacnReceiver::with_ip(bind_addr, None)
+                let socket = bind_socket(bind_ip)
                     .map_err(|e| {
                         let msg = format!("Failed to bind to {}: {}. Is another application using port 5568?", bind_addr, e);
                         eprintln!("{}", msg);
@@ -59,126 +851,401 @@ impl SacnListener {
                     })?;
                 
                 println!("Joining multicast group for universe {}", universe);
-                rcv.listen_universes(&[universe])
+                join_universe(&socket, universe, interface_ip)
                     .map_err(|e| {
                         let msg = format!("Failed to join multicast for universe {}: {}", universe, e);
                         eprintln!("{}", msg);
                         msg
                     })?;
-                println!("Successfully joined multicast group for universe {} on {}", universe, bind_addr);
-                rcv
+                println!("Successfully joined multicast group for universe {} on {}", universe, interface_ip);
+                socket
             }
             SacnMode::Unicast => {
                 if unicast_ip.is_empty() {
                     return Err("Unicast mode requires an IP address".to_string());
                 }
-                // For unicast, bind to 0.0.0.0 so we receive data sent to our IP on any interface.
+                if !allowed_sources.is_empty() {
+                    println!("Accepting unicast only from {:?}", allowed_sources);
+                }
+                // For unicast, bind to 0.0.0.0 (or :: for IPv6) so we receive data sent to our IP on any interface.
                 // The sACN controller just sends a normal UDP packet directly to our IP:5568.
-                let bind_addr = SocketAddr::from(([0, 0, 0, 0], ACN_SDT_MULTICAST_PORT));
+                let bind_addr = SocketAddr::new(unspecified_like(interface_ip), ACN_SDT_MULTICAST_PORT);
                 println!("Unicast: binding to {} (receiving unicast from {})", bind_addr, unicast_ip);
                 
-                let mut rcv = SacnReceiver::with_ip(bind_addr, None)
+                let socket = bind_socket(bind_addr.ip())
                     .map_err(|e| {
                         let msg = format!("Failed to bind to {}: {}. Is another application using port 5568?", bind_addr, e);
                         eprintln!("{}", msg);
                         msg
                     })?;
-                
-                // Disable multicast (we don't need it for unicast) to avoid errors on
-                // machines where multicast isn't available.
-                let _ = rcv.set_is_multicast_enabled(false);
-                
-                // Still need to register the universe so the receiver knows to process it
-                rcv.listen_universes(&[universe])
-                    .map_err(|e| format!("Failed to register universe {}: {}", universe, e))?;
                 println!("Unicast listener ready on port {}", ACN_SDT_MULTICAST_PORT);
-                rcv
+                socket
             }
         };
         
+        // Sources announce every universe they send on the discovery universe
+        match join_universe(&socket, E131_DISCOVERY_UNIVERSE, interface_ip) {
+            Ok(()) => println!("Listening for universe discovery"),
+            Err(e) => eprintln!("Failed to join universe discovery: {}", e),
+        }
+        
         let running = Arc::clone(&self.running);
         *running.lock().unwrap() = true;
         
         let running_clone = Arc::clone(&running);
+        let event_handler = self.event_handler.clone();
+        let discovered = Arc::clone(&self.discovered);
+        let stats = Arc::clone(&self.stats);
+        let active_sources = Arc::clone(&self.active_sources);
+        let (loopback_tx, loopback_rx) = mpsc::channel::<Vec<u8>>();
+        *LOOPBACK.lock().unwrap() = Some(loopback_tx);
+        let (universe_tx, universe_rx) = mpsc::channel::<u16>();
+        self.universe_change = Some(universe_tx);
+        let watched_channels = self.watched_channels.clone();
         
         // Spawn listening thread
         let handle = std::thread::spawn(move || {
             println!("Listener thread started, entering receive loop...");
             let mut packet_count = 0;
             let mut last_log_time = std::time::Instant::now();
+            let mut sources = SourceTracker::default();
+            let mut sync = SyncState::default();
+            let mut levels_sent = LevelCache::default();
+            let mut last_report = Instant::now();
+            let mut reports: u64 = 0;
+            // Started on all interfaces because the named one was missing
+            let mut interface_missing = interface_ip.is_unspecified();
+            let mut interface_ip = interface_ip;
+            let mut socket = socket;
+            let mut arrivals = ArrivalTimes::default();
+            let mut reported_packets: u64 = 0;
+            let mut universe = universe;
+            // CIDs in the last reported conflict, so it is reported once
+            let mut conflict: Vec<String> = Vec::new();
+            let mut buf = [0u8; 1144];
             
             while *running_clone.lock().unwrap() {
-                // CRITICAL FIX: Use timeout instead of blocking forever
-                match receiver.recv(Some(Duration::from_millis(100))) {
-                    Ok(packets) => {
-                        packet_count += packets.len();
-                        
-                        // Log every 5 seconds if no packets, or immediately if we get packets
-                        let now = std::time::Instant::now();
-                        if !packets.is_empty() || now.duration_since(last_log_time).as_secs() >= 5 {
-                            if !packets.is_empty() {
-                                println!("Received {} sACN packet(s). Total so far: {}", packets.len(), packet_count);
+                if let Some(next) = universe_rx.try_iter().last().filter(|next| *next != universe) {
+                    // The sync universe's group may already be the new universe's
+                    let joined = mode != SacnMode::Multicast || sync.joined == Some(next) || match join_universe(&socket, next, interface_ip) {
+                        Ok(()) => true,
+                        Err(e) => {
+                            eprintln!("Failed to join multicast for universe {}: {}; staying on {}", next, e, universe);
+                            false
+                        }
+                    };
+                    if joined {
+                        if mode == SacnMode::Multicast {
+                            if let Err(e) = leave_universe(&socket, universe, interface_ip) {
+                                eprintln!("Failed to leave multicast for universe {}: {}", universe, e);
+                            }
+                        }
+                        println!("Switched from universe {} to {}", universe, next);
+                        if mode == SacnMode::Multicast {
+                            leave_sync_universe(&socket, &sync, &[universe, next], interface_ip);
+                        }
+                        universe = next;
+                        // Sources and held frames belong to the old universe
+                        sources = SourceTracker::default();
+                        sync = SyncState::default();
+                    }
+                }
+                
+                if last_report.elapsed() >= Duration::from_secs(1) {
+                    let elapsed = last_report.elapsed();
+                    let report = sources.report(elapsed);
+                    last_report = Instant::now();
+                    reports += 1;
+                    
+                    let (conflicting, channels) = sources.conflicts(&watched_channels);
+                    let cids: Vec<String> = conflicting.iter().map(|s| s.cid.clone()).collect();
+                    if cids != conflict {
+                        if !conflicting.is_empty() {
+                            let names: Vec<String> = conflicting.iter()
+                                .map(|s| format!("'{}' ({})", s.name, s.ip))
+                                .collect();
+                            let on = if channels.len() > 8 {
+                                format!("{} channels", channels.len())
                             } else {
-                                println!("Still listening... No packets received yet (total: {})", packet_count);
+                                format!("channel(s) {}", channels.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "))
+                            };
+                            let message = format!("{} are sending universe {} at the same priority on {}; the clips may flicker",
+                                names.join(", "), universe, on);
+                            eprintln!("{}", message);
+                            if let Some(handler) = &event_handler {
+                                handler(ListenerEvent::SourceConflict(SourceConflict {
+                                    universe,
+                                    sources: conflicting,
+                                    channels,
+                                    message,
+                                }));
                             }
-                            last_log_time = now;
+                        } else {
+                            println!("Source conflict on universe {} resolved", universe);
                         }
-                        
-                        // Process all received DMX packets
-                        for packet in packets {
-                            println!("Packet received: Universe {} (looking for {}), {} channels", 
-                                packet.universe, universe, packet.values.len());
-                            
-                            if packet.universe == universe {
-                                println!("sACN packet MATCHED on universe {}, {} channels", universe, packet.values.len());
-                                // Only process channels that are actually used (skip trailing zeros)
-                                // Find the highest non-zero channel to avoid processing all 512 channels
-                                // packet.values[0] is the DMX start code; channel N is at index N
-                                let mut max_channel = 0;
-                                for (i, &val) in packet.values.iter().enumerate().skip(1) {
-                                    if val > 0 {
-                                        max_channel = i; // index == channel number
+                        conflict = cids;
+                    }
+                    
+                    // Every 5 s, make sure the interface we joined on is still up
+                    // and follow it to a new address (venue DHCP)
+                    if !network_interface.is_empty() && reports.is_multiple_of(5) {
+                        let wanted = if interface_ip.is_unspecified() { ip_address.clone() } else { interface_ip.to_string() };
+                        match resolve_interface(&network_interface, &wanted) {
+                            Some(ip) if ip != interface_ip => {
+                                let mut universes = vec![universe, E131_DISCOVERY_UNIVERSE];
+                                universes.extend(sync.joined.filter(|joined| *joined != universe));
+                                match reopen_socket(&mode, ip, &universes) {
+                                    Ok(reopened) => {
+                                        let message = format!("Network interface '{}' moved from {} to {}; listening on the new address",
+                                            network_interface, interface_ip, ip);
+                                        println!("{}", message);
+                                        if let Some(handler) = &event_handler {
+                                            handler(ListenerEvent::InterfaceChanged(InterfaceChanged {
+                                                interface: network_interface.clone(),
+                                                old_ip: interface_ip.to_string(),
+                                                new_ip: ip.to_string(),
+                                                message,
+                                            }));
+                                        }
+                                        socket = reopened;
+                                        interface_ip = ip;
+                                        interface_missing = false;
                                     }
+                                    // Tried again on the next check
+                                    Err(e) => eprintln!("Failed to listen on {} ({}): {}", network_interface, ip, e),
                                 }
-                                
-                                // If no channels have data, process at least first 50 channels (to catch zeros)
-                                let channels_to_process = if max_channel == 0 { 50 } else { max_channel.max(50) };
-                                
-                                println!("Processing {} channels (highest non-zero: {})", channels_to_process, max_channel);
-                                
-                                // Process only relevant DMX channels
-                                // Skip index 0 (start code); channel N lives at packet.values[N]
-                                for channel in 1..=channels_to_process {
-                                    let value = packet.values.get(channel).copied().unwrap_or(0);
-                                    let update = DmxUpdate {
-                                        universe,
-                                        channel: channel as u16,
-                                        value,
-                                    };
-                                    callback(update);
+                            }
+                            Some(_) => interface_missing = false,
+                            None => {
+                                if !interface_missing {
+                                    let message = format!("Network interface '{}' ({}) disappeared; sACN may stop arriving until it is back",
+                                        network_interface, interface_ip);
+                                    eprintln!("{}", message);
+                                    if let Some(handler) = &event_handler {
+                                        handler(ListenerEvent::InterfaceError(InterfaceError { interface: network_interface.clone(), message }));
+                                    }
                                 }
-                            } else {
-                                println!("Packet universe {} does NOT match target universe {}, ignoring", 
-                                    packet.universe, universe);
+                                interface_missing = true;
                             }
                         }
                     }
+                    let (max_gap_ms, jitter_ms) = arrivals.take();
+                    let snapshot = {
+                        let mut stats = stats.lock().unwrap();
+                        stats.packets_per_sec = (stats.packets - reported_packets) as f64 / elapsed.as_secs_f64();
+                        reported_packets = stats.packets;
+                        stats.secs_since_last_packet = arrivals.last.map(|at| at.elapsed().as_secs_f64());
+                        stats.max_gap_ms = max_gap_ms;
+                        stats.jitter_ms = jitter_ms;
+                        stats.sources = report.clone();
+                        stats.clone()
+                    };
+                    *active_sources.lock().unwrap() = report.clone();
+                    if let Some(handler) = &event_handler {
+                        handler(ListenerEvent::Sources { sources: report });
+                        handler(ListenerEvent::Stats(snapshot));
+                    }
+                }
+                
+                let expired = sync.expire();
+                if !expired.is_empty() {
+                    println!("No sync packet on universe {} for {:?}, acting on data immediately",
+                        expired[0].sync_address, E131_NETWORK_DATA_LOSS_TIMEOUT);
+                    for frame in expired {
+                        sources.set_levels(&frame.cid, &frame.values);
+                    }
+                    emit_levels(universe, &sources.merged(merge_mode), &mut levels_sent, &callback);
+                }
+                
+                // Injected packets first, so they don't wait on the socket
+                let injected = loopback_rx.try_recv().ok();
+                let loopback = injected.is_some();
+                let received = match injected {
+                    Some(packet) => {
+                        let len = packet.len().min(buf.len());
+                        buf[..len].copy_from_slice(&packet[..len]);
+                        Ok((len, SocketAddr::from((Ipv4Addr::LOCALHOST, ACN_SDT_MULTICAST_PORT))))
+                    }
+                    // Times out every 100 ms so the stop flag is noticed
+                    None => socket.recv_from(&mut buf),
+                };
+                let (len, from) = match received {
+                    Ok(received) => received,
                     Err(e) => {
-                        // Ignore timeout errors, just continue
-                        match e {
-                            sacn::error::errors::SacnError::Io(io_err) => {
-                                if io_err.kind() != std::io::ErrorKind::WouldBlock &&
-                                   io_err.kind() != std::io::ErrorKind::TimedOut {
-                                    eprintln!("sACN IO error: {}", io_err);
-                                }
+                        if e.kind() != std::io::ErrorKind::WouldBlock &&
+                           e.kind() != std::io::ErrorKind::TimedOut {
+                            eprintln!("sACN IO error: {}", e);
+                        }
+                        let now = std::time::Instant::now();
+                        if now.duration_since(last_log_time).as_secs() >= 5 {
+                            println!("Still listening... No packets received yet (total: {})", packet_count);
+                            last_log_time = now;
+                        }
+                        continue;
+                    }
+                };
+                if !loopback && !allowed_sources.is_empty() && !allowed_sources.contains(&from.ip()) {
+                    stats.lock().unwrap().rejected += 1;
+                    continue;
+                }
+                packet_count += 1;
+                last_log_time = std::time::Instant::now();
+                
+                let packet = match AcnRootLayerProtocol::parse(&buf[..len]) {
+                    Ok(packet) => packet,
+                    Err(e) => {
+                        eprintln!("sACN error: ignoring malformed packet from {}: {:?}", from, e);
+                        continue;
+                    }
+                };
+                let cid = packet.pdu.cid.to_string();
+                let data = match packet.pdu.data {
+                    E131RootLayerData::DataPacket(data) => data,
+                    E131RootLayerData::SynchronizationPacket(sync_packet) => {
+                        let address = sync_packet.synchronization_address;
+                        let released = sync.release(address);
+                        if !released.is_empty() {
+                            for frame in released {
+                                sources.set_levels(&frame.cid, &frame.values);
                             }
-                            _ => {
-                                // Log other errors except timeouts
-                                eprintln!("sACN error: {:?}", e);
+                            emit_levels(universe, &sources.merged(merge_mode), &mut levels_sent, &callback);
+                        }
+                        continue;
+                    }
+                    E131RootLayerData::UniverseDiscoveryPacket(discovery) => {
+                        let mut discovered = discovered.lock().unwrap();
+                        let entry = discovered.entry(cid).or_insert_with(|| DiscoveryEntry {
+                            name: String::new(),
+                            ip: String::new(),
+                            pages: HashMap::new(),
+                            last_seen: Instant::now(),
+                        });
+                        let last_page = discovery.data.last_page;
+                        entry.name = discovery.source_name.to_string();
+                        entry.ip = from.ip().to_string();
+                        entry.pages.retain(|page, _| *page <= last_page);
+                        entry.pages.insert(discovery.data.page, discovery.data.universes.to_vec());
+                        entry.last_seen = Instant::now();
+                        continue;
+                    }
+                };
+                
+                println!("Packet received: Universe {} (looking for {}), {} channels", 
+                    data.universe, universe, data.data.property_values.len());
+                if data.universe != universe {
+                    println!("Packet universe {} does NOT match target universe {}, ignoring", 
+                        data.universe, universe);
+                    continue;
+                }
+                // Visualiser-only data
+                if data.preview_data {
+                    continue;
+                }
+                if data.stream_terminated {
+                    // Sent three times; only the first one finds the source
+                    let Some(source) = sources.remove(&cid) else {
+                        continue;
+                    };
+                    sync.pending.retain(|frame| frame.cid != cid);
+                    let remaining = sources.len();
+                    let message = if remaining > 0 {
+                        format!("'{}' ({}) stopped sending universe {}; {} other source(s) still sending",
+                            source.name, source.ip, universe, remaining)
+                    } else if on_termination == TerminationAction::Blackout {
+                        format!("'{}' ({}) stopped sending universe {}; outputs blacked out",
+                            source.name, source.ip, universe)
+                    } else {
+                        format!("'{}' ({}) stopped sending universe {}; holding the last look",
+                            source.name, source.ip, universe)
+                    };
+                    println!("{}", message);
+                    
+                    // Whoever is left takes over the channels it had
+                    if sources.has_levels() {
+                        emit_levels(universe, &sources.merged(merge_mode), &mut levels_sent, &callback);
+                    } else if remaining == 0 && on_termination == TerminationAction::Blackout {
+                        emit_levels(universe, &[0u8; 513], &mut levels_sent, &callback);
+                    }
+                    if let Some(handler) = &event_handler {
+                        handler(ListenerEvent::SourceTerminated(SourceTerminated {
+                            universe,
+                            source,
+                            remaining,
+                            action: on_termination,
+                            message,
+                        }));
+                    }
+                    continue;
+                }
+                
+                let source = SacnSource {
+                    cid: cid.clone(),
+                    name: data.source_name.to_string(),
+                    ip: from.ip().to_string(),
+                    priority: data.priority,
+                };
+                stats.lock().unwrap().packets += 1;
+                arrivals.record();
+                if sources.out_of_sequence(&cid, data.sequence_number) {
+                    stats.lock().unwrap().out_of_sequence += 1;
+                    println!("Dropping out-of-sequence packet from {}", from.ip());
+                    continue;
+                }
+                let previous_sequence = sources.last_sequence(&cid);
+                if let Some(warning) = sources.record(universe, source, data.sequence_number) {
+                    println!("sACN source warning: {}", warning.message);
+                    if let Some(handler) = &event_handler {
+                        handler(ListenerEvent::SourceWarning(warning));
+                    }
+                }
+                // A jump of more than one means packets in between never came;
+                // a big jump back is a restarted source, not a gap
+                if let Some(previous) = previous_sequence {
+                    let step = data.sequence_number.wrapping_sub(previous) as i8;
+                    if step > 1 {
+                        stats.lock().unwrap().sequence_gaps += (step - 1) as u64;
+                    }
+                }
+                
+                let values = &data.data.property_values;
+                match values.first().copied() {
+                    Some(START_CODE_LEVELS) => {}
+                    Some(START_CODE_PER_ADDRESS_PRIORITY) => {
+                        sources.set_priorities(&cid, values);
+                        if sources.has_levels() {
+                            emit_levels(universe, &sources.merged(merge_mode), &mut levels_sent, &callback);
+                        }
+                        continue;
+                    }
+                    // Text, system information and other alternate start codes
+                    _ => continue,
+                }
+                println!("sACN packet MATCHED on universe {}, {} channels", universe, values.len());
+                
+                // Synchronized data is held until its sync packet (E1.31 §6.2.4)
+                let sync_address = data.synchronization_address;
+                if sync_address != 0 && !sync.lost {
+                    if mode == SacnMode::Multicast && sync.joined != Some(sync_address) {
+                        leave_sync_universe(&socket, &sync, &[universe], interface_ip);
+                        // Sync on the data universe needs no group of its own
+                        if sync_address != universe {
+                            match join_universe(&socket, sync_address, interface_ip) {
+                                Ok(()) => println!("Joined sync universe {}", sync_address),
+                                Err(e) => eprintln!("Failed to join sync universe {}: {}", sync_address, e),
                             }
                         }
+                        sync.joined = Some(sync_address);
                     }
+                    sync.hold(PendingFrame {
+                        cid,
+                        sync_address,
+                        values: values.to_vec(),
+                        received: Instant::now(),
+                    });
+                    continue;
                 }
+                sources.set_levels(&cid, values);
+                emit_levels(universe, &sources.merged(merge_mode), &mut levels_sent, &callback);
             }
         });
         
@@ -188,6 +1255,10 @@ impl SacnListener {
         Ok(())
     }
     
+    pub fn is_running(&self) -> bool {
+        *self.running.lock().unwrap()
+    }
+    
     /// Signal the listener thread to stop, then wait for it to exit so the
     /// socket is fully released before the caller proceeds (e.g. to rebind).
     /// Do NOT call this while holding any mutex the listener thread also needs.
@@ -213,3 +1284,93 @@ impl Drop for SacnListener {
         self.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A full universe (start code at index 0) with the given channels set
+    fn frame(start_code: u8, channels: &[(usize, u8)]) -> Vec<u8> {
+        let mut values = vec![0u8; 513];
+        values[0] = start_code;
+        for &(channel, value) in channels {
+            values[channel] = value;
+        }
+        values
+    }
+
+    /// Record a source and its levels
+    fn send(tracker: &mut SourceTracker, cid: &str, priority: u8, channels: &[(usize, u8)]) {
+        let info = SacnSource {
+            cid: cid.to_string(),
+            name: cid.to_string(),
+            ip: "10.0.0.1".to_string(),
+            priority,
+        };
+        let sequence = tracker.last_sequence(cid).map_or(0, |s| s.wrapping_add(1));
+        tracker.record(1, info, sequence);
+        tracker.set_levels(cid, &frame(START_CODE_LEVELS, channels));
+    }
+
+    #[test]
+    fn equal_priorities_merge_per_channel() {
+        let mut tracker = SourceTracker::default();
+        send(&mut tracker, "a", 100, &[(1, 100), (2, 10)]);
+        std::thread::sleep(Duration::from_millis(5));
+        send(&mut tracker, "b", 100, &[(1, 50), (2, 200)]);
+        std::thread::sleep(Duration::from_millis(5));
+        // A's latest packet only changes channel 2
+        send(&mut tracker, "a", 100, &[(1, 100), (2, 20)]);
+
+        let cases = [
+            (MergeMode::Htp, 1, 100),
+            (MergeMode::Htp, 2, 200),
+            (MergeMode::Ltp, 1, 50),
+            (MergeMode::Ltp, 2, 20),
+            (MergeMode::Ltp, 3, 0),
+        ];
+        for (mode, channel, expected) in cases {
+            assert_eq!(tracker.merged(mode)[channel], expected, "{:?} channel {}", mode, channel);
+        }
+    }
+
+    #[test]
+    fn per_address_priority_overrides_packet_priority() {
+        // (priority, 0xDD priority for channel 1) of sources A (level 50)
+        // and B (level 200), and the level channel 1 ends up at
+        let cases = [
+            ((100, None), (150, None), 200),
+            ((100, Some(200)), (150, None), 50),
+            ((100, None), (150, Some(0)), 50),
+            ((200, Some(50)), (100, None), 200),
+            ((100, Some(0)), (100, Some(0)), 0),
+        ];
+        for (i, ((a_priority, a_address), (b_priority, b_address), expected)) in cases.into_iter().enumerate() {
+            let mut tracker = SourceTracker::default();
+            send(&mut tracker, "a", a_priority, &[(1, 50)]);
+            send(&mut tracker, "b", b_priority, &[(1, 200)]);
+            if let Some(priority) = a_address {
+                tracker.set_priorities("a", &frame(START_CODE_PER_ADDRESS_PRIORITY, &[(1, priority)]));
+            }
+            if let Some(priority) = b_address {
+                tracker.set_priorities("b", &frame(START_CODE_PER_ADDRESS_PRIORITY, &[(1, priority)]));
+            }
+            assert_eq!(tracker.merged(MergeMode::Htp)[1], expected, "case {}", i);
+        }
+    }
+
+    #[test]
+    fn per_address_priority_expires_without_0xdd_packets() {
+        let mut tracker = SourceTracker::default();
+        send(&mut tracker, "a", 100, &[(1, 50)]);
+        send(&mut tracker, "b", 150, &[(1, 200)]);
+        tracker.set_priorities("a", &frame(START_CODE_PER_ADDRESS_PRIORITY, &[(1, 200)]));
+        assert_eq!(tracker.merged(MergeMode::Htp)[1], 50);
+
+        // Levels keep coming but the 0xDD packets stopped
+        tracker.sources.get_mut("a").unwrap().priorities_updated =
+            Instant::now() - E131_NETWORK_DATA_LOSS_TIMEOUT - Duration::from_millis(100);
+        send(&mut tracker, "a", 100, &[(1, 50)]);
+        assert_eq!(tracker.merged(MergeMode::Htp)[1], 200);
+    }
+}
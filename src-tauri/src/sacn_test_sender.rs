@@ -1,25 +1,116 @@
+use crate::sacn_listener;
+use sacn::packet::{
+    AcnRootLayerProtocol, DataPacketDmpLayer, DataPacketFramingLayer, E131RootLayer, E131RootLayerData,
+    ACN_SDT_MULTICAST_PORT, E131_DEFAULT_PRIORITY, E131_MAX_PRIORITY,
+};
 use sacn::source::SacnSource;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 
+/// How a test sender sends. The default multicasts at priority 100 from
+/// whichever interface the OS picks.
+#[derive(Debug, Clone, Default)]
+pub struct TestSenderOptions {
+    pub priority: Option<u8>,
+    /// Packets carry the preview flag, which receivers must not show
+    pub preview: bool,
+    /// Unicast to this machine instead of multicast
+    pub destination: Option<IpAddr>,
+    /// Hand packets to the running listener in-process instead of the
+    /// network, for venues that block multicast loopback
+    pub loopback: bool,
+    /// Local address to send from, so a multi-homed machine sends on the
+    /// show network's NIC
+    pub interface_ip: Option<IpAddr>,
+    /// Routers the packets may cross (1-255); the OS default keeps
+    /// multicast on the local subnet
+    pub ttl: Option<u32>,
+}
+
 /// Test sACN sender for loopback testing
 pub struct SacnTestSender {
     source: SacnSource,
     universe: u16,
+    /// E1.31 priority of every packet sent
+    priority: u8,
+    /// Unicast target; None multicasts to the universe's group
+    destination: Option<SocketAddr>,
+    /// Hand packets straight to the running listener instead of the network
+    loopback: bool,
+    /// Sequence number of the last packet built for loopback
+    sequence: u8,
 }
 
 impl SacnTestSender {
     /// Create a new test sender
-    pub fn new(universe: u16, source_name: &str) -> Result<Self, String> {
-        let source = SacnSource::with_ip(source_name, SocketAddr::from(([0, 0, 0, 0], 0)))
-            .map_err(|e| format!("Failed to create sACN source: {}", e))?;
-        
-        println!("Created test sACN sender for universe {}", universe);
+    pub fn new(universe: u16, source_name: &str, options: TestSenderOptions) -> Result<Self, String> {
+        let TestSenderOptions { priority, preview, destination, loopback, interface_ip, ttl } = options;
+        // Binding the interface's address also makes multicast leave through it
+        let bind_ip = match interface_ip {
+            Some(IpAddr::V6(ip)) => {
+                return Err(format!("Invalid interface: {}. The test sender only sends over IPv4", ip));
+            }
+            Some(ip) => ip,
+            None => IpAddr::from([0, 0, 0, 0]),
+        };
+        let mut source = SacnSource::with_ip(source_name, SocketAddr::new(bind_ip, 0))
+            .map_err(|e| format!("Failed to create sACN source on {}: {}", bind_ip, e))?;
+        if let Some(ttl) = ttl {
+            if !(1..=255).contains(&ttl) {
+                return Err(format!("Invalid TTL: {}. Must be 1-255", ttl));
+            }
+            source.set_multicast_ttl(ttl)
+                .and_then(|_| source.set_ttl(ttl))
+                .map_err(|e| format!("Failed to set TTL: {}", e))?;
+        }
         
-        Ok(Self {
+        let mut sender = Self {
             source,
             universe,
-        })
+            priority: E131_DEFAULT_PRIORITY,
+            destination: None,
+            loopback,
+            sequence: 0,
+        };
+        sender.set_priority(priority.unwrap_or(E131_DEFAULT_PRIORITY))?;
+        sender.set_preview(preview)?;
+        sender.set_destination(destination)?;
+        
+        let target = match destination {
+            _ if loopback => "the listener in-process".to_string(),
+            Some(ip) => ip.to_string(),
+            None => "multicast".to_string(),
+        };
+        println!("Created test sACN sender for universe {} at priority {}{}, sending to {} from {}{}",
+            universe, sender.priority, if preview { " (preview)" } else { "" }, target, bind_ip,
+            ttl.map(|ttl| format!(" with TTL {}", ttl)).unwrap_or_default());
+        
+        Ok(sender)
+    }
+    
+    /// Priority for the following packets (0-200)
+    pub fn set_priority(&mut self, priority: u8) -> Result<(), String> {
+        if priority > E131_MAX_PRIORITY {
+            return Err(format!("Invalid priority: {}. Must be 0-{}", priority, E131_MAX_PRIORITY));
+        }
+        self.priority = priority;
+        Ok(())
+    }
+    
+    /// Unicast the following packets to `destination`, or multicast with None
+    pub fn set_destination(&mut self, destination: Option<IpAddr>) -> Result<(), String> {
+        // The source's socket is IPv4
+        if let Some(IpAddr::V6(ip)) = destination {
+            return Err(format!("Invalid destination: {}. The test sender only sends over IPv4", ip));
+        }
+        self.destination = destination.map(|ip| SocketAddr::new(ip, ACN_SDT_MULTICAST_PORT));
+        Ok(())
+    }
+    
+    /// Set or clear the preview flag on the following packets
+    pub fn set_preview(&mut self, preview: bool) -> Result<(), String> {
+        self.source.set_preview_mode(preview)
+            .map_err(|e| format!("Failed to set preview mode: {}", e))
     }
     
     /// Send test DMX data on specific channels
@@ -40,16 +131,68 @@ impl SacnTestSender {
             }
         }
         
+        self.send(&dmx_data)?;
+        println!("Sent test DMX data to universe {}", self.universe);
+        Ok(())
+    }
+    
+    /// Send a whole frame at once: `values[0]` is channel 1. Channels past
+    /// the end of `values` are sent as 0.
+    pub fn send_frame(&mut self, values: &[u8]) -> Result<(), String> {
+        if values.len() > 512 {
+            return Err(format!("Too many channel values: {}. A universe has 512", values.len()));
+        }
+        let mut dmx_data = [0u8; 513];
+        dmx_data[1..=values.len()].copy_from_slice(values);
+        self.send(&dmx_data)
+    }
+    
+    /// Send start code and channels as one packet
+    fn send(&mut self, dmx_data: &[u8; 513]) -> Result<(), String> {
+        if self.loopback {
+            return self.inject(dmx_data, false);
+        }
+        
         // Register universe if not already registered
         self.source.register_universe(self.universe)
             .map_err(|e| format!("Failed to register universe: {}", e))?;
         
         // Send the data - sacn 0.11 API: send(universes, data, priority, dst_ip, sync_uni)
-        self.source.send(&[self.universe], &dmx_data, None, None, None)
-            .map_err(|e| format!("Failed to send DMX data: {}", e))?;
+        self.source.send(&[self.universe], dmx_data, Some(self.priority), self.destination, None)
+            .map_err(|e| format!("Failed to send DMX data: {}", e))
+    }
+    
+    /// Build the packet the network path would send and give it to the listener
+    fn inject(&mut self, property_values: &[u8], stream_terminated: bool) -> Result<(), String> {
+        let cid = self.source.cid()
+            .map_err(|e| format!("Failed to read source CID: {}", e))?;
+        let name = self.source.name()
+            .map_err(|e| format!("Failed to read source name: {}", e))?;
+        let preview_data = self.source.preview_mode()
+            .map_err(|e| format!("Failed to read preview mode: {}", e))?;
+        self.sequence = self.sequence.wrapping_add(1);
         
-        println!("Sent test DMX data to universe {}", self.universe);
-        Ok(())
+        let packet = AcnRootLayerProtocol {
+            pdu: E131RootLayer {
+                cid,
+                data: E131RootLayerData::DataPacket(DataPacketFramingLayer {
+                    source_name: name.as_str().into(),
+                    priority: self.priority,
+                    synchronization_address: 0,
+                    sequence_number: self.sequence,
+                    preview_data,
+                    stream_terminated,
+                    force_synchronization: false,
+                    universe: self.universe,
+                    data: DataPacketDmpLayer {
+                        property_values: property_values.into(),
+                    },
+                }),
+            },
+        };
+        let bytes = packet.pack_alloc()
+            .map_err(|e| format!("Failed to build sACN packet: {}", e))?;
+        sacn_listener::inject_loopback(bytes)
     }
     
     /// Send a sequence of test values to simulate channel changes
@@ -93,7 +236,11 @@ impl SacnTestSender {
 impl Drop for SacnTestSender {
     fn drop(&mut self) {
         // Cleanup: terminate universe with start code 0 (DMX)
-        let _ = self.source.terminate_stream(self.universe, 0);
+        if self.loopback {
+            let _ = self.inject(&[0], true);
+        } else {
+            let _ = self.source.terminate_stream(self.universe, 0);
+        }
         println!("Test sACN sender terminated for universe {}", self.universe);
     }
 }
@@ -0,0 +1,191 @@
+use crate::config::{AppConfig, CueLook};
+use crate::macros;
+use crate::sacn_listener::DmxFrame;
+use crate::trigger_output;
+use rhai::{Array, Dynamic, Engine, Scope, AST};
+use std::collections::HashMap;
+use std::net::UdpSocket;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::AppHandle;
+
+/// Upper bound on work per hook call, so a runaway loop can't stall the show
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Spawn the script host.
+///
+/// While scripting is enabled the script is compiled (and recompiled when
+/// the file changes) and its top level run once. While a listener is
+/// running these functions are called if the script defines them:
+/// `on_dmx(channel, value)` for every channel change,
+/// `on_clip_change(monitor_id, clip)` when a monitor's clip channel changes,
+/// `on_signal_loss()` / `on_signal_restored()` around gaps in sACN.
+///
+/// Scripts can call `select_clip(monitor_id, clip)`,
+/// `set_dimmer(monitor_id, dimmer)`, `send_osc(target, address, [ints])`
+/// and `send_udp(target, text)`; there is no file or process access.
+pub fn spawn_script_host(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        // Looks the script has set; the console takes over again when it
+        // changes a monitor's channels
+        let looks: Arc<Mutex<HashMap<String, CueLook>>> = Arc::new(Mutex::new(HashMap::new()));
+        let engine = build_engine(&app_handle, &config, &dmx_frame, &looks);
+        let mut scope = Scope::new();
+        let mut loaded: Option<(String, Option<SystemTime>)> = None;
+        let mut ast: Option<AST> = None;
+
+        let mut previous: Option<[u8; 513]> = None;
+        let mut signal_lost = false;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(25));
+
+            let cfg = config.lock().unwrap().clone();
+            if !cfg.scripting.enabled || cfg.scripting.script_path.is_empty() {
+                loaded = None;
+                ast = None;
+                previous = None;
+                continue;
+            }
+
+            // (Re)load when the path or the file's modification time changes
+            let modified = std::fs::metadata(&cfg.scripting.script_path)
+                .and_then(|m| m.modified())
+                .ok();
+            let current = (cfg.scripting.script_path.clone(), modified);
+            if loaded.as_ref() != Some(&current) {
+                loaded = Some(current);
+                scope = Scope::new();
+                looks.lock().unwrap().clear();
+                ast = load_script(&engine, &mut scope, Path::new(&cfg.scripting.script_path));
+            }
+            let Some(script) = ast.as_ref() else {
+                continue;
+            };
+
+            let (values, last_received, listening_since) = {
+                let frame = dmx_frame.lock().unwrap();
+                (frame.values, frame.last_received, frame.listening_since)
+            };
+            let Some(since) = listening_since else {
+                previous = None;
+                signal_lost = false;
+                continue;
+            };
+
+            let last_activity = last_received.filter(|t| *t >= since).unwrap_or(since);
+            let lost = Instant::now().duration_since(last_activity)
+                >= Duration::from_secs(cfg.scripting.signal_loss_secs as u64);
+            if lost != signal_lost {
+                signal_lost = lost;
+                let hook = if lost { "on_signal_loss" } else { "on_signal_restored" };
+                call_hook(&engine, &mut scope, script, hook, ());
+            }
+
+            // The first frame after (re)starting is the baseline, not a change
+            let Some(before) = previous.replace(values) else {
+                continue;
+            };
+            for (channel, (value, old)) in values.iter().zip(before.iter()).enumerate().skip(1) {
+                if value != old {
+                    call_hook(&engine, &mut scope, script, "on_dmx", (channel as i64, *value as i64));
+                }
+            }
+            for (monitor_id, monitor) in [("monitor1", &cfg.monitor1), ("monitor2", &cfg.monitor2)] {
+                let channel = monitor.clip_channel() as usize;
+                if monitor.enabled && channel < values.len() && values[channel] != before[channel] {
+                    call_hook(&engine, &mut scope, script, "on_clip_change", (monitor_id.to_string(), values[channel] as i64));
+                }
+            }
+        }
+    });
+}
+
+fn build_engine(
+    app_handle: &AppHandle,
+    config: &Arc<Mutex<AppConfig>>,
+    dmx_frame: &Arc<Mutex<DmxFrame>>,
+    looks: &Arc<Mutex<HashMap<String, CueLook>>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(1024);
+    engine.on_print(|text| println!("[script] {}", text));
+    engine.on_debug(|text, _, _| println!("[script] {}", text));
+
+    let (app, cfg, frame, script_looks) = (app_handle.clone(), Arc::clone(config), Arc::clone(dmx_frame), Arc::clone(looks));
+    engine.register_fn("select_clip", move |monitor_id: &str, clip: i64| {
+        let clip = clip.clamp(0, u16::MAX as i64) as u16;
+        let mut looks = script_looks.lock().unwrap();
+        if let Err(e) = macros::change_look(&app, &cfg, &frame, &mut looks, monitor_id, |look| look.clip = clip) {
+            eprintln!("Script select_clip: {}", e);
+        }
+    });
+
+    let (app, cfg, frame, script_looks) = (app_handle.clone(), Arc::clone(config), Arc::clone(dmx_frame), Arc::clone(looks));
+    engine.register_fn("set_dimmer", move |monitor_id: &str, dimmer: i64| {
+        let dimmer = dimmer.clamp(0, 255) as u8;
+        let mut looks = script_looks.lock().unwrap();
+        if let Err(e) = macros::change_look(&app, &cfg, &frame, &mut looks, monitor_id, |look| look.dimmer = dimmer) {
+            eprintln!("Script set_dimmer: {}", e);
+        }
+    });
+
+    engine.register_fn("send_osc", |target: &str, address: &str, args: Array| {
+        let args: Vec<i32> = args.iter()
+            .map(|arg| arg.as_int().unwrap_or(0) as i32)
+            .collect();
+        if let Err(e) = trigger_output::send_osc(target, address, &args) {
+            eprintln!("Script send_osc: {}", e);
+        }
+    });
+
+    engine.register_fn("send_udp", |target: &str, text: &str| {
+        let sent = UdpSocket::bind("0.0.0.0:0").and_then(|socket| socket.send_to(text.as_bytes(), target));
+        if let Err(e) = sent {
+            eprintln!("Script send_udp to {}: {}", target, e);
+        }
+    });
+
+    engine
+}
+
+/// Compile the script and run its top level; None if it doesn't load
+fn load_script(engine: &Engine, scope: &mut Scope, path: &Path) -> Option<AST> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to read script {:?}: {}", path, e);
+            return None;
+        }
+    };
+    let ast = match engine.compile(&source) {
+        Ok(ast) => ast,
+        Err(e) => {
+            eprintln!("Script {:?} does not compile: {}", path, e);
+            return None;
+        }
+    };
+    if let Err(e) = engine.run_ast_with_scope(scope, &ast) {
+        eprintln!("Script {:?} failed at startup: {}", path, e);
+    }
+    println!("Loaded script {:?}", path);
+    Some(ast)
+}
+
+/// Call a hook if the script defines it
+fn call_hook(engine: &Engine, scope: &mut Scope, ast: &AST, name: &str, args: impl rhai::FuncArgs) {
+    if !ast.iter_functions().any(|f| f.name == name) {
+        return;
+    }
+    if let Err(e) = engine.call_fn::<Dynamic>(scope, ast, name, args) {
+        eprintln!("Script {}: {}", name, e);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::config::{AppConfig, DmxUpdate, MonitorConfig, SacnMode};
+use crate::media_scanner;
+use crate::sacn_listener::SacnListener;
+use crate::sacn_test_sender::{SacnTestSender, TestSenderOptions};
+use serde::Serialize;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a sent frame may take to show up at the listener
+const RECEIVE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Dimmer levels sent in turn, so every round is a fresh frame
+const DIMMER_SEQUENCE: [u8; 3] = [85, 170, 255];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestStep {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+}
+
+impl SelfTestReport {
+    fn step(&mut self, name: impl Into<String>, result: Result<String, String>) -> bool {
+        let (passed, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        let name = name.into();
+        println!("Self-test {}: {} ({})", if passed { "PASS" } else { "FAIL" }, name, detail);
+        self.steps.push(SelfTestStep { name, passed, detail });
+        passed
+    }
+}
+
+/// Channels driven for one monitor and the clip it should end up showing
+struct MonitorPlan<'a> {
+    monitor_id: &'static str,
+    monitor: &'a MonitorConfig,
+    clip: Option<(u8, String)>,
+}
+
+/// Loop the configured universe back through a temporary listener: send a
+/// short sequence with the test sender, check every frame arrives intact,
+/// then resolve each monitor's clip channel to a file in its media folder.
+/// The caller must make sure the regular listener isn't holding the port.
+pub fn run(config: &AppConfig) -> SelfTestReport {
+    let mut report = SelfTestReport { passed: false, steps: Vec::new() };
+    let universe = config.sacn.universe;
+
+    let plans: Vec<MonitorPlan> = [("monitor1", &config.monitor1), ("monitor2", &config.monitor2)]
+        .into_iter()
+        .filter(|(_, monitor)| monitor.enabled)
+        .map(|(monitor_id, monitor)| MonitorPlan {
+            monitor_id,
+            monitor,
+            clip: first_clip(monitor),
+        })
+        .collect();
+    if plans.is_empty() {
+        report.step("Monitors", Err("No monitor is enabled".to_string()));
+        return report;
+    }
+
+    let received = Arc::new(Mutex::new([0u8; 513]));
+    let received_cb = Arc::clone(&received);
+    let mut listener = SacnListener::new(config.sacn.clone());
+    let started = listener.start(move |update: DmxUpdate| {
+        let mut received = received_cb.lock().unwrap();
+        for (channel, value) in update.channels {
+            if let Some(slot) = received.get_mut(channel as usize) {
+                *slot = value;
+            }
+        }
+    });
+    let listening = report.step(
+        "Start listener",
+        started.map(|_| format!("Universe {} ({:?})", universe, config.sacn.mode)),
+    );
+    if !listening {
+        return report;
+    }
+
+    let mut sender = match SacnTestSender::new(universe, "StagePlayer DMX Self-Test", TestSenderOptions {
+        // Send on the interface the listener joined, not whichever NIC the OS picks
+        interface_ip: config.sacn.ip_address.parse().ok().filter(|ip: &IpAddr| ip.is_ipv4() && !ip.is_unspecified()),
+        ..Default::default()
+    }) {
+        Ok(sender) => {
+            report.step("Start test sender", Ok(format!("Universe {}", universe)));
+            sender
+        }
+        Err(e) => {
+            report.step("Start test sender", Err(e));
+            return report;
+        }
+    };
+
+    let mut loopback_ok = true;
+    for (round, dimmer) in DIMMER_SEQUENCE.iter().enumerate() {
+        let channels: Vec<(u16, u8)> = plans.iter()
+            .flat_map(|plan| {
+                let clip = plan.clip.as_ref().map(|(value, _)| *value).unwrap_or(1);
+                [
+                    (plan.monitor.clip_channel(), clip),
+                    (plan.monitor.dimmer_channel(), *dimmer),
+                    (plan.monitor.playtype_channel(), 0),
+                ]
+            })
+            .collect();
+
+        let result = sender.send_test_data(channels.clone())
+            .and_then(|_| wait_for(&received, &channels))
+            .map(|elapsed| format!("{} channels in {} ms", channels.len(), elapsed.as_millis()));
+        let result = match (result, &config.sacn.mode) {
+            (Err(e), SacnMode::Unicast) => Err(format!("{}; the test sender uses multicast, which a unicast listener doesn't receive", e)),
+            (result, _) => result,
+        };
+        if !report.step(format!("Loopback frame {}", round + 1), result) {
+            loopback_ok = false;
+            break;
+        }
+    }
+
+    if loopback_ok {
+        let values = *received.lock().unwrap();
+        for plan in &plans {
+            let name = format!("Route {} ({})", plan.monitor.name, plan.monitor_id);
+            let result = match &plan.clip {
+                None => Err(format!("No playable clips in {}", plan.monitor.media_folder.display())),
+                Some((value, expected)) => {
+                    let channel_value = values.get(plan.monitor.clip_channel() as usize).copied().unwrap_or(0);
+                    media_scanner::scan_media_folder(&plan.monitor.media_folder)
+                        .map_err(|e| format!("Failed to read media folder: {}", e))
+                        .and_then(|media| match media.get(&channel_value) {
+                            Some(file) if &file.filename == expected => {
+                                Ok(format!("Clip {:03} → {}", value, file.filename))
+                            }
+                            Some(file) => Err(format!("Clip {:03} resolved to {}, expected {}", channel_value, file.filename, expected)),
+                            None => Err(format!("Clip {:03} resolved to no file", channel_value)),
+                        })
+                }
+            };
+            report.step(name, result);
+        }
+    }
+
+    // Leave the universe dark rather than on the test look
+    let _ = sender.send_test_data(Vec::new());
+    drop(sender);
+    listener.stop();
+
+    report.passed = report.steps.iter().all(|s| s.passed);
+    report
+}
+
+/// Lowest numbered playable clip in the monitor's folder
+fn first_clip(monitor: &MonitorConfig) -> Option<(u8, String)> {
+    let media = media_scanner::scan_media_folder(&monitor.media_folder).ok()?;
+    media.into_iter()
+        .filter(|(value, _)| *value < 255)
+        .min_by_key(|(value, _)| *value)
+        .map(|(value, file)| (value, file.filename))
+}
+
+/// Wait until every channel reads back the value sent
+fn wait_for(received: &Mutex<[u8; 513]>, channels: &[(u16, u8)]) -> Result<Duration, String> {
+    let start = Instant::now();
+    loop {
+        let mismatch = {
+            let values = received.lock().unwrap();
+            channels.iter()
+                .map(|(channel, value)| (*channel, *value, values.get(*channel as usize).copied().unwrap_or(0)))
+                .find(|(_, sent, got)| sent != got)
+        };
+        let Some((channel, sent, got)) = mismatch else {
+            return Ok(start.elapsed());
+        };
+        if start.elapsed() >= RECEIVE_TIMEOUT {
+            return Err(format!("Channel {} sent {} but received {}", channel, sent, got));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::config::{AppConfig, SacnMode};
+use crate::cue_stack::CueState;
+use crate::playback::MonitorPlayback;
+use crate::sacn_listener::DmxFrame;
+use crate::timeline::TimelineState;
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Listener state at the moment of the snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct ListenerSnapshot {
+    pub listening: bool,
+    pub universe: u16,
+    pub mode: SacnMode,
+    /// Seconds since the last packet, None if nothing arrived since starting
+    pub secs_since_last_packet: Option<f64>,
+}
+
+/// Everything the player was doing at one moment, for support and show reports
+#[derive(Debug, Clone, Serialize)]
+pub struct StateSnapshot {
+    /// Unix time (seconds) the snapshot was taken
+    pub taken_at: u64,
+    pub version: String,
+    pub monitors: Vec<MonitorPlayback>,
+    pub listener: ListenerSnapshot,
+    /// Last received levels, channel 1 first
+    pub dmx: Vec<u8>,
+    pub cue: CueState,
+    pub timeline: TimelineState,
+}
+
+pub fn collect(
+    config: &AppConfig,
+    frame: &DmxFrame,
+    monitors: Vec<MonitorPlayback>,
+    cue: CueState,
+    timeline: TimelineState,
+) -> StateSnapshot {
+    let secs_since_last_packet = match (frame.listening_since, frame.last_received) {
+        (Some(since), Some(last)) if last >= since => Some(last.elapsed().as_secs_f64()),
+        _ => None,
+    };
+
+    StateSnapshot {
+        taken_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        monitors,
+        listener: ListenerSnapshot {
+            listening: frame.listening_since.is_some(),
+            universe: config.sacn.universe,
+            mode: config.sacn.mode.clone(),
+            secs_since_last_packet,
+        },
+        dmx: frame.values[1..].to_vec(),
+        cue,
+        timeline,
+    }
+}
@@ -0,0 +1,377 @@
+#[cfg(windows)]
+use windows::core::{Interface, PCSTR};
+#[cfg(windows)]
+use windows::Win32::Foundation::{CloseHandle, HANDLE, HMODULE, INVALID_HANDLE_VALUE, WAIT_ABANDONED, WAIT_OBJECT_0};
+#[cfg(windows)]
+use windows::Win32::Graphics::Direct3D::{D3D_DRIVER_TYPE_HARDWARE, D3D_FEATURE_LEVEL_11_0};
+#[cfg(windows)]
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_BIND_RENDER_TARGET,
+    D3D11_BIND_SHADER_RESOURCE, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_RESOURCE_MISC_SHARED,
+    D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT,
+};
+#[cfg(windows)]
+use windows::Win32::Graphics::Dxgi::Common::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_SAMPLE_DESC};
+#[cfg(windows)]
+use windows::Win32::Graphics::Dxgi::IDXGIResource;
+#[cfg(windows)]
+use windows::Win32::System::Memory::{
+    CreateFileMappingA, MapViewOfFile, UnmapViewOfFile, FILE_MAP_ALL_ACCESS, MEMORY_MAPPED_VIEW_ADDRESS, PAGE_READWRITE,
+};
+#[cfg(windows)]
+use windows::Win32::System::Threading::{CreateMutexA, ReleaseMutex, WaitForSingleObject};
+
+/// Name Spout receivers list for a monitor when its config leaves it empty
+pub fn default_sender_name(monitor_name: &str) -> String {
+    format!("StagePlayer DMX {}", monitor_name)
+}
+
+/// A Spout sender sharing one monitor's picture as a D3D11 texture, so
+/// Resolume, TouchDesigner and other Spout receivers on the same machine can
+/// take it without screen-grabbing. Speaks the Spout 2 protocol directly:
+/// the texture's share handle is published in the shared memory Spout
+/// receivers read, and the sender is listed among "SpoutSenderNames".
+#[cfg(windows)]
+pub struct SpoutSender {
+    name: String,
+    device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    texture: Option<(ID3D11Texture2D, u32, u32)>,
+    /// Sender info (share handle, size, format) receivers look the name up in
+    info: SharedMemory,
+    /// Held while the texture is written, as Spout receivers hold it to read
+    access_mutex: HANDLE,
+}
+
+/// Longest sender name, with its terminating zero
+#[cfg(windows)]
+const NAME_LEN: usize = 256;
+
+/// Slots in "SpoutSenderNames"; the Spout 2.007 default
+#[cfg(windows)]
+const MAX_SENDERS: usize = 64;
+
+/// How long Spout waits for its locks before giving up on a frame
+#[cfg(windows)]
+const LOCK_TIMEOUT_MS: u32 = 67;
+
+/// Spout's SharedTextureInfo, the sender's entry receivers read
+#[cfg(windows)]
+#[repr(C)]
+struct SharedTextureInfo {
+    share_handle: u32,
+    width: u32,
+    height: u32,
+    format: u32,
+    usage: u32,
+    description: [u8; 256],
+    partner_id: u32,
+}
+
+/// A named shared memory block with the "<name>_mutex" lock Spout uses for it
+#[cfg(windows)]
+struct SharedMemory {
+    mapping: HANDLE,
+    view: MEMORY_MAPPED_VIEW_ADDRESS,
+    mutex: HANDLE,
+    size: usize,
+}
+
+#[cfg(windows)]
+impl SharedMemory {
+    /// Create the block, or open it if another Spout app already has
+    fn open(name: &str, size: usize) -> Result<Self, String> {
+        let map_name = c_name(name)?;
+        let mutex_name = c_name(&format!("{}_mutex", name))?;
+        unsafe {
+            let mapping = CreateFileMappingA(
+                INVALID_HANDLE_VALUE, None, PAGE_READWRITE, 0, size as u32, PCSTR(map_name.as_ptr().cast()),
+            ).map_err(|e| format!("Failed to create shared memory {}: {}", name, e))?;
+            let view = MapViewOfFile(mapping, FILE_MAP_ALL_ACCESS, 0, 0, size);
+            if view.Value.is_null() {
+                let _ = CloseHandle(mapping);
+                return Err(format!("Failed to map shared memory {}", name));
+            }
+            let mutex = match CreateMutexA(None, false, PCSTR(mutex_name.as_ptr().cast())) {
+                Ok(mutex) => mutex,
+                Err(e) => {
+                    let _ = UnmapViewOfFile(view);
+                    let _ = CloseHandle(mapping);
+                    return Err(format!("Failed to create lock for {}: {}", name, e));
+                }
+            };
+            Ok(Self { mapping, view, mutex, size })
+        }
+    }
+
+    /// Run `f` on the block's bytes while holding its lock
+    fn with_lock<T>(&self, f: impl FnOnce(&mut [u8]) -> T) -> Result<T, String> {
+        let _lock = Lock::acquire(self.mutex)?;
+        let bytes = unsafe { std::slice::from_raw_parts_mut(self.view.Value as *mut u8, self.size) };
+        Ok(f(bytes))
+    }
+}
+
+#[cfg(windows)]
+impl Drop for SharedMemory {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = UnmapViewOfFile(self.view);
+            let _ = CloseHandle(self.mapping);
+            let _ = CloseHandle(self.mutex);
+        }
+    }
+}
+
+/// A held Windows mutex, released on drop
+#[cfg(windows)]
+struct Lock(HANDLE);
+
+#[cfg(windows)]
+impl Lock {
+    fn acquire(mutex: HANDLE) -> Result<Self, String> {
+        let result = unsafe { WaitForSingleObject(mutex, LOCK_TIMEOUT_MS) };
+        if result == WAIT_OBJECT_0 || result == WAIT_ABANDONED {
+            Ok(Self(mutex))
+        } else {
+            Err("Timed out waiting for a Spout lock".to_string())
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Lock {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = ReleaseMutex(self.0);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl SpoutSender {
+    /// Create the D3D11 device and register the sender under `name`
+    pub fn new(name: &str) -> Result<Self, String> {
+        if name.is_empty() || name.len() >= NAME_LEN {
+            return Err(format!("Spout sender names must be 1-{} characters", NAME_LEN - 1));
+        }
+
+        let mut device = None;
+        let mut context = None;
+        unsafe {
+            D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                Some(&[D3D_FEATURE_LEVEL_11_0]),
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )
+        }.map_err(|e| format!("Failed to create D3D11 device: {}", e))?;
+        let device = device.ok_or("D3D11 returned no device")?;
+        let context = context.ok_or("D3D11 returned no device context")?;
+
+        let access_name = c_name(&format!("{}_SpoutAccessMutex", name))?;
+        let access_mutex = unsafe { CreateMutexA(None, false, PCSTR(access_name.as_ptr().cast())) }
+            .map_err(|e| format!("Failed to create Spout access lock: {}", e))?;
+        let info = match SharedMemory::open(name, std::mem::size_of::<SharedTextureInfo>()) {
+            Ok(info) => info,
+            Err(e) => {
+                unsafe {
+                    let _ = CloseHandle(access_mutex);
+                }
+                return Err(e);
+            }
+        };
+
+        let sender = Self { name: name.to_string(), device, context, texture: None, info, access_mutex };
+        sender.register()?;
+        println!("Created Spout sender '{}'", name);
+        Ok(sender)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Share one frame of RGBA pixels (as a canvas gives them); the texture
+    /// follows the frame's size
+    pub fn send(&mut self, width: u32, height: u32, rgba: &[u8]) -> Result<(), String> {
+        if width == 0 || height == 0 || rgba.len() != width as usize * height as usize * 4 {
+            return Err(format!("Frame of {} bytes doesn't match {}x{}", rgba.len(), width, height));
+        }
+        if !matches!(self.texture, Some((_, w, h)) if (w, h) == (width, height)) {
+            self.create_texture(width, height)?;
+        }
+        let Some((texture, _, _)) = &self.texture else {
+            return Ok(());
+        };
+
+        // BGRA is the format every Spout receiver reads, DX9 ones included
+        let mut bgra = rgba.to_vec();
+        for pixel in bgra.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        // A receiver is still reading the last frame; drop this one
+        let Ok(_lock) = Lock::acquire(self.access_mutex) else {
+            return Ok(());
+        };
+        unsafe {
+            self.context.UpdateSubresource(texture, 0, None, bgra.as_ptr() as *const _, width * 4, 0);
+            self.context.Flush();
+        }
+        Ok(())
+    }
+
+    /// A new shared texture for a new frame size, published to receivers
+    fn create_texture(&mut self, width: u32, height: u32) -> Result<(), String> {
+        let desc = D3D11_TEXTURE2D_DESC {
+            Width: width,
+            Height: height,
+            MipLevels: 1,
+            ArraySize: 1,
+            Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC { Count: 1, Quality: 0 },
+            Usage: D3D11_USAGE_DEFAULT,
+            BindFlags: (D3D11_BIND_SHADER_RESOURCE.0 | D3D11_BIND_RENDER_TARGET.0) as u32,
+            CPUAccessFlags: 0,
+            MiscFlags: D3D11_RESOURCE_MISC_SHARED.0 as u32,
+        };
+        let mut texture = None;
+        unsafe { self.device.CreateTexture2D(&desc, None, Some(&mut texture)) }
+            .map_err(|e| format!("Failed to create {}x{} shared texture: {}", width, height, e))?;
+        let texture = texture.ok_or("D3D11 returned no texture")?;
+        let share_handle = unsafe { texture.cast::<IDXGIResource>().and_then(|resource| resource.GetSharedHandle()) }
+            .map_err(|e| format!("Failed to share texture: {}", e))?;
+
+        // Shared handles are 32-bit values, even in 64-bit processes
+        let info = SharedTextureInfo {
+            share_handle: share_handle.0 as usize as u32,
+            width,
+            height,
+            format: DXGI_FORMAT_B8G8R8A8_UNORM.0 as u32,
+            usage: 0,
+            description: [0; 256],
+            partner_id: 0,
+        };
+        self.info.with_lock(|bytes| unsafe {
+            std::ptr::copy_nonoverlapping(&info as *const SharedTextureInfo as *const u8, bytes.as_mut_ptr(), bytes.len());
+        })?;
+        self.texture = Some((texture, width, height));
+        Ok(())
+    }
+
+    /// Add the sender to "SpoutSenderNames" and make it the active sender
+    /// if there is none
+    fn register(&self) -> Result<(), String> {
+        let names = SharedMemory::open("SpoutSenderNames", NAME_LEN * MAX_SENDERS)?;
+        let added = names.with_lock(|bytes| {
+            let mut senders = read_names(bytes);
+            let added = senders.insert(self.name.clone());
+            write_names(bytes, &senders);
+            added
+        })?;
+        if !added {
+            return Err(format!("A Spout sender named '{}' already exists", self.name));
+        }
+
+        let active = SharedMemory::open("ActiveSenderName", NAME_LEN)?;
+        active.with_lock(|bytes| {
+            if bytes[0] == 0 {
+                write_name(bytes, &self.name);
+            }
+        })
+    }
+
+    /// Take the sender off "SpoutSenderNames", handing "active" on if it had it
+    fn unregister(&self) -> Result<(), String> {
+        let names = SharedMemory::open("SpoutSenderNames", NAME_LEN * MAX_SENDERS)?;
+        let remaining = names.with_lock(|bytes| {
+            let mut senders = read_names(bytes);
+            senders.remove(&self.name);
+            write_names(bytes, &senders);
+            senders
+        })?;
+
+        let active = SharedMemory::open("ActiveSenderName", NAME_LEN)?;
+        active.with_lock(|bytes| {
+            if read_name(bytes) == self.name {
+                write_name(bytes, remaining.iter().next().map(String::as_str).unwrap_or(""));
+            }
+        })
+    }
+}
+
+// The device, texture and handles are only used behind AppState's mutex
+#[cfg(windows)]
+unsafe impl Send for SpoutSender {}
+
+#[cfg(windows)]
+impl Drop for SpoutSender {
+    fn drop(&mut self) {
+        if let Err(e) = self.unregister() {
+            eprintln!("Failed to unregister Spout sender '{}': {}", self.name, e);
+        }
+        unsafe {
+            let _ = CloseHandle(self.access_mutex);
+        }
+        println!("Spout sender '{}' closed", self.name);
+    }
+}
+
+#[cfg(windows)]
+fn c_name(name: &str) -> Result<std::ffi::CString, String> {
+    std::ffi::CString::new(name).map_err(|_| format!("Invalid name '{}'", name))
+}
+
+#[cfg(windows)]
+fn read_name(slot: &[u8]) -> String {
+    let len = slot.iter().position(|&b| b == 0).unwrap_or(slot.len());
+    String::from_utf8_lossy(&slot[..len]).into_owned()
+}
+
+#[cfg(windows)]
+fn write_name(slot: &mut [u8], name: &str) {
+    slot.fill(0);
+    slot[..name.len()].copy_from_slice(name.as_bytes());
+}
+
+/// Sender names up to the first empty slot, as Spout keeps them
+#[cfg(windows)]
+fn read_names(bytes: &[u8]) -> std::collections::BTreeSet<String> {
+    bytes.chunks_exact(NAME_LEN)
+        .map(read_name)
+        .take_while(|name| !name.is_empty())
+        .collect()
+}
+
+/// Write the names back in order, ending with an empty slot
+#[cfg(windows)]
+fn write_names(bytes: &mut [u8], names: &std::collections::BTreeSet<String>) {
+    bytes.fill(0);
+    for (slot, name) in bytes.chunks_exact_mut(NAME_LEN).zip(names) {
+        write_name(slot, name);
+    }
+}
+
+/// Spout is DirectX-only; other platforms report that instead of sharing
+#[cfg(not(windows))]
+pub struct SpoutSender;
+
+#[cfg(not(windows))]
+impl SpoutSender {
+    pub fn new(_name: &str) -> Result<Self, String> {
+        Err("Spout output is only available on Windows".to_string())
+    }
+
+    pub fn name(&self) -> &str {
+        ""
+    }
+
+    pub fn send(&mut self, _width: u32, _height: u32, _rgba: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Port of the local relay server, set once it is listening
+static RELAY_PORT: OnceLock<u16> = OnceLock::new();
+
+/// Viewers of each network source, keyed by source URL
+type Relays = Arc<Mutex<HashMap<String, Vec<SyncSender<Arc<Vec<u8>>>>>>>;
+
+/// Start the local relay server for ".stream" clips.
+///
+/// Webviews can't play RTSP or NDI, so each source is pulled by FFmpeg and
+/// re-served as MJPEG on 127.0.0.1, which an <img> element can show. A source
+/// is only connected while an output window is showing it; if the connection
+/// drops it is retried every few seconds until the clip is changed.
+pub fn start_relay_server() {
+    let listener = match TcpListener::bind("127.0.0.1:0") {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to start stream relay: {}", e);
+            return;
+        }
+    };
+    let port = match listener.local_addr() {
+        Ok(addr) => addr.port(),
+        Err(e) => {
+            eprintln!("Failed to start stream relay: {}", e);
+            return;
+        }
+    };
+    let _ = RELAY_PORT.set(port);
+    println!("Stream relay listening on 127.0.0.1:{}", port);
+
+    let relays: Relays = Arc::new(Mutex::new(HashMap::new()));
+    std::thread::spawn(move || {
+        for connection in listener.incoming().flatten() {
+            let relays = Arc::clone(&relays);
+            std::thread::spawn(move || serve_viewer(connection, relays));
+        }
+    });
+}
+
+/// Local URL an output window loads to show a network source
+pub fn relay_url(source: &str) -> Option<String> {
+    let port = RELAY_PORT.get()?;
+    let mut encoded = String::new();
+    for byte in source.as_bytes() {
+        let c = *byte as char;
+        if c.is_ascii_alphanumeric() || "-_.~".contains(c) {
+            encoded.push(c);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Some(format!("http://127.0.0.1:{}/stream?src={}", port, encoded))
+}
+
+fn serve_viewer(mut connection: TcpStream, relays: Relays) {
+    let Some(source) = read_requested_source(&connection) else {
+        let _ = connection.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+        return;
+    };
+
+    let header = "HTTP/1.1 200 OK\r\n\
+        Content-Type: multipart/x-mixed-replace; boundary=frame\r\n\
+        Cache-Control: no-cache\r\n\
+        Access-Control-Allow-Origin: *\r\n\r\n";
+    if connection.write_all(header.as_bytes()).is_err() {
+        return;
+    }
+
+    for frame in subscribe(&relays, &source) {
+        // Empty frames are liveness probes sent while the source is down
+        if frame.is_empty() {
+            if viewer_gone(&connection) {
+                break;
+            }
+            continue;
+        }
+        let part = format!("--frame\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n", frame.len());
+        let sent = connection.write_all(part.as_bytes())
+            .and_then(|_| connection.write_all(&frame))
+            .and_then(|_| connection.write_all(b"\r\n"));
+        if sent.is_err() {
+            // Viewer went away; dropping the receiver unsubscribes it
+            break;
+        }
+    }
+}
+
+/// A closed socket reads as end-of-file without blocking
+fn viewer_gone(connection: &TcpStream) -> bool {
+    if connection.set_nonblocking(true).is_err() {
+        return true;
+    }
+    let mut probe = [0u8; 1];
+    let gone = matches!(connection.peek(&mut probe), Ok(0));
+    let _ = connection.set_nonblocking(false);
+    gone
+}
+
+/// Source from "GET /stream?src=<percent-encoded url> HTTP/1.1"
+fn read_requested_source(connection: &TcpStream) -> Option<String> {
+    let mut reader = BufReader::new(connection.try_clone().ok()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    // Drain the headers
+    let mut line = String::new();
+    while reader.read_line(&mut line).ok()? > 2 {
+        line.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1)?;
+    let encoded = target.strip_prefix("/stream?src=")?;
+    Some(crate::media_scanner::percent_decode(encoded))
+}
+
+/// Join the viewers of a source, starting its FFmpeg pull if nobody was watching
+fn subscribe(relays: &Relays, source: &str) -> Receiver<Arc<Vec<u8>>> {
+    // Small buffer: a slow viewer skips frames rather than falling behind
+    let (tx, rx) = mpsc::sync_channel(2);
+    let mut relays_guard = relays.lock().unwrap();
+    let start_pull = !relays_guard.contains_key(source);
+    relays_guard.entry(source.to_string()).or_default().push(tx);
+    drop(relays_guard);
+
+    if start_pull {
+        let relays = Arc::clone(relays);
+        let source = source.to_string();
+        std::thread::spawn(move || pull_source(relays, source));
+    }
+    rx
+}
+
+/// Keep FFmpeg connected to the source while anyone is watching
+fn pull_source(relays: Relays, source: String) {
+    let Some(ffmpeg) = crate::find_ffmpeg() else {
+        eprintln!("Stream '{}' needs FFmpeg, which was not found", source);
+        relays.lock().unwrap().remove(&source);
+        return;
+    };
+
+    loop {
+        println!("Connecting to stream {}", source);
+        match spawn_ffmpeg(&ffmpeg, &source) {
+            Ok(mut child) => {
+                let watched = forward_frames(&mut child, &relays, &source);
+                let _ = child.kill();
+                let _ = child.wait();
+                if !watched {
+                    println!("Stream {} no longer shown, disconnected", source);
+                    return;
+                }
+                eprintln!("Stream {} dropped, reconnecting", source);
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+
+        std::thread::sleep(Duration::from_secs(3));
+        if !has_viewers(&relays, &source) {
+            return;
+        }
+    }
+}
+
+fn spawn_ffmpeg(ffmpeg: &str, source: &str) -> Result<Child, String> {
+    let mut command = Command::new(ffmpeg);
+    command.args(["-hide_banner", "-loglevel", "error"]);
+
+    if let Some(ndi_name) = source.strip_prefix("ndi://") {
+        // Needs an FFmpeg build with the libndi_newtek input device
+        command.args(["-f", "libndi_newtek", "-i", ndi_name]);
+    } else {
+        if source.starts_with("rtsp://") || source.starts_with("rtsps://") {
+            // TCP survives lossy networks and firewalls better than RTP over UDP;
+            // the 5 s socket timeout turns a stalled camera into a reconnect
+            command.args(["-rtsp_transport", "tcp", "-timeout", "5000000"]);
+        }
+        command.args(["-fflags", "nobuffer", "-flags", "low_delay", "-i", source]);
+    }
+
+    command.args(["-an", "-f", "image2pipe", "-c:v", "mjpeg", "-q:v", "5", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    command.spawn().map_err(|e| format!("Failed to start FFmpeg for {}: {}", source, e))
+}
+
+/// Split FFmpeg's JPEG stream into frames and hand them to the viewers.
+/// Returns false once nobody is watching, true if the stream ended.
+fn forward_frames(child: &mut Child, relays: &Relays, source: &str) -> bool {
+    let Some(mut stdout) = child.stdout.take() else {
+        return true;
+    };
+
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 64 * 1024];
+    loop {
+        let read = match stdout.read(&mut chunk) {
+            Ok(0) | Err(_) => return true,
+            Ok(n) => n,
+        };
+        buffer.extend_from_slice(&chunk[..read]);
+
+        // Each frame runs from SOI (FF D8) to EOI (FF D9)
+        while let Some(end) = buffer.windows(2).position(|w| w == [0xFF, 0xD9]) {
+            let frame: Vec<u8> = buffer.drain(..end + 2).collect();
+            if !frame.starts_with(&[0xFF, 0xD8]) {
+                continue;
+            }
+            if !broadcast(relays, source, Arc::new(frame)) {
+                return false;
+            }
+        }
+    }
+}
+
+/// Send a frame to every viewer, dropping the ones that have gone away.
+/// Returns false (and forgets the source) when no viewers remain.
+fn broadcast(relays: &Relays, source: &str, frame: Arc<Vec<u8>>) -> bool {
+    let mut relays_guard = relays.lock().unwrap();
+    let Some(viewers) = relays_guard.get_mut(source) else {
+        return false;
+    };
+    viewers.retain(|viewer| !matches!(viewer.try_send(Arc::clone(&frame)), Err(TrySendError::Disconnected(_))));
+    if viewers.is_empty() {
+        relays_guard.remove(source);
+        return false;
+    }
+    true
+}
+
+fn has_viewers(relays: &Relays, source: &str) -> bool {
+    let mut relays_guard = relays.lock().unwrap();
+    let Some(viewers) = relays_guard.get_mut(source) else {
+        return false;
+    };
+    // Probe so viewers that left while no frames flowed drop their receivers
+    viewers.retain(|viewer| !matches!(viewer.try_send(Arc::new(Vec::new())), Err(TrySendError::Disconnected(_))));
+    if viewers.is_empty() {
+        relays_guard.remove(source);
+        return false;
+    }
+    true
+}
@@ -0,0 +1,130 @@
+use crate::config::AppConfig;
+use crate::media_scanner;
+use crate::output_window;
+use crate::sacn_listener::DmxFrame;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// One caption from a sidecar file, in seconds from the start of the clip
+#[derive(Debug, Clone, Serialize)]
+pub struct Caption {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Captions for the clip at a media URL, from a ".vtt" or ".srt" file next
+/// to it with the same name (e.g. 001_intro.mp4 + 001_intro.srt)
+pub fn captions_for_url(media_url: &str) -> Vec<Caption> {
+    media_scanner::path_from_asset_url(media_url)
+        .and_then(|path| sidecar_for(&path))
+        .and_then(|sidecar| std::fs::read_to_string(sidecar).ok())
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+fn sidecar_for(media_path: &Path) -> Option<PathBuf> {
+    ["vtt", "srt", "VTT", "SRT"].iter()
+        .map(|ext| media_path.with_extension(ext))
+        .find(|path| path.is_file())
+}
+
+/// Parse SRT or WebVTT. Blocks are separated by blank lines; each caption
+/// block has a "start --> end" line followed by its text. Cue numbers,
+/// the WEBVTT header, NOTE blocks and formatting tags are dropped.
+pub fn parse(contents: &str) -> Vec<Caption> {
+    let contents = contents.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut captions = Vec::new();
+
+    for block in contents.split("\n\n") {
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let mut times = timing.split("-->");
+        let (Some(start), Some(end)) = (
+            times.next().and_then(parse_time),
+            times.next().and_then(|rest| parse_time(rest.split_whitespace().next().unwrap_or(""))),
+        ) else {
+            continue;
+        };
+        let text = lines.map(strip_tags).collect::<Vec<_>>().join("\n");
+        if !text.trim().is_empty() {
+            captions.push(Caption { start, end, text });
+        }
+    }
+    captions
+}
+
+/// "01:02:03,456" (SRT), "01:02:03.456" or "02:03.456" (WebVTT) in seconds
+fn parse_time(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>().ok()?, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        [m, s] => (0.0, m.parse::<f64>().ok()?, s.parse::<f64>().ok()?),
+        _ => return None,
+    };
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+fn strip_tags(line: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Whether a monitor shows captions: enabled in its settings and, if it has
+/// a subtitle channel, that channel at 128 or above
+pub fn visible(config: &AppConfig, values: &[u8], monitor_id: &str) -> bool {
+    let Some(monitor) = config.monitor(monitor_id) else {
+        return false;
+    };
+    if !monitor.subtitles.enabled {
+        return false;
+    }
+    match monitor.subtitles.channel {
+        Some(channel) => values.get(channel as usize).copied().unwrap_or(0) >= 128,
+        None => true,
+    }
+}
+
+/// Spawn the caption switch: pushes each monitor's caption visibility to its
+/// output window whenever the setting or the subtitle channel changes.
+pub fn spawn_subtitle_switch(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    std::thread::spawn(move || {
+        let mut shown: [Option<bool>; 2] = [None, None];
+
+        loop {
+            std::thread::sleep(Duration::from_millis(50));
+
+            let cfg = config.lock().unwrap().clone();
+            let values = dmx_frame.lock().unwrap().values;
+            for (i, monitor_id) in ["monitor1", "monitor2"].iter().enumerate() {
+                let visible = visible(&cfg, &values, monitor_id);
+                if shown[i] == Some(visible) {
+                    continue;
+                }
+                shown[i] = Some(visible);
+                if let Err(e) = output_window::push_captions_visible(&app_handle, monitor_id, visible) {
+                    eprintln!("Failed to switch captions on {}: {}", monitor_id, e);
+                }
+            }
+        }
+    });
+}
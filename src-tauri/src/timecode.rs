@@ -0,0 +1,281 @@
+use crate::artnet_listener::ARTNET_PORT;
+use crate::config::{AppConfig, TimecodeConfig, TimecodeSource};
+use crate::midi_input;
+use crate::output_window;
+use crate::playback::PlaybackTracker;
+use crate::timeline::{self, Transport};
+use midir::{Ignore, MidiInputConnection};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const OP_TIME_CODE: u16 = 0x9700;
+
+/// No frame for this long and timecode counts as stopped
+const TIMECODE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A seeked clip gets this long to settle before it is checked again
+const SEEK_SETTLE: Duration = Duration::from_secs(1);
+
+/// The last timecode frame received
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    /// Time the frame's label stands for, in seconds
+    position_secs: f64,
+    received: Instant,
+}
+
+impl Frame {
+    /// Where timecode is now, assuming it kept running since the frame
+    fn now(&self) -> f64 {
+        self.position_secs + self.received.elapsed().as_secs_f64()
+    }
+}
+
+/// Seconds for hh:mm:ss:ff. Drop-frame labels are taken at face value
+/// (ff / 30), which is what consoles show for them.
+fn to_secs(hours: u8, minutes: u8, seconds: u8, frames: u8, fps: f64) -> f64 {
+    hours as f64 * 3600.0 + minutes as f64 * 60.0 + seconds as f64 + frames as f64 / fps
+}
+
+/// Frame rate from the two-bit rate code MTC and ArtTimeCode share
+fn frame_rate(code: u8) -> f64 {
+    match code & 0x03 {
+        0 => 24.0,
+        1 => 25.0,
+        // 29.97 drop-frame and 30
+        _ => 30.0,
+    }
+}
+
+/// Parse an ArtTimeCode packet
+fn parse_art_time_code(packet: &[u8]) -> Option<f64> {
+    if packet.len() < 19 || &packet[..8] != b"Art-Net\0" {
+        return None;
+    }
+    if u16::from_le_bytes([packet[8], packet[9]]) != OP_TIME_CODE {
+        return None;
+    }
+    let [frames, seconds, minutes, hours, rate] = [packet[14], packet[15], packet[16], packet[17], packet[18]];
+    Some(to_secs(hours, minutes, seconds, frames, frame_rate(rate)))
+}
+
+/// Assembles MIDI Timecode from quarter-frame and full-frame messages
+#[derive(Default)]
+struct MtcDecoder {
+    /// Nibbles of the eight quarter-frame pieces
+    pieces: [u8; 8],
+    /// Bit n set once piece n arrived since the last piece 0
+    received: u8,
+}
+
+impl MtcDecoder {
+    /// Feed one MIDI message; returns a position when it completes one
+    fn feed(&mut self, message: &[u8]) -> Option<f64> {
+        match message {
+            // Full frame, sent after locating: F0 7F <device> 01 01 hr mn sc fr F7
+            [0xf0, 0x7f, _, 0x01, 0x01, hours, minutes, seconds, frames, ..] => {
+                self.received = 0;
+                Some(to_secs(hours & 0x1f, *minutes, *seconds, *frames, frame_rate(hours >> 5)))
+            }
+            [0xf1, data, ..] => {
+                let piece = (data >> 4) as usize & 0x07;
+                if piece == 0 {
+                    self.received = 0;
+                }
+                self.pieces[piece] = data & 0x0f;
+                self.received |= 1 << piece;
+                if piece != 7 || self.received != 0xff {
+                    return None;
+                }
+                let p = &self.pieces;
+                let frames = p[0] | ((p[1] & 0x01) << 4);
+                let seconds = p[2] | ((p[3] & 0x03) << 4);
+                let minutes = p[4] | ((p[5] & 0x03) << 4);
+                let hours = p[6] | ((p[7] & 0x01) << 4);
+                let fps = frame_rate(p[7] >> 1);
+                // The eight pieces took two frames to send, counted from piece 0
+                Some(to_secs(hours, minutes, seconds, frames, fps) + 2.0 / fps)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The open timecode input
+enum Input {
+    ArtNet(UdpSocket),
+    Midi {
+        device: String,
+        rx: Receiver<Vec<u8>>,
+        decoder: MtcDecoder,
+        _connection: MidiInputConnection<()>,
+    },
+}
+
+impl Input {
+    fn open(timecode: &TimecodeConfig) -> Result<Self, String> {
+        match timecode.source {
+            TimecodeSource::ArtNet => bind_socket()
+                .map(Input::ArtNet)
+                .map_err(|e| format!("Failed to bind Art-Net port {} for timecode: {}", ARTNET_PORT, e)),
+            TimecodeSource::Midi => {
+                let (tx, rx) = mpsc::channel();
+                // Quarter frames are MIDI time messages, so only active sense is ignored
+                let (_, connection) = midi_input::connect(&timecode.midi_device, Ignore::ActiveSense, tx)?;
+                Ok(Input::Midi {
+                    device: timecode.midi_device.clone(),
+                    rx,
+                    decoder: MtcDecoder::default(),
+                    _connection: connection,
+                })
+            }
+        }
+    }
+
+    /// Whether this input is what the config asks for
+    fn matches(&self, timecode: &TimecodeConfig) -> bool {
+        match self {
+            Input::ArtNet(_) => timecode.source == TimecodeSource::ArtNet,
+            Input::Midi { device, .. } => timecode.source == TimecodeSource::Midi && *device == timecode.midi_device,
+        }
+    }
+
+    /// Wait up to 50 ms for the next position
+    fn receive(&mut self, buf: &mut [u8]) -> Option<f64> {
+        match self {
+            Input::ArtNet(socket) => {
+                let len = socket.recv(buf).ok()?;
+                parse_art_time_code(&buf[..len])
+            }
+            Input::Midi { rx, decoder, .. } => {
+                let message = rx.recv_timeout(Duration::from_millis(50)).ok()?;
+                decoder.feed(&message)
+            }
+        }
+    }
+}
+
+/// Spawn the timecode input.
+///
+/// While enabled, each monitor with `chase_timecode` has its playing clip
+/// seeked whenever it drifts from the incoming timecode (less the monitor's
+/// `timecode_offset_secs`) by more than `max_drift_ms`. With `drive_timeline`
+/// the timeline's clock is located to the timecode and runs while it does.
+/// When timecode stops, clips carry on free-running from where they are.
+pub fn spawn_timecode_input(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    playback: Arc<Mutex<PlaybackTracker>>,
+    transport: Arc<Mutex<Transport>>,
+) {
+    std::thread::spawn(move || {
+        let mut input: Option<Input> = None;
+        let mut frame: Option<Frame> = None;
+        let mut last_seek: [Option<Instant>; 2] = [None, None];
+        let mut buf = [0u8; 1024];
+
+        loop {
+            let cfg = config.lock().unwrap().clone();
+            let timecode = &cfg.timecode;
+            if !timecode.enabled {
+                if input.take().is_some() {
+                    println!("Timecode input stopped");
+                }
+                frame = None;
+                std::thread::sleep(Duration::from_millis(250));
+                continue;
+            }
+
+            if !input.as_ref().is_some_and(|input| input.matches(timecode)) {
+                input = match Input::open(timecode) {
+                    Ok(opened) => {
+                        println!("Timecode input listening for {:?}", timecode.source);
+                        Some(opened)
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        std::thread::sleep(Duration::from_secs(2));
+                        continue;
+                    }
+                };
+            }
+            let Some(opened) = input.as_mut() else {
+                continue;
+            };
+
+            let was_running = frame.is_some_and(|f| f.received.elapsed() < TIMECODE_TIMEOUT);
+            if let Some(position_secs) = opened.receive(&mut buf) {
+                if !was_running {
+                    println!("Timecode running from {:.2}s", position_secs);
+                }
+                frame = Some(Frame { position_secs, received: Instant::now() });
+            }
+            let Some(current) = frame else {
+                continue;
+            };
+            let running = current.received.elapsed() < TIMECODE_TIMEOUT;
+            if !running {
+                if was_running {
+                    println!("Timecode stopped at {:.2}s", current.position_secs);
+                    if timecode.drive_timeline {
+                        timeline::pause(&app_handle, &transport);
+                    }
+                }
+                continue;
+            }
+
+            let now = current.now();
+            let max_drift = timecode.max_drift_ms as f64 / 1000.0;
+            if timecode.drive_timeline {
+                let state = timeline::state(&transport);
+                if (state.position_secs - now).abs() > max_drift {
+                    timeline::locate(&app_handle, &transport, now);
+                }
+                if !state.playing {
+                    timeline::play(&app_handle, &transport);
+                }
+            }
+
+            let monitors = [("monitor1", &cfg.monitor1), ("monitor2", &cfg.monitor2)];
+            for (i, (monitor_id, monitor)) in monitors.iter().enumerate() {
+                if !monitor.enabled || !monitor.chase_timecode {
+                    continue;
+                }
+                if last_seek[i].is_some_and(|at| at.elapsed() < SEEK_SETTLE) {
+                    continue;
+                }
+                let Some((position, duration)) = playback.lock().unwrap().position_now(monitor_id) else {
+                    continue;
+                };
+                // Before the clip's start or past its end there is nothing to chase
+                let target = now - monitor.timecode_offset_secs;
+                if target < 0.0 || target >= duration || (position - target).abs() <= max_drift {
+                    continue;
+                }
+                println!("Timecode {:.2}s: seeking {} from {:.2}s to {:.2}s", now, monitor_id, position, target);
+                if let Err(e) = output_window::push_seek(&app_handle, monitor_id, target) {
+                    eprintln!("Failed to seek {}: {}", monitor_id, e);
+                }
+                last_seek[i] = Some(Instant::now());
+            }
+        }
+    });
+}
+
+/// Port 6454 on every interface, shared with the Art-Net listener;
+/// ArtTimeCode is broadcast so both sockets get it
+fn bind_socket() -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(unix)]
+    socket.set_reuse_port(true)?;
+    socket.set_broadcast(true)?;
+    socket.bind(&SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), ARTNET_PORT).into())?;
+    let socket: UdpSocket = socket.into();
+    socket.set_read_timeout(Some(Duration::from_millis(50)))?;
+    Ok(socket)
+}
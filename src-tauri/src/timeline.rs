@@ -0,0 +1,160 @@
+use crate::config::{AppConfig, TimelineConfig};
+use crate::cue_stack;
+use crate::output_window;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Transport state of the timeline clock
+#[derive(Debug, Default)]
+pub struct Transport {
+    /// Set while playing: when the clock was last started
+    started: Option<Instant>,
+    /// Position when the clock was last started or stopped
+    position_secs: f64,
+    /// Bumped by locate so the driver re-applies the look at the new time
+    generation: u64,
+}
+
+impl Transport {
+    /// Current show time, before looping is applied
+    fn position(&self) -> f64 {
+        match self.started {
+            Some(started) => self.position_secs + started.elapsed().as_secs_f64(),
+            None => self.position_secs,
+        }
+    }
+}
+
+/// Payload of get_timeline_state and the timeline-state event
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineState {
+    pub playing: bool,
+    pub position_secs: f64,
+}
+
+pub fn state(transport: &Mutex<Transport>) -> TimelineState {
+    let transport = transport.lock().unwrap();
+    TimelineState {
+        playing: transport.started.is_some(),
+        position_secs: transport.position(),
+    }
+}
+
+pub fn play(app_handle: &AppHandle, transport: &Mutex<Transport>) {
+    {
+        let mut transport = transport.lock().unwrap();
+        if transport.started.is_none() {
+            transport.started = Some(Instant::now());
+        }
+    }
+    println!("Timeline play");
+    let _ = app_handle.emit("timeline-state", state(transport));
+}
+
+pub fn pause(app_handle: &AppHandle, transport: &Mutex<Transport>) {
+    {
+        let mut transport = transport.lock().unwrap();
+        transport.position_secs = transport.position();
+        transport.started = None;
+    }
+    println!("Timeline pause");
+    let _ = app_handle.emit("timeline-state", state(transport));
+}
+
+/// Move the clock, keeping it playing or paused. Also how an external
+/// clock (e.g. timecode) keeps the timeline in step.
+pub fn locate(app_handle: &AppHandle, transport: &Mutex<Transport>, position_secs: f64) {
+    {
+        let mut transport = transport.lock().unwrap();
+        transport.position_secs = position_secs.max(0.0);
+        if transport.started.is_some() {
+            transport.started = Some(Instant::now());
+        }
+        transport.generation += 1;
+    }
+    println!("Timeline locate to {:.2}s", position_secs);
+    let _ = app_handle.emit("timeline-state", state(transport));
+}
+
+/// Spawn the timeline driver.
+///
+/// While the clock runs, each monitor shows the latest event at or before
+/// the current time. After a locate the event in effect is shown straight
+/// away, with videos seeked to where they would be.
+pub fn spawn_timeline_driver(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    transport: Arc<Mutex<Transport>>,
+) {
+    std::thread::spawn(move || {
+        // Per monitor: index of the event shown
+        let mut shown: [Option<usize>; 2] = [None, None];
+        let mut seen_generation = 0;
+
+        loop {
+            std::thread::sleep(Duration::from_millis(25));
+
+            let (playing, raw_position, generation) = {
+                let transport = transport.lock().unwrap();
+                (transport.started.is_some(), transport.position(), transport.generation)
+            };
+            let relocated = generation != seen_generation;
+            if !playing && !relocated {
+                continue;
+            }
+            seen_generation = generation;
+
+            let cfg = config.lock().unwrap().clone();
+            let timeline = &cfg.timeline;
+            let position = looped_position(timeline, raw_position);
+            if position != raw_position {
+                // Wrap the clock itself so the reported position loops too
+                transport.lock().unwrap().position_secs -= raw_position - position;
+            }
+
+            let monitors = [("monitor1", &cfg.monitor1), ("monitor2", &cfg.monitor2)];
+            for (i, (monitor_id, monitor)) in monitors.iter().enumerate() {
+                let current = event_at(timeline, monitor_id, position);
+                if current == shown[i] && !relocated {
+                    continue;
+                }
+                shown[i] = current;
+
+                let Some((index, event)) = current.map(|index| (index, &timeline.events[index])) else {
+                    continue;
+                };
+                if !monitor.enabled {
+                    continue;
+                }
+                println!("Timeline {:.2}s: {} event {} (clip {})", position, monitor_id, index, event.look.clip);
+                cue_stack::show_look(&app_handle, monitor_id, monitor, event.look);
+
+                // Joined part way through: start the video where it would be
+                let offset = position - event.at_secs;
+                if relocated && offset > 0.1 {
+                    if let Err(e) = output_window::push_seek(&app_handle, monitor_id, offset) {
+                        eprintln!("Failed to seek {}: {}", monitor_id, e);
+                    }
+                }
+            }
+        }
+    });
+}
+
+fn looped_position(timeline: &TimelineConfig, position: f64) -> f64 {
+    match timeline.loop_at_secs {
+        Some(length) if length > 0.0 => position % length,
+        _ => position,
+    }
+}
+
+/// Index of the latest event for the monitor at or before `position`
+fn event_at(timeline: &TimelineConfig, monitor_id: &str, position: f64) -> Option<usize> {
+    timeline.events.iter()
+        .enumerate()
+        .filter(|(_, event)| event.monitor_id == monitor_id && event.at_secs <= position)
+        .max_by(|(_, a), (_, b)| a.at_secs.total_cmp(&b.at_secs))
+        .map(|(index, _)| index)
+}
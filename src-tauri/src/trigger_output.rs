@@ -0,0 +1,69 @@
+use crate::config::{TriggerOutputConfig, TriggerProtocol};
+use serde::Serialize;
+use std::net::UdpSocket;
+
+/// Payload of the clip-finished event
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipFinished {
+    pub monitor_id: String,
+    /// DMX value of the clip that ended, if it could be worked out
+    pub clip: Option<u16>,
+    pub media_url: String,
+}
+
+/// Send the configured outbound message for a finished clip
+pub fn send_clip_finished(config: &TriggerOutputConfig, finished: &ClipFinished) -> Result<(), String> {
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let clip = finished.clip.unwrap_or(0);
+    let packet = match config.protocol {
+        TriggerProtocol::Osc => encode_osc(&config.osc_address, &finished.monitor_id, clip as i32),
+        TriggerProtocol::Udp => config.udp_message
+            .replace("{monitor}", &finished.monitor_id)
+            .replace("{clip}", &clip.to_string())
+            .into_bytes(),
+    };
+
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .map_err(|e| format!("Failed to open trigger socket: {}", e))?;
+    socket.send_to(&packet, &config.target)
+        .map_err(|e| format!("Failed to send trigger to {}: {}", config.target, e))?;
+    println!("Sent clip-finished trigger for {} clip {} to {}", finished.monitor_id, clip, config.target);
+    Ok(())
+}
+
+/// Send an OSC message with int32 arguments to "host:port"
+pub fn send_osc(target: &str, address: &str, args: &[i32]) -> Result<(), String> {
+    let mut packet = Vec::new();
+    push_osc_string(&mut packet, address);
+    let type_tags: String = std::iter::once(',').chain(args.iter().map(|_| 'i')).collect();
+    push_osc_string(&mut packet, &type_tags);
+    for arg in args {
+        packet.extend_from_slice(&arg.to_be_bytes());
+    }
+
+    let socket = UdpSocket::bind("0.0.0.0:0")
+        .map_err(|e| format!("Failed to open OSC socket: {}", e))?;
+    socket.send_to(&packet, target)
+        .map_err(|e| format!("Failed to send OSC to {}: {}", target, e))?;
+    Ok(())
+}
+
+/// OSC message with a string and an int32 argument: "<address> ,si <monitor> <clip>"
+fn encode_osc(address: &str, monitor_id: &str, clip: i32) -> Vec<u8> {
+    let mut packet = Vec::new();
+    push_osc_string(&mut packet, address);
+    push_osc_string(&mut packet, ",si");
+    push_osc_string(&mut packet, monitor_id);
+    packet.extend_from_slice(&clip.to_be_bytes());
+    packet
+}
+
+/// OSC strings are null-terminated and padded to a multiple of 4 bytes
+fn push_osc_string(packet: &mut Vec<u8>, value: &str) {
+    packet.extend_from_slice(value.as_bytes());
+    let padding = 4 - value.len() % 4;
+    packet.extend(std::iter::repeat(0u8).take(padding));
+}
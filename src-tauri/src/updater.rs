@@ -0,0 +1,118 @@
+use crate::config::{AppConfig, UpdateChannel};
+use crate::sacn_listener::DmxFrame;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Url};
+use tauri_plugin_updater::UpdaterExt;
+
+/// Release manifests published with each GitHub release. Beta builds are
+/// attached to the rolling "beta" release.
+const STABLE_ENDPOINT: &str = "https://github.com/bboyink/MediaPlayer-e131/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str = "https://github.com/bboyink/MediaPlayer-e131/releases/download/beta/latest.json";
+
+/// Payload of check_for_updates and the update-available event
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub version: String,
+    pub notes: Option<String>,
+}
+
+fn endpoint(channel: UpdateChannel) -> &'static str {
+    match channel {
+        UpdateChannel::Stable => STABLE_ENDPOINT,
+        UpdateChannel::Beta => BETA_ENDPOINT,
+    }
+}
+
+async fn find_update(app_handle: &AppHandle, channel: UpdateChannel) -> Result<Option<tauri_plugin_updater::Update>, String> {
+    let url = Url::parse(endpoint(channel))
+        .map_err(|e| format!("Invalid update URL: {}", e))?;
+    let updater = app_handle.updater_builder()
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Failed to set up updater: {}", e))?;
+    updater.check().await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+/// Newer release on the configured channel, if any
+pub async fn check(app_handle: &AppHandle, channel: UpdateChannel) -> Result<Option<UpdateInfo>, String> {
+    let update = find_update(app_handle, channel).await?;
+    Ok(update.map(|update| UpdateInfo {
+        current_version: update.current_version.clone(),
+        version: update.version.clone(),
+        notes: update.body.clone(),
+    }))
+}
+
+/// Why an update can't be installed right now, if it can't
+pub fn install_blocked(config: &AppConfig, listening: bool) -> Option<String> {
+    if config.updates.defer {
+        return Some("Updates are deferred for the current show period".to_string());
+    }
+    if listening {
+        return Some("Updates can't be installed while production is running".to_string());
+    }
+    None
+}
+
+/// Download and install the newest release on the channel, then restart
+pub async fn install(app_handle: &AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    let Some(update) = find_update(app_handle, channel).await? else {
+        return Err("Already up to date".to_string());
+    };
+
+    println!("Installing update {} -> {}", update.current_version, update.version);
+    update.download_and_install(|_, _| {}, || println!("Update downloaded"))
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    app_handle.restart();
+}
+
+/// Check once shortly after launch when enabled, emitting "update-available"
+/// and installing straight away if auto_install is set and nothing blocks it.
+pub fn spawn_startup_check(
+    app_handle: AppHandle,
+    config: Arc<Mutex<AppConfig>>,
+    dmx_frame: Arc<Mutex<DmxFrame>>,
+) {
+    tauri::async_runtime::spawn(async move {
+        // Let the network come up on machines that launch at logon
+        tokio::time::sleep(Duration::from_secs(30)).await;
+
+        let settings = config.lock().unwrap().updates.clone();
+        if !settings.check_on_startup {
+            return;
+        }
+
+        let info = match check(&app_handle, settings.channel).await {
+            Ok(Some(info)) => info,
+            Ok(None) => {
+                println!("No update available");
+                return;
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                return;
+            }
+        };
+        println!("Update available: {}", info.version);
+        let _ = app_handle.emit("update-available", &info);
+
+        if !settings.auto_install {
+            return;
+        }
+        let listening = dmx_frame.lock().unwrap().listening_since.is_some();
+        let blocked = install_blocked(&config.lock().unwrap(), listening);
+        if let Some(reason) = blocked {
+            println!("Not installing update: {}", reason);
+            return;
+        }
+        if let Err(e) = install(&app_handle, settings.channel).await {
+            eprintln!("{}", e);
+        }
+    });
+}
@@ -2,13 +2,13 @@ import { useState, useEffect, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
 import { convertFileSrc } from '@tauri-apps/api/core'
-import { AppConfig, NetworkInterface, DisplayInfo, DmxUpdate } from './types'
+import { getCurrentWebview } from '@tauri-apps/api/webview'
+import { AppConfig, NetworkInterface, DisplayInfo, DmxUpdate, LaunchOptions, MediaEntry, MediaFile, DiskSpace, AppInfo, UpdateInfo, SelfTestReport, Cue, CueLook, CueState, TimelineState, StateSnapshot, DimmingCurve, Deinterlace, MediaProbe, SourceWarning, MergeMode, DiscoveredSource, SacnStats, SourceTerminated, TerminationAction, DataLossAction, SignalLossEvent, ActiveSource, InterfaceError, InterfaceChanged, SourceConflict, FailoverEvent, AvailableInput, RecordingSummary, PlaybackState, MonitorConfig, ChannelMap, getClipChannel, getClipFineChannel, getDimmerChannel, getPlaytypeChannel, getVolumeChannel, getPanChannel, getZoomChannel, getSpeedChannel } from './types'
 import Slider from 'rc-slider'
 import videojs from 'video.js'
 import 'video.js/dist/video-js.css'
 import 'rc-slider/assets/index.css'
 import './App.css'
-import { version } from '../package.json'
 
 function App() {
   const [config, setConfig] = useState<AppConfig | null>(null)
@@ -26,6 +26,9 @@ function App() {
   const loadConfig = async () => {
     const cfg = await invoke<AppConfig>('get_config')
     setConfig(cfg)
+    // Launched by the auto-start task: go straight to production
+    const launch = await invoke<LaunchOptions>('get_launch_options')
+    if (launch.production) startProduction(cfg)
   }
 
   const loadNetworkInterfaces = async () => {
@@ -46,7 +49,8 @@ function App() {
         name: 'Primary Display',
         is_primary: true,
         width: 1920,
-        height: 1080
+        height: 1080,
+        scale_factor: 1
       }])
     }
   }
@@ -97,75 +101,214 @@ function App() {
   // Production DMX state
   const [prodMonitor1Video, setProdMonitor1Video] = useState(0)
   const [prodMonitor2Video, setProdMonitor2Video] = useState(0)
+  // The clip channel is at its close value (255, or 65535 in 16-bit mode)
+  const [prodMonitor1Closed, setProdMonitor1Closed] = useState(false)
+  const [prodMonitor2Closed, setProdMonitor2Closed] = useState(false)
   const [prodMonitor1Dimmer, setProdMonitor1Dimmer] = useState(255)
   const [prodMonitor2Dimmer, setProdMonitor2Dimmer] = useState(255)
   const [prodMonitor1Mode, setProdMonitor1Mode] = useState(0)
   const [prodMonitor2Mode, setProdMonitor2Mode] = useState(0)
-  const [prodMonitor1Files, setProdMonitor1Files] = useState<string[]>([])
-  const [prodMonitor2Files, setProdMonitor2Files] = useState<string[]>([])
+  // Volume channel level; null leaves volume to the clip's defaults
+  const [prodMonitor1Volume, setProdMonitor1Volume] = useState<number | null>(null)
+  const [prodMonitor2Volume, setProdMonitor2Volume] = useState<number | null>(null)
+  // Crossfade into the current clip, as the backend resolved it
+  const [prodMonitor1Crossfade, setProdMonitor1Crossfade] = useState(0)
+  const [prodMonitor2Crossfade, setProdMonitor2Crossfade] = useState(0)
+  // Ken Burns pan/zoom/speed for still images
+  const [prodMonitor1Motion, setProdMonitor1Motion] = useState<[number, number, number]>([0, 0, 0])
+  const [prodMonitor2Motion, setProdMonitor2Motion] = useState<[number, number, number]>([0, 0, 0])
+  // Shader effect amounts: pixelate, chroma shift, vignette
+  const [prodMonitor1Effects, setProdMonitor1Effects] = useState<[number, number, number]>([0, 0, 0])
+  const [prodMonitor2Effects, setProdMonitor2Effects] = useState<[number, number, number]>([0, 0, 0])
+  // Resolved by the backend along with the clip value
+  const [prodMonitor1Url, setProdMonitor1Url] = useState<string | null>(null)
+  const [prodMonitor2Url, setProdMonitor2Url] = useState<string | null>(null)
+
+  // A media or convert folder's drive is nearly full
+  useEffect(() => {
+    const unlisten = listen<DiskSpace>('disk-space-low', (event) => {
+      const space = event.payload
+      const freeMb = Math.round(space.free_bytes / (1024 * 1024))
+      alert(`Low disk space: only ${freeMb} MB free for ${space.folder}. Imports and conversions may fail.`)
+    })
+    return () => { unlisten.then(fn => fn()) }
+  }, [])
 
-  // Load production media file lists
+  // Another sACN source appeared on our universe, sources are fighting over our
+  // channels, one stopped sending or the network interface went away or moved;
+  // say why control may have changed hands
+  const [sourceWarnings, setSourceWarnings] = useState<(SourceWarning | SourceTerminated | SourceConflict | InterfaceError | InterfaceChanged | FailoverEvent)[]>([])
   useEffect(() => {
-    if (!config) return
-    if (config.monitor1.media_folder) {
-      invoke<string[]>('get_media_files', { folder: config.monitor1.media_folder })
-        .then(files => setProdMonitor1Files(files)).catch(() => {})
+    const unlisten = listen<SourceWarning>('sacn-source-warning', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    const unlistenTerminated = listen<SourceTerminated>('source-terminated', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    const unlistenConflict = listen<SourceConflict>('source-conflict', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    const unlistenFailover = listen<FailoverEvent>('input-failover', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    const unlistenInterface = listen<InterfaceError>('sacn-interface-error', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    const unlistenInterfaceChanged = listen<InterfaceChanged>('sacn-interface-changed', (event) => {
+      setSourceWarnings(warnings => [...warnings.slice(-4), event.payload])
+    })
+    return () => {
+      unlisten.then(fn => fn())
+      unlistenTerminated.then(fn => fn())
+      unlistenConflict.then(fn => fn())
+      unlistenFailover.then(fn => fn())
+      unlistenInterface.then(fn => fn())
+      unlistenInterfaceChanged.then(fn => fn())
     }
-    if (config.monitor2.media_folder) {
-      invoke<string[]>('get_media_files', { folder: config.monitor2.media_folder })
-        .then(files => setProdMonitor2Files(files)).catch(() => {})
+  }, [])
+
+  // Set while the listened universe has had no DMX for the data loss timeout
+  const [signalLost, setSignalLost] = useState<SignalLossEvent | null>(null)
+  useEffect(() => {
+    const unlistenLost = listen<SignalLossEvent>('dmx-signal-lost', (event) => setSignalLost(event.payload))
+    const unlistenRestored = listen<SignalLossEvent>('dmx-signal-restored', () => setSignalLost(null))
+    return () => {
+      unlistenLost.then(fn => fn())
+      unlistenRestored.then(fn => fn())
     }
-  }, [config?.monitor1.media_folder, config?.monitor2.media_folder])
+  }, [])
+
+  // Files dropped on a monitor's config card are imported into its media folder
+  useEffect(() => {
+    let unlisten: (() => void) | null = null
+    getCurrentWebview().onDragDropEvent(async (event) => {
+      if (event.payload.type !== 'drop') return
+      const scale = window.devicePixelRatio || 1
+      const { x, y } = event.payload.position
+      const target = document.elementFromPoint(x / scale, y / scale)?.closest('[data-drop-monitor]')
+      const monitorId = target?.getAttribute('data-drop-monitor')
+      if (!monitorId) return
+      try {
+        const imported = await invoke<MediaFile[]>('import_media', {
+          monitorId,
+          paths: event.payload.paths,
+          transcode: true
+        })
+        console.log('Imported media:', imported.map(f => f.filename))
+      } catch (err) {
+        alert('Import failed: ' + err)
+      }
+    }).then(fn => { unlisten = fn })
+    return () => { if (unlisten) unlisten() }
+  }, [])
 
   // sACN listener for production mode
   useEffect(() => {
     if (!productionActive || !config) return
     setProdMonitor1Video(0); setProdMonitor2Video(0)
+    setProdMonitor1Closed(false); setProdMonitor2Closed(false)
     setProdMonitor1Dimmer(255); setProdMonitor2Dimmer(255)
     setProdMonitor1Mode(0); setProdMonitor2Mode(0)
+    setProdMonitor1Url(null); setProdMonitor2Url(null)
+    setProdMonitor1Volume(null); setProdMonitor2Volume(null)
+    setProdMonitor1Crossfade(0); setProdMonitor2Crossfade(0)
+    setProdMonitor1Motion([0, 0, 0]); setProdMonitor2Motion([0, 0, 0])
+    setProdMonitor1Effects([0, 0, 0]); setProdMonitor2Effects([0, 0, 0])
     let unlistenFn: (() => void) | null = null
+    let unlistenState: (() => void) | null = null
     const setup = async () => {
       try { await invoke('start_sacn_listener') } catch (err) { console.error('Failed to start sACN listener:', err); return }
-      unlistenFn = await listen('dmx-update', (event: any) => {
-        const update = event.payload as DmxUpdate
-        if (config.monitor1.enabled) {
-          if (update.channel === config.monitor1.start_channel) setProdMonitor1Video(update.value)
-          else if (update.channel === config.monitor1.start_channel + 1) setProdMonitor1Dimmer(update.value)
-          else if (update.channel === config.monitor1.start_channel + 2) setProdMonitor1Mode(update.value)
+      // Clip, dimmer and playtype arrive assembled per monitor, only when they change
+      unlistenState = await listen<PlaybackState>('playback-state', (event) => {
+        const state = event.payload
+        if (state.monitor_id === 'monitor1') {
+          setProdMonitor1Video(state.clip); setProdMonitor1Closed(state.close_output); setProdMonitor1Dimmer(state.dimmer)
+          setProdMonitor1Mode(state.playtype); setProdMonitor1Url(state.media_url)
+          setProdMonitor1Volume(state.volume); setProdMonitor1Crossfade(state.transition_ms)
+        } else if (state.monitor_id === 'monitor2') {
+          setProdMonitor2Video(state.clip); setProdMonitor2Closed(state.close_output); setProdMonitor2Dimmer(state.dimmer)
+          setProdMonitor2Mode(state.playtype); setProdMonitor2Url(state.media_url)
+          setProdMonitor2Volume(state.volume); setProdMonitor2Crossfade(state.transition_ms)
         }
-        if (config.monitor2.enabled) {
-          if (update.channel === config.monitor2.start_channel) setProdMonitor2Video(update.value)
-          else if (update.channel === config.monitor2.start_channel + 1) setProdMonitor2Dimmer(update.value)
-          else if (update.channel === config.monitor2.start_channel + 2) setProdMonitor2Mode(update.value)
+      })
+      unlistenFn = await listen<DmxUpdate>('dmx-update', (event) => {
+        for (const [channel, value] of event.payload.channels) {
+          // Pan, zoom and speed can be patched anywhere, so find which one this is
+          const motionOffset = (monitor: MonitorConfig) =>
+            [getPanChannel(monitor), getZoomChannel(monitor), getSpeedChannel(monitor)].indexOf(channel)
+          const setMotionValue = (offset: number) => (motion: [number, number, number]) =>
+            motion.map((v, i) => i === offset ? value : v) as [number, number, number]
+          if (config.monitor1.enabled && config.monitor1.ken_burns) {
+            const offset = motionOffset(config.monitor1)
+            if (offset >= 0) setProdMonitor1Motion(setMotionValue(offset))
+          }
+          if (config.monitor2.enabled && config.monitor2.ken_burns) {
+            const offset = motionOffset(config.monitor2)
+            if (offset >= 0) setProdMonitor2Motion(setMotionValue(offset))
+          }
+          if (config.monitor1.enabled && config.monitor1.effects_channel) {
+            const offset = channel - config.monitor1.effects_channel
+            if (offset >= 0 && offset < 3) setProdMonitor1Effects(setMotionValue(offset))
+          }
+          if (config.monitor2.enabled && config.monitor2.effects_channel) {
+            const offset = channel - config.monitor2.effects_channel
+            if (offset >= 0 && offset < 3) setProdMonitor2Effects(setMotionValue(offset))
+          }
         }
       })
     }
     setup()
-    return () => { unlistenFn?.() }
+    return () => { unlistenFn?.(); unlistenState?.() }
   }, [productionActive])
 
-  const getProdMediaFile = (dmxValue: number, files: string[]): string | null => {
-    if (dmxValue === 0) return null
-    const padded = dmxValue.toString().padStart(3, '0')
-    return files.find(f => f.startsWith(padded + '_')) || null
-  }
-
   // Update output windows when DMX values change during production
   useEffect(() => {
     if (!productionActive || !config?.monitor1.enabled) return
-    if (prodMonitor1Video === 255) return // handled by close_output_window effect below
-    const file = getProdMediaFile(prodMonitor1Video, prodMonitor1Files)
-    const mediaUrl = file ? convertFileSrc(`${config.monitor1.media_folder}/${file}`) : null
-    invoke('update_output_window', { monitorId: 'monitor1', mediaUrl, dimmer: prodMonitor1Dimmer, playtype: prodMonitor1Mode, orientation: config.monitor1.orientation }).catch(() => {})
-  }, [prodMonitor1Video, prodMonitor1Dimmer, prodMonitor1Mode, productionActive])
+    if (prodMonitor1Closed) return // handled by close_output_window effect below
+    invoke('update_output_window', { monitorId: 'monitor1', mediaUrl: prodMonitor1Url, dimmer: prodMonitor1Dimmer, playtype: prodMonitor1Mode, orientation: config.monitor1.orientation, transitionMs: prodMonitor1Crossfade }).catch(() => {})
+    // The crossfade time is read when the clip changes, so it isn't a dependency
+  }, [prodMonitor1Video, prodMonitor1Url, prodMonitor1Dimmer, prodMonitor1Mode, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor2.enabled) return
+    if (prodMonitor2Closed) return // handled by close_output_window effect below
+    invoke('update_output_window', { monitorId: 'monitor2', mediaUrl: prodMonitor2Url, dimmer: prodMonitor2Dimmer, playtype: prodMonitor2Mode, orientation: config.monitor2.orientation, transitionMs: prodMonitor2Crossfade }).catch(() => {})
+    // The crossfade time is read when the clip changes, so it isn't a dependency
+  }, [prodMonitor2Video, prodMonitor2Url, prodMonitor2Dimmer, prodMonitor2Mode, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor1.enabled) return
+    invoke('update_output_volume', { monitorId: 'monitor1', volume: prodMonitor1Volume }).catch(() => {})
+  }, [prodMonitor1Volume, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor2.enabled) return
+    invoke('update_output_volume', { monitorId: 'monitor2', volume: prodMonitor2Volume }).catch(() => {})
+  }, [prodMonitor2Volume, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor1.enabled) return
+    const [pan, zoom, speed] = prodMonitor1Motion
+    invoke('update_output_motion', { monitorId: 'monitor1', pan, zoom, speed }).catch(() => {})
+  }, [prodMonitor1Motion, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor2.enabled) return
+    const [pan, zoom, speed] = prodMonitor2Motion
+    invoke('update_output_motion', { monitorId: 'monitor2', pan, zoom, speed }).catch(() => {})
+  }, [prodMonitor2Motion, productionActive])
+
+  useEffect(() => {
+    if (!productionActive || !config?.monitor1.enabled) return
+    const [pixelate, chromaShift, vignette] = prodMonitor1Effects
+    invoke('update_output_effects', { monitorId: 'monitor1', pixelate, chromaShift, vignette }).catch(() => {})
+  }, [prodMonitor1Effects, productionActive])
 
   useEffect(() => {
     if (!productionActive || !config?.monitor2.enabled) return
-    if (prodMonitor2Video === 255) return // handled by close_output_window effect below
-    const file = getProdMediaFile(prodMonitor2Video, prodMonitor2Files)
-    const mediaUrl = file ? convertFileSrc(`${config.monitor2.media_folder}/${file}`) : null
-    invoke('update_output_window', { monitorId: 'monitor2', mediaUrl, dimmer: prodMonitor2Dimmer, playtype: prodMonitor2Mode, orientation: config.monitor2.orientation }).catch(() => {})
-  }, [prodMonitor2Video, prodMonitor2Dimmer, prodMonitor2Mode, productionActive])
+    const [pixelate, chromaShift, vignette] = prodMonitor2Effects
+    invoke('update_output_effects', { monitorId: 'monitor2', pixelate, chromaShift, vignette }).catch(() => {})
+  }, [prodMonitor2Effects, productionActive])
 
   // ESC to stop production
   useEffect(() => {
@@ -175,16 +318,16 @@ function App() {
     return () => window.removeEventListener('keydown', handleKey)
   }, [productionActive])
 
-  // Video channel 255 = close that monitor's output window
+  // Video channel 255 (65535 in 16-bit mode) = close that monitor's output window
   useEffect(() => {
     if (!productionActive) return
-    if (prodMonitor1Video === 255) invoke('close_output_window', { monitorId: 'monitor1' }).catch(() => {})
-  }, [prodMonitor1Video, productionActive])
+    if (prodMonitor1Closed) invoke('close_output_window', { monitorId: 'monitor1' }).catch(() => {})
+  }, [prodMonitor1Closed, productionActive])
 
   useEffect(() => {
     if (!productionActive) return
-    if (prodMonitor2Video === 255) invoke('close_output_window', { monitorId: 'monitor2' }).catch(() => {})
-  }, [prodMonitor2Video, productionActive])
+    if (prodMonitor2Closed) invoke('close_output_window', { monitorId: 'monitor2' }).catch(() => {})
+  }, [prodMonitor2Closed, productionActive])
 
   if (!config) {
     return <div className="loading">Loading...</div>
@@ -242,6 +385,27 @@ function App() {
       </nav>
 
       <main className="content">
+        {signalLost && (
+          <div className="card" style={{ background: '#2a1111', border: '1px solid #552222', marginBottom: '16px' }}>
+            <strong style={{ color: '#f66' }}>⚠ No DMX on universe {signalLost.universe}</strong>
+            <p style={{ margin: '6px 0 0', fontSize: '13px', color: '#ccc' }}>
+              {signalLost.action === 'Hold' && 'Holding the last look until data returns.'}
+              {signalLost.action === 'FadeToBlack' && 'Outputs are fading to black until data returns.'}
+              {signalLost.action === 'FallbackClip' && 'Monitors with a fallback clip switch to it after their fallback timeout.'}
+            </p>
+          </div>
+        )}
+        {sourceWarnings.length > 0 && (
+          <div className="card" style={{ background: '#2a2211', border: '1px solid #554422', marginBottom: '16px' }}>
+            <strong style={{ color: '#fc6' }}>⚠ sACN warning</strong>
+            {sourceWarnings.map((warning, i) => (
+              <p key={i} style={{ margin: '6px 0 0', fontSize: '13px', color: '#ccc' }}>{warning.message}</p>
+            ))}
+            <button type="button" className="browse-button" style={{ marginTop: '8px' }} onClick={() => setSourceWarnings([])}>
+              Dismiss
+            </button>
+          </div>
+        )}
         {activeSection === 'dmx' && (
           <DmxSection 
             config={config} 
@@ -392,30 +556,45 @@ function EditableName({
 // Preview Test Panel Component with support for both monitors
 function PreviewTestPanel({ 
   universe, 
-  monitor1Start,
+  monitor1Channels,
   monitor1Name,
-  monitor2Start,
+  monitor2Channels,
   monitor2Name,
   onValuesChange 
 }: { 
   universe: number
-  monitor1Start: number
+  // Clip, dimmer and playtype channels, as patched
+  monitor1Channels: [number, number, number]
   monitor1Name: string
-  monitor2Start: number
+  monitor2Channels: [number, number, number]
   monitor2Name: string
   onValuesChange: (monitor1Video: number, monitor1Dimmer: number, monitor1Mode: number, monitor2Video: number, monitor2Dimmer: number, monitor2Mode: number) => void
 }) {
   const [testSenderActive, setTestSenderActive] = useState(false)
   const [m1Video, setM1Video] = useState(100)
   const [m1Dimmer, setM1Dimmer] = useState(255)
-  const [m1Mode, setM1Mode] = useState(1)
+  const [m1Mode, setM1Mode] = useState(0)
   const [m2Video, setM2Video] = useState(100)
   const [m2Dimmer, setM2Dimmer] = useState(255)
-  const [m2Mode, setM2Mode] = useState(1)
+  const [m2Mode, setM2Mode] = useState(0)
+  const [priority, setPriority] = useState(100)
+  const [preview, setPreview] = useState(false)
+  const [destination, setDestination] = useState('')
+  const [loopback, setLoopback] = useState(false)
+  const [interfaceIp, setInterfaceIp] = useState('')
+  const [ttl, setTtl] = useState('')
+  const [networkInterfaces, setNetworkInterfaces] = useState<NetworkInterface[]>([])
   const [error, setError] = useState<string | null>(null)
   const [success, setSuccess] = useState<string | null>(null)
   const [isCollapsed, setIsCollapsed] = useState(true)
 
+  // Multi-homed machines pick the show network's NIC to send from
+  useEffect(() => {
+    invoke<NetworkInterface[]>('get_network_interfaces')
+      .then(interfaces => setNetworkInterfaces(interfaces.filter(iface => !iface.ip_address.includes(':'))))
+      .catch(() => setNetworkInterfaces([]))
+  }, [])
+
   const createTestSender = async () => {
     try {
       setError(null)
@@ -425,7 +604,7 @@ function PreviewTestPanel({
         // Ignore error if no sender exists
       }
       
-      await invoke('create_test_sender', { universe })
+      await invoke('create_test_sender', { universe, priority, preview, destination, loopback, interfaceIp, ttl: ttl === '' ? null : parseInt(ttl) })
       setTestSenderActive(true)
       setSuccess('Test sender created!')
       setTimeout(() => setSuccess(null), 2000)
@@ -456,20 +635,13 @@ function PreviewTestPanel({
     
     try {
       setError(null)
-      // Send data for monitor 1
-      await invoke('send_test_three_channels', {
-        startChannel: monitor1Start,
-        clipValue: m1Video,
-        dimmerValue: m1Dimmer,
-        playtypeValue: m1Mode
-      })
-      // Send data for monitor 2
-      await invoke('send_test_three_channels', {
-        startChannel: monitor2Start,
-        clipValue: m2Video,
-        dimmerValue: m2Dimmer,
-        playtypeValue: m2Mode
-      })
+      // Both monitors in one frame, on whatever channels they are patched to
+      const values: number[] = new Array(512).fill(0)
+      const setLevels = (channels: [number, number, number], levels: number[]) =>
+        channels.forEach((channel, i) => { values[channel - 1] = levels[i] })
+      setLevels(monitor1Channels, [m1Video, m1Dimmer, m1Mode])
+      setLevels(monitor2Channels, [m2Video, m2Dimmer, m2Mode])
+      await invoke('send_test_frame', { values, priority, preview, destination })
       // Update the preview values
       onValuesChange(m1Video, m1Dimmer, m1Mode, m2Video, m2Dimmer, m2Mode)
       setSuccess(`Sent all channels`)
@@ -511,6 +683,67 @@ function PreviewTestPanel({
       
       {!isCollapsed && (
         <>
+          <div style={{ display: 'flex', gap: '16px', alignItems: 'center', marginBottom: '12px' }}>
+            <label>
+              Priority{' '}
+              <input
+                type="number"
+                min="0"
+                max="200"
+                value={priority}
+                onChange={(e) => setPriority(Math.min(200, Math.max(0, parseInt(e.target.value) || 0)))}
+                style={{ width: '70px' }}
+              />
+            </label>
+            <label>
+              <input type="checkbox" checked={preview} onChange={(e) => setPreview(e.target.checked)} />
+              {' '}Preview data (receivers should ignore it)
+            </label>
+            <label>
+              Destination IP{' '}
+              <input
+                type="text"
+                value={destination}
+                placeholder="multicast"
+                disabled={loopback}
+                onChange={(e) => setDestination(e.target.value)}
+                style={{ width: '130px' }}
+              />
+            </label>
+            <label title="Hands test packets straight to the running listener, for networks that block multicast loopback">
+              <input type="checkbox" checked={loopback} disabled={testSenderActive} onChange={(e) => setLoopback(e.target.checked)} />
+              {' '}Internal loopback (no network)
+            </label>
+            <label>
+              Send from{' '}
+              <select
+                value={interfaceIp}
+                disabled={loopback || testSenderActive}
+                onChange={(e) => setInterfaceIp(e.target.value)}
+              >
+                <option value="">Any interface</option>
+                {networkInterfaces.map(iface => (
+                  <option key={`${iface.name}-${iface.ip_address}`} value={iface.ip_address}>
+                    {iface.name} ({iface.ip_address})
+                  </option>
+                ))}
+              </select>
+            </label>
+            <label title="Routers the test packets may cross; the default keeps multicast on the local network">
+              TTL{' '}
+              <input
+                type="number"
+                min="1"
+                max="255"
+                value={ttl}
+                placeholder="default"
+                disabled={loopback || testSenderActive}
+                onChange={(e) => setTtl(e.target.value)}
+                style={{ width: '60px' }}
+              />
+            </label>
+          </div>
+
           <div style={{ marginBottom: '16px' }}>
             {!testSenderActive ? (
               <button onClick={createTestSender} className="btn-primary">
@@ -538,7 +771,7 @@ function PreviewTestPanel({
           <div style={{ display: 'grid', gridTemplateColumns: '1fr 1fr', gap: '24px' }}>
             {/* Monitor 1 Controls */}
             <div>
-              <h4 style={{ marginBottom: '12px', color: '#4a9eff' }}>{monitor1Name} (Ch {monitor1Start}-{monitor1Start + 2})</h4>
+              <h4 style={{ marginBottom: '12px', color: '#4a9eff' }}>{monitor1Name} (Ch {monitor1Channels.join('/')})</h4>
               <div className="test-channel-group">
                 <label>
                   <strong>Clip</strong>
@@ -595,9 +828,12 @@ function PreviewTestPanel({
                     onChange={(e) => setM1Mode(parseInt(e.target.value))}
                     style={{ width: '100%', padding: '8px' }}
                   >
-                    <option value="0">0 - Stop</option>
-                    <option value="1">1 - Loop</option>
-                    <option value="128">128 - Play Once</option>
+                    <option value="0">0 - Loop</option>
+                    <option value="64">64 - Pause</option>
+                    <option value="96">96 - Restart</option>
+                    <option value="128">128 - Freeze Last Frame</option>
+                    <option value="160">160 - Play Once</option>
+                    <option value="192">192 - Slideshow</option>
                   </select>
                 </label>
               </div>
@@ -605,7 +841,7 @@ function PreviewTestPanel({
 
             {/* Monitor 2 Controls */}
             <div>
-              <h4 style={{ marginBottom: '12px', color: '#4a9eff' }}>{monitor2Name} (Ch {monitor2Start}-{monitor2Start + 2})</h4>
+              <h4 style={{ marginBottom: '12px', color: '#4a9eff' }}>{monitor2Name} (Ch {monitor2Channels.join('/')})</h4>
               <div className="test-channel-group">
                 <label>
                   <strong>Clip</strong>
@@ -662,9 +898,12 @@ function PreviewTestPanel({
                     onChange={(e) => setM2Mode(parseInt(e.target.value))}
                     style={{ width: '100%', padding: '8px' }}
                   >
-                    <option value="0">0 - Stop</option>
-                    <option value="1">1 - Loop</option>
-                    <option value="128">128 - Play Once</option>
+                    <option value="0">0 - Loop</option>
+                    <option value="64">64 - Pause</option>
+                    <option value="96">96 - Restart</option>
+                    <option value="128">128 - Freeze Last Frame</option>
+                    <option value="160">160 - Play Once</option>
+                    <option value="192">192 - Slideshow</option>
                   </select>
                 </label>
               </div>
@@ -690,6 +929,9 @@ function PreviewTestPanel({
 }
 
 // DMX Section Component
+// Input protocols with their own settings cards
+const BUILTIN_INPUTS = ['sacn', 'artnet']
+
 function DmxSection({ 
   config, 
   saveConfig, 
@@ -701,9 +943,179 @@ function DmxSection({
 }) {
   const [dmxValues, setDmxValues] = useState<Map<number, number>>(new Map())
   const [isListening, setIsListening] = useState(false)
+  const [availableInputs, setAvailableInputs] = useState<AvailableInput[]>([])
   const [lastUpdate, setLastUpdate] = useState<Date | null>(null)
   const [listenerError, setListenerError] = useState<string | null>(null)
   const [showAbout, setShowAbout] = useState(false)
+  const [appInfo, setAppInfo] = useState<AppInfo | null>(null)
+
+  useEffect(() => {
+    if (!showAbout || appInfo) return
+    invoke<AppInfo>('get_app_info')
+      .then(setAppInfo)
+      .catch(err => console.error('Failed to load app info:', err))
+  }, [showAbout])
+
+  // Refreshed when monitoring starts or stops so "running" is current
+  useEffect(() => {
+    invoke<AvailableInput[]>('get_available_inputs')
+      .then(setAvailableInputs)
+      .catch(err => console.error('Failed to list inputs:', err))
+  }, [isListening])
+
+  const copyAppInfo = () => {
+    if (!appInfo) return
+    const text = [
+      `StagePlayer DMX ${appInfo.version}`,
+      `Built: ${new Date(appInfo.build_timestamp * 1000).toISOString()}`,
+      `OS: ${appInfo.os}`,
+      `FFmpeg: ${appInfo.ffmpeg_version ?? 'not found'}`,
+      `sACN library: ${appInfo.sacn_version}`,
+    ].join('\n')
+    navigator.clipboard.writeText(text).catch(err => console.error('Failed to copy:', err))
+  }
+
+  const [selfTestRunning, setSelfTestRunning] = useState(false)
+  const [selfTestReport, setSelfTestReport] = useState<SelfTestReport | null>(null)
+  const [selfTestError, setSelfTestError] = useState('')
+
+  const runSelfTest = async () => {
+    setSelfTestRunning(true)
+    setSelfTestReport(null)
+    setSelfTestError('')
+    try {
+      setSelfTestReport(await invoke<SelfTestReport>('self_test'))
+    } catch (err) {
+      setSelfTestError(String(err))
+    } finally {
+      setSelfTestRunning(false)
+    }
+  }
+
+  const [recording, setRecording] = useState(false)
+  const [replaying, setReplaying] = useState(false)
+  const [replayLooped, setReplayLooped] = useState(false)
+  const [recorderStatus, setRecorderStatus] = useState('')
+
+  // A replay ends on its own; keep the buttons in step with the backend
+  useEffect(() => {
+    const refresh = () => {
+      invoke<[boolean, boolean]>('get_dmx_recorder_status')
+        .then(([isRecording, isReplaying]) => {
+          setRecording(isRecording)
+          setReplaying(isReplaying)
+        })
+        .catch(err => console.error('Failed to get recorder status:', err))
+    }
+    refresh()
+    const timer = setInterval(refresh, 1000)
+    return () => clearInterval(timer)
+  }, [])
+
+  const startRecording = async () => {
+    try {
+      const path = await invoke<string | null>('start_dmx_recording')
+      if (path) {
+        setRecording(true)
+        setRecorderStatus(`Recording to ${path}`)
+      }
+    } catch (err) {
+      setRecorderStatus(`Failed: ${err}`)
+    }
+  }
+
+  const stopRecording = async () => {
+    try {
+      const summary = await invoke<RecordingSummary>('stop_dmx_recording')
+      setRecording(false)
+      setRecorderStatus(`Saved ${summary.frames} frames (${summary.duration_secs.toFixed(1)} s) to ${summary.path}`)
+    } catch (err) {
+      setRecorderStatus(`Failed: ${err}`)
+    }
+  }
+
+  const startReplay = async () => {
+    try {
+      const path = await invoke<string | null>('play_dmx_recording', { looped: replayLooped })
+      if (path) {
+        setReplaying(true)
+        setRecorderStatus(`Replaying ${path}`)
+      }
+    } catch (err) {
+      setRecorderStatus(`Failed: ${err}`)
+    }
+  }
+
+  const stopReplay = async () => {
+    await invoke('stop_dmx_replay').catch(err => console.error('Failed to stop replay:', err))
+    setReplaying(false)
+    setRecorderStatus('Replay stopped')
+  }
+
+  const [snapshotStatus, setSnapshotStatus] = useState('')
+
+  const copyStateSnapshot = async () => {
+    try {
+      const snapshot = await invoke<StateSnapshot>('get_state_snapshot')
+      await navigator.clipboard.writeText(JSON.stringify(snapshot, null, 2))
+      setSnapshotStatus('Copied to clipboard')
+    } catch (err) {
+      setSnapshotStatus(`Failed: ${err}`)
+    }
+  }
+
+  const saveStateSnapshot = async () => {
+    try {
+      const path = await invoke<string | null>('save_state_snapshot')
+      if (path) setSnapshotStatus(`Saved to ${path}`)
+    } catch (err) {
+      setSnapshotStatus(`Failed: ${err}`)
+    }
+  }
+
+  const [discoveredSources, setDiscoveredSources] = useState<DiscoveredSource[]>([])
+  const [sacnStats, setSacnStats] = useState<SacnStats | null>(null)
+  const [activeSources, setActiveSources] = useState<ActiveSource[]>([])
+
+  // The listener reports who is sending our universe every second
+  useEffect(() => {
+    if (!isListening) {
+      setActiveSources([])
+      return
+    }
+    invoke<ActiveSource[]>('get_active_sources')
+      .then(setActiveSources)
+      .catch(err => console.error('Failed to get active sources:', err))
+    const unlisten = listen<ActiveSource[]>('sacn-sources', (event) => setActiveSources(event.payload))
+    return () => { unlisten.then(fn => fn()) }
+  }, [isListening])
+
+  // Discovery announcements arrive every 10 s; refresh while listening
+  useEffect(() => {
+    if (!isListening) {
+      setDiscoveredSources([])
+      setSacnStats(null)
+      return
+    }
+    const refresh = () => {
+      invoke<DiscoveredSource[]>('get_discovered_sources')
+        .then(setDiscoveredSources)
+        .catch(err => console.error('Failed to get discovered sources:', err))
+    }
+    refresh()
+    const timer = setInterval(refresh, 2000)
+    return () => clearInterval(timer)
+  }, [isListening])
+
+  // Packet rate and timing, reported every second
+  useEffect(() => {
+    if (!isListening) return
+    invoke<SacnStats | null>('get_sacn_stats')
+      .then(setSacnStats)
+      .catch(err => console.error('Failed to get sACN stats:', err))
+    const unlisten = listen<SacnStats>('sacn-stats', (event) => setSacnStats(event.payload))
+    return () => { unlisten.then(fn => fn()) }
+  }, [isListening])
 
   // DMX listener for debugging
   useEffect(() => {
@@ -725,11 +1137,11 @@ function DmxSection({
         return
       }
 
-      unlistenFn = await listen('dmx-update', (event: any) => {
-        const update = event.payload as DmxUpdate
+      unlistenFn = await listen<DmxUpdate>('dmx-update', (event) => {
+        const update = event.payload
         setDmxValues(prev => {
           const next = new Map(prev)
-          next.set(update.channel, update.value)
+          for (const [channel, value] of update.channels) next.set(channel, value)
           return next
         })
         setLastUpdate(new Date())
@@ -771,14 +1183,15 @@ function DmxSection({
       <div className="card">
         <h3>Network Interface</h3>
         <select
-          value={config.sacn.network_interface || ''}
+          value={config.sacn.network_interface ? config.sacn.ip_address : ''}
           onChange={(e) => {
-            const selectedInterface = networkInterfaces.find(i => i.name === e.target.value)
+            // Listed per address, so an interface can appear for IPv4 and IPv6
+            const selectedInterface = networkInterfaces.find(i => i.ip_address === e.target.value)
             saveConfig({
               ...config,
               sacn: {
                 ...config.sacn,
-                network_interface: e.target.value,
+                network_interface: selectedInterface?.name || '',
                 ip_address: selectedInterface?.ip_address || ''
               }
             })
@@ -786,7 +1199,7 @@ function DmxSection({
         >
           <option value="">All Interfaces</option>
           {networkInterfaces.map(iface => (
-            <option key={iface.name} value={iface.name}>
+            <option key={`${iface.name}-${iface.ip_address}`} value={iface.ip_address}>
               {iface.name} ({iface.ip_address})
             </option>
           ))}
@@ -805,10 +1218,15 @@ function DmxSection({
             min="1"
             max="63999"
             value={config.sacn.universe}
-            onChange={(e) => saveConfig({
-              ...config,
-              sacn: { ...config.sacn, universe: parseInt(e.target.value) }
-            })}
+            onChange={(e) => {
+              const universe = parseInt(e.target.value)
+              saveConfig({ ...config, sacn: { ...config.sacn, universe } })
+              // A running listener switches over without restarting
+              if (universe >= 1 && universe <= 63999) {
+                invoke('set_listen_universe', { universe })
+                  .catch((err) => setListenerError(String(err)))
+              }
+            }}
           />
         </label>
         
@@ -826,6 +1244,92 @@ function DmxSection({
           </select>
         </label>
 
+        <label>
+          Merge equal priorities:
+          <select
+            value={config.sacn.merge_mode || 'Ltp'}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, merge_mode: e.target.value as MergeMode }
+            })}
+          >
+            <option value="Ltp">LTP (latest change wins)</option>
+            <option value="Htp">HTP (highest level wins)</option>
+          </select>
+        </label>
+
+        <label>
+          When the console stops sending:
+          <select
+            value={config.sacn.on_termination || 'Hold'}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, on_termination: e.target.value as TerminationAction }
+            })}
+          >
+            <option value="Hold">Hold last look</option>
+            <option value="Blackout">Blackout</option>
+          </select>
+        </label>
+
+        <label>
+          When DMX stops arriving:
+          <select
+            value={config.sacn.on_data_loss || 'FallbackClip'}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, on_data_loss: e.target.value as DataLossAction }
+            })}
+          >
+            <option value="FallbackClip">Play fallback clips</option>
+            <option value="Hold">Hold last look</option>
+            <option value="FadeToBlack">Fade to black</option>
+          </select>
+        </label>
+
+        {config.sacn.on_data_loss === 'FadeToBlack' && (
+          <label>
+            Fade time (seconds):
+            <input
+              type="number"
+              min="0"
+              step="0.5"
+              value={config.sacn.data_loss_fade_secs ?? 3}
+              onChange={(e) => saveConfig({
+                ...config,
+                sacn: { ...config.sacn, data_loss_fade_secs: parseFloat(e.target.value) || 0 }
+              })}
+            />
+          </label>
+        )}
+
+        <label title="Noisy wireless DMX can flash a wrong clip for one frame; a new clip value must hold this long before the clip changes. Restart monitoring to apply.">
+          Clip glitch filter: hold
+          <input
+            type="number"
+            min="0"
+            step="10"
+            value={config.sacn.clip_stable_ms ?? 0}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, clip_stable_ms: Math.max(0, parseInt(e.target.value) || 0) }
+            })}
+            style={{ width: '70px', margin: '0 6px' }}
+          />
+          ms over
+          <input
+            type="number"
+            min="1"
+            value={config.sacn.clip_stable_frames ?? 1}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, clip_stable_frames: Math.max(1, parseInt(e.target.value) || 1) }
+            })}
+            style={{ width: '50px', margin: '0 6px' }}
+          />
+          frame(s)
+        </label>
+
         {config.sacn.mode === 'Unicast' && (
           <label>
             Unicast IP:
@@ -840,31 +1344,194 @@ function DmxSection({
             />
           </label>
         )}
+
+        {config.sacn.mode === 'Unicast' && (
+          <label>
+            Allowed sources:
+            <input
+              type="text"
+              placeholder="Any sender (e.g. 10.0.0.5, 10.0.0.6)"
+              defaultValue={(config.sacn.allowed_sources || []).join(', ')}
+              onBlur={(e) => saveConfig({
+                ...config,
+                sacn: {
+                  ...config.sacn,
+                  allowed_sources: e.target.value.split(',').map(ip => ip.trim()).filter(ip => ip !== '')
+                }
+              })}
+            />
+          </label>
+        )}
       </div>
 
       <div className="card">
-        <h3>DMX Monitor</h3>
-        <p className="info">Debug incoming DMX data on Universe {config.sacn.universe}</p>
-        
-        <div style={{ marginTop: '20px', marginBottom: '16px' }}>
-          {!isListening ? (
-            <button onClick={startListener} className="btn-primary">
-              Start Monitoring
-            </button>
-          ) : (
-            <button onClick={stopListener} className="btn-secondary">
-              Stop Monitoring
-            </button>
-          )}
-        </div>
+        <h3>Art-Net</h3>
+        <label>
+          <input
+            type="checkbox"
+            checked={(config.sacn.extra_inputs || []).includes('artnet')}
+            onChange={(e) => {
+              const others = (config.sacn.extra_inputs || []).filter(name => name !== 'artnet')
+              saveConfig({
+                ...config,
+                sacn: { ...config.sacn, extra_inputs: e.target.checked ? [...others, 'artnet'] : others }
+              })
+            }}
+          />
+          Also receive Art-Net
+        </label>
 
-        {listenerError && (
-          <div className="error-message">
-            <strong>Error:</strong> {listenerError}
-          </div>
-        )}
+        <label title="Art-Net is ignored while sACN arrives and takes over when sACN goes silent">
+          <input
+            type="checkbox"
+            checked={config.sacn.failover_input === 'artnet'}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, failover_input: e.target.checked ? 'artnet' : '' }
+            })}
+          />
+          Art-Net as backup only, after sACN is silent for
+          <input
+            type="number"
+            min="0.5"
+            step="0.5"
+            value={config.sacn.failover_timeout_secs ?? 3}
+            onChange={(e) => saveConfig({
+              ...config,
+              sacn: { ...config.sacn, failover_timeout_secs: Math.max(0.5, parseFloat(e.target.value) || 3) }
+            })}
+            style={{ width: '60px', margin: '0 6px' }}
+          />
+          s
+        </label>
 
-        {isListening && (
+        {((config.sacn.extra_inputs || []).includes('artnet') || config.sacn.failover_input === 'artnet') && (
+          <label>
+            Port-Address:
+            <input
+              type="number"
+              min="0"
+              max="32767"
+              value={config.sacn.artnet_universe ?? 0}
+              onChange={(e) => saveConfig({
+                ...config,
+                sacn: { ...config.sacn, artnet_universe: parseInt(e.target.value) || 0 }
+              })}
+            />
+          </label>
+        )}
+        <p className="info">ArtDMX on UDP port 6454 drives the player like sACN Universe {config.sacn.universe}</p>
+      </div>
+
+      {availableInputs.some(input => !BUILTIN_INPUTS.includes(input.name)) && (
+        <div className="card">
+          <h3>Other Inputs</h3>
+          {availableInputs.filter(input => !BUILTIN_INPUTS.includes(input.name)).map(input => (
+            <label key={input.name}>
+              <input
+                type="checkbox"
+                checked={(config.sacn.extra_inputs || []).includes(input.name)}
+                onChange={(e) => {
+                  const others = (config.sacn.extra_inputs || []).filter(name => name !== input.name)
+                  saveConfig({
+                    ...config,
+                    sacn: { ...config.sacn, extra_inputs: e.target.checked ? [...others, input.name] : others }
+                  })
+                }}
+              />
+              Also receive {input.name} {input.running && <span style={{ color: '#0f0' }}>(running)</span>}
+            </label>
+          ))}
+          <p className="info">Input protocols added by plugins; they drive the player like sACN Universe {config.sacn.universe}. Restart monitoring to apply.</p>
+        </div>
+      )}
+
+      <div className="card">
+        <h3>Feedback to Console</h3>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.feedback.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              feedback: { ...config.feedback, enabled: e.target.checked }
+            })}
+          />
+          Send player status over sACN
+        </label>
+
+        {config.feedback.enabled && (
+          <>
+            <label>
+              Universe:
+              <input
+                type="number"
+                min="1"
+                max="63999"
+                value={config.feedback.universe}
+                onChange={(e) => saveConfig({
+                  ...config,
+                  feedback: { ...config.feedback, universe: parseInt(e.target.value) || 1 }
+                })}
+              />
+            </label>
+            <label>
+              Start Channel:
+              <input
+                type="number"
+                min="1"
+                max="497"
+                value={config.feedback.start_channel}
+                onChange={(e) => saveConfig({
+                  ...config,
+                  feedback: { ...config.feedback, start_channel: parseInt(e.target.value) || 1 }
+                })}
+              />
+            </label>
+            <label>
+              Priority:
+              <input
+                type="number"
+                min="0"
+                max="200"
+                value={config.feedback.priority}
+                onChange={(e) => saveConfig({
+                  ...config,
+                  feedback: { ...config.feedback, priority: parseInt(e.target.value) || 0 }
+                })}
+              />
+            </label>
+            {config.feedback.universe === config.sacn.universe && (
+              <p className="info" style={{ color: '#fc6' }}>The feedback universe can't be the listened universe; nothing is sent.</p>
+            )}
+          </>
+        )}
+        <p className="info">8 channels per monitor from the start channel: clip, dimmer, playtype, progress, elapsed seconds (16-bit) and health.</p>
+      </div>
+
+      <div className="card">
+        <h3>DMX Monitor</h3>
+        <p className="info">Debug incoming DMX data on Universe {config.sacn.universe}</p>
+        
+        <div style={{ marginTop: '20px', marginBottom: '16px' }}>
+          {!isListening ? (
+            <button onClick={startListener} className="btn-primary">
+              Start Monitoring
+            </button>
+          ) : (
+            <button onClick={stopListener} className="btn-secondary">
+              Stop Monitoring
+            </button>
+          )}
+        </div>
+
+        {listenerError && (
+          <div className="error-message">
+            <strong>Error:</strong> {listenerError}
+          </div>
+        )}
+
+        {isListening && (
           <>
             <div className="dmx-monitor-status">
               <span>Status: <strong style={{ color: '#0f0' }}>Listening</strong></span>
@@ -872,8 +1539,33 @@ function DmxSection({
                 <span>Last Update: {lastUpdate.toLocaleTimeString()}</span>
               )}
               <span>Active Channels: {activeChannels.length}</span>
+              {sacnStats && (
+                <span>Out of Sequence: {sacnStats.out_of_sequence} / {sacnStats.packets}</span>
+              )}
+              {sacnStats && (
+                <span title={`Longest gap ${sacnStats.max_gap_ms.toFixed(0)} ms, jitter ${sacnStats.jitter_ms.toFixed(1)} ms, ${sacnStats.sequence_gaps} packets lost`}>
+                  Rate:{' '}
+                  <strong style={{ color: sacnStats.secs_since_last_packet === null || sacnStats.secs_since_last_packet > 1 ? '#f66' : sacnStats.max_gap_ms > 1500 ? '#fa0' : '#0f0' }}>
+                    {sacnStats.packets_per_sec.toFixed(1)} packets/s
+                  </strong>
+                </span>
+              )}
+              {sacnStats && sacnStats.rejected > 0 && (
+                <span>Rejected: <strong style={{ color: '#f66' }}>{sacnStats.rejected}</strong></span>
+              )}
             </div>
 
+            {activeSources.length > 0 && (
+              <div style={{ margin: '0 0 12px', fontSize: '13px' }}>
+                {activeSources.map(source => (
+                  <div key={source.cid} style={{ padding: '4px 0', color: '#ccc' }}>
+                    <strong>{source.name || 'Unnamed source'}</strong> ({source.ip}) · priority {source.priority} · {source.packets_per_sec.toFixed(1)} packets/s ({source.packets} total)
+                    <span style={{ color: '#888', marginLeft: '8px' }}>CID {source.cid}</span>
+                  </div>
+                ))}
+              </div>
+            )}
+
             <div className="dmx-channel-grid">
               {activeChannels.length === 0 ? (
                 <div className="dmx-no-data">
@@ -901,6 +1593,81 @@ function DmxSection({
         )}
       </div>
 
+      <div className="card">
+        <h3>Sources on the Network</h3>
+        <p className="info">Consoles announcing themselves with sACN universe discovery, on any universe. Start monitoring to listen.</p>
+        {isListening && discoveredSources.length === 0 && (
+          <p style={{ color: '#888', fontSize: '13px' }}>No sources announced yet (announcements come every 10 seconds)</p>
+        )}
+        {discoveredSources.map(source => (
+          <div key={source.cid} style={{ padding: '8px 0', borderBottom: '1px solid #333', fontSize: '13px' }}>
+            <div>
+              <strong>{source.name || 'Unnamed source'}</strong> ({source.ip})
+              {source.universes.includes(config.sacn.universe) && (
+                <span style={{ color: '#0f0', marginLeft: '8px' }}>sending our universe</span>
+              )}
+            </div>
+            <div style={{ color: '#888' }}>CID {source.cid}</div>
+            <div style={{ color: '#aaa' }}>
+              Universes: {source.universes.length > 0 ? source.universes.join(', ') : 'none'}
+            </div>
+          </div>
+        ))}
+      </div>
+
+      <div className="card">
+        <h3>DMX Recorder</h3>
+        <p className="info">Record the incoming DMX with its timing, then replay it through the player for rehearsals without the console. Replayed levels drive the outputs like live sACN while production output is running.</p>
+        <div style={{ display: 'flex', gap: '8px', flexWrap: 'wrap' }}>
+          {!recording ? (
+            <button type="button" className="browse-button" onClick={startRecording}>Record…</button>
+          ) : (
+            <button type="button" className="browse-button" onClick={stopRecording}>Stop Recording</button>
+          )}
+          {!replaying ? (
+            <button type="button" className="browse-button" onClick={startReplay}>Replay…</button>
+          ) : (
+            <button type="button" className="browse-button" onClick={stopReplay}>Stop Replay</button>
+          )}
+          <label style={{ display: 'flex', alignItems: 'center', gap: '6px' }}>
+            <input type="checkbox" checked={replayLooped} onChange={(e) => setReplayLooped(e.target.checked)} />
+            Loop
+          </label>
+        </div>
+        {recorderStatus && <p style={{ color: '#aaa', fontSize: '13px' }}>{recorderStatus}</p>}
+      </div>
+
+      <div className="card">
+        <h3>Self-Test</h3>
+        <p className="info">Sends a short test sequence on the configured universe, checks it is received, and checks each monitor's clip channel finds a file. Stop the listener first.</p>
+        <button type="button" className="browse-button" onClick={runSelfTest} disabled={selfTestRunning || isListening}>
+          {selfTestRunning ? 'Running…' : 'Run Self-Test'}
+        </button>
+        {selfTestError && <p style={{ color: '#f88', fontSize: '13px' }}>{selfTestError}</p>}
+        {selfTestReport && (
+          <div style={{ marginTop: '12px', fontSize: '13px', lineHeight: '1.7' }}>
+            <div style={{ color: selfTestReport.passed ? '#5f5' : '#f88', fontWeight: 600 }}>
+              {selfTestReport.passed ? '✓ Passed' : '✗ Failed'}
+            </div>
+            {selfTestReport.steps.map((step, i) => (
+              <div key={i} style={{ color: step.passed ? '#aaa' : '#f88' }}>
+                {step.passed ? '✓' : '✗'} {step.name}: {step.detail}
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+
+      <div className="card">
+        <h3>State Snapshot</h3>
+        <p className="info">Captures each monitor's clip, dimmer, playtype and position, the listener status and the last DMX frame as JSON, for support and show reports.</p>
+        <div style={{ display: 'flex', gap: '8px' }}>
+          <button type="button" className="browse-button" onClick={copyStateSnapshot}>Copy State</button>
+          <button type="button" className="browse-button" onClick={saveStateSnapshot}>Save State…</button>
+        </div>
+        {snapshotStatus && <p style={{ color: '#aaa', fontSize: '13px' }}>{snapshotStatus}</p>}
+      </div>
+
       <div className="card" style={{ marginTop: '8px' }}>
         <button
           onClick={() => setShowAbout(true)}
@@ -927,11 +1694,25 @@ function DmxSection({
             }}
           >
             <img src="/logo.png" alt="StagePlayer DMX" style={{ width: '80%', maxWidth: '280px', marginBottom: '8px' }} />
-            <p style={{ color: '#888', fontSize: '12px', margin: '0 0 12px' }}>Version {version}</p>
+            <p style={{ color: '#888', fontSize: '12px', margin: '0 0 12px' }}>Version {appInfo?.version ?? '…'}</p>
+            {appInfo && (
+              <p style={{ color: '#666', fontSize: '12px', lineHeight: '1.6', margin: '0 0 12px' }}>
+                Built {appInfo.build_timestamp ? new Date(appInfo.build_timestamp * 1000).toLocaleDateString() : 'unknown'} · {appInfo.os}<br />
+                FFmpeg {appInfo.ffmpeg_version ?? 'not found'} · sACN {appInfo.sacn_version}
+              </p>
+            )}
             <p style={{ color: '#ccc', fontSize: '14px', lineHeight: '1.7', margin: 0 }}>
               © 2026 Brad Boyink. All rights reserved.<br />
               Free for use in schools and community theaters.
             </p>
+            <button
+              onClick={copyAppInfo}
+              className="btn-secondary"
+              style={{ marginTop: '24px', marginRight: '8px', padding: '8px 16px' }}
+              disabled={!appInfo}
+            >
+              Copy Details
+            </button>
             <button
               onClick={() => setShowAbout(false)}
               className="btn-secondary"
@@ -958,7 +1739,7 @@ function ConfigSection({
 }) {
   const browseFolderMonitor1 = async () => {
     try {
-      const selected = await invoke<string | null>('select_folder')
+      const selected = await invoke<string | null>('select_folder', { purpose: 'Media' })
       console.log('Selected folder:', selected)
       if (selected) {
         saveConfig({
@@ -973,7 +1754,7 @@ function ConfigSection({
 
   const browseFolderMonitor2 = async () => {
     try {
-      const selected = await invoke<string | null>('select_folder')
+      const selected = await invoke<string | null>('select_folder', { purpose: 'Media' })
       console.log('Selected folder:', selected)
       if (selected) {
         saveConfig({
@@ -988,7 +1769,7 @@ function ConfigSection({
 
   const browseFolderPresentation = async () => {
     try {
-      const selected = await invoke<string | null>('select_folder')
+      const selected = await invoke<string | null>('select_folder', { purpose: 'Presentation' })
       if (selected) {
         saveConfig({ ...config, presentation_folder: selected })
       }
@@ -999,7 +1780,7 @@ function ConfigSection({
 
   const browseFolderConvert = async () => {
     try {
-      const selected = await invoke<string | null>('select_folder')
+      const selected = await invoke<string | null>('select_folder', { purpose: 'Convert' })
       if (selected) {
         saveConfig({ ...config, convert_folder: selected })
       }
@@ -1008,11 +1789,142 @@ function ConfigSection({
     }
   }
 
+  const openInExplorer = async (path: string) => {
+    try {
+      await invoke('open_in_explorer', { path })
+    } catch (error) {
+      alert('Failed to open folder: ' + error)
+    }
+  }
+
+  const [autostart, setAutostart] = useState(false)
+
+  useEffect(() => {
+    invoke<boolean>('get_autostart').then(setAutostart).catch(() => {})
+  }, [])
+
+  const [updateStatus, setUpdateStatus] = useState('')
+  const [availableUpdate, setAvailableUpdate] = useState<UpdateInfo | null>(null)
+
+  const checkForUpdates = async () => {
+    setUpdateStatus('Checking…')
+    try {
+      const update = await invoke<UpdateInfo | null>('check_for_updates')
+      setAvailableUpdate(update)
+      setUpdateStatus(update ? `Version ${update.version} is available` : 'Up to date')
+    } catch (err) {
+      setUpdateStatus(String(err))
+    }
+  }
+
+  const installUpdate = async () => {
+    setUpdateStatus('Installing… the app will restart')
+    try {
+      await invoke('install_update')
+    } catch (err) {
+      setUpdateStatus(String(err))
+    }
+  }
+
+  const [cueState, setCueState] = useState<CueState | null>(null)
+
+  useEffect(() => {
+    invoke<CueState>('get_cue_state').then(setCueState).catch(() => {})
+    const unlisten = listen<CueState>('cue-changed', (event) => setCueState(event.payload))
+    return () => { unlisten.then(fn => fn()) }
+  }, [])
+
+  const saveCues = (cues: Cue[]) => saveConfig({ ...config, cue_stack: { ...config.cue_stack, cues } })
+
+  const updateCue = (index: number, cue: Cue) => {
+    saveCues(config.cue_stack.cues.map((c, i) => i === index ? cue : c))
+  }
+
+  const addCue = () => {
+    saveCues([...config.cue_stack.cues, {
+      name: `Cue ${config.cue_stack.cues.length + 1}`,
+      monitor1: { clip: 1, dimmer: 255, playtype: 0 },
+      monitor2: null,
+      fade_out_ms: 0,
+      fade_in_ms: 1000
+    }])
+  }
+
+  const cueGo = () => invoke('cue_go').catch(err => alert('GO failed: ' + err))
+
+  const [setupWindows, setSetupWindows] = useState(false)
+
+  const toggleSetupWindows = async (windowed: boolean) => {
+    try {
+      await invoke('set_output_setup_mode', { windowed })
+      setSetupWindows(windowed)
+    } catch (err) {
+      alert('Failed to switch output windows: ' + err)
+    }
+  }
+
+  const [timelineState, setTimelineState] = useState<TimelineState | null>(null)
+
+  useEffect(() => {
+    invoke<TimelineState>('get_timeline_state').then(setTimelineState).catch(() => {})
+    const unlisten = listen<TimelineState>('timeline-state', (event) => setTimelineState(event.payload))
+    return () => { unlisten.then(fn => fn()) }
+  }, [])
+
+  // The clock runs in the backend; refresh the position readout while it plays
+  useEffect(() => {
+    if (!timelineState?.playing) return
+    const timer = setInterval(() => {
+      invoke<TimelineState>('get_timeline_state').then(setTimelineState).catch(() => {})
+    }, 250)
+    return () => clearInterval(timer)
+  }, [timelineState?.playing])
+
+  // Blank clip field = leave that monitor as it is
+  const lookInputs = (cue: Cue, index: number, key: 'monitor1' | 'monitor2') => {
+    const look = cue[key]
+    const setLook = (next: CueLook | null) => updateCue(index, { ...cue, [key]: next })
+    // Clips follow the monitor's clip channel, which may be 16-bit
+    const numberInput = (field: keyof CueLook) => {
+      const max = field === 'clip' && config[key].clip_16bit ? 65535 : 255
+      return (
+        <input
+          type="number"
+          min="0"
+          max={max}
+          style={{ width: '56px' }}
+          value={look ? look[field] : ''}
+          disabled={!look}
+          onChange={(e) => look && setLook({ ...look, [field]: Math.max(0, Math.min(max, parseInt(e.target.value) || 0)) })}
+        />
+      )
+    }
+    return (
+      <td>
+        <input
+          type="checkbox"
+          checked={look !== null}
+          onChange={(e) => setLook(e.target.checked ? { clip: 1, dimmer: 255, playtype: 0 } : null)}
+        />
+        {numberInput('clip')} {numberInput('dimmer')} {numberInput('playtype')}
+      </td>
+    )
+  }
+
+  const toggleAutostart = async (enabled: boolean) => {
+    try {
+      await invoke('set_autostart', { enabled })
+      setAutostart(enabled)
+    } catch (err) {
+      alert('Failed to change auto-start: ' + err)
+    }
+  }
+
   return (
     <div className="section">
       <h2>Monitor Configuration</h2>
       
-      <div className="card">
+      <div className="card" data-drop-monitor="monitor1">
         <h3>
           <EditableName 
             name={config.monitor1.name}
@@ -1048,168 +1960,753 @@ function ConfigSection({
           />
         </label>
         
+        <label title="Clip selected by a coarse/fine channel pair; plays files named 00261_name.ext">
+          <input
+            type="checkbox"
+            checked={config.monitor1.clip_16bit}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, clip_16bit: e.target.checked }
+            })}
+          />
+          16-bit clip selection (Ch {getClipChannel(config.monitor1)}/{getClipFineChannel(config.monitor1)}: coarse/fine)
+        </label>
+        
         <label>
-          Output Display:
-          <select
-            className="display-select"
-            value={config.monitor1.display_index}
+          <input
+            type="checkbox"
+            checked={config.monitor1.volume_control}
             onChange={(e) => saveConfig({
               ...config,
-              monitor1: { 
-                ...config.monitor1, 
-                display_index: parseInt(e.target.value),
-                window_x: null,
-                window_y: null
-              }
+              monitor1: { ...config.monitor1, volume_control: e.target.checked }
             })}
-          >
-            {availableDisplays.map((display) => (
-              <option key={display.index} value={display.index}>
-                {display.name} ({display.width}×{display.height}){display.is_primary ? ' - Primary' : ''}
-              </option>
-            ))}
-          </select>
+          />
+          Volume channel (Ch {getVolumeChannel(config.monitor1)})
+        </label>
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor1.ken_burns}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, ken_burns: e.target.checked }
+            })}
+          />
+          Ken Burns channels (Ch {getPanChannel(config.monitor1)}/{getZoomChannel(config.monitor1)}/{getSpeedChannel(config.monitor1)}: pan, zoom, speed)
+        </label>
+        
+        <ChannelPatch
+          monitor={config.monitor1}
+          onChange={(channel_map) => saveConfig({ ...config, monitor1: { ...config.monitor1, channel_map } })}
+        />
+        
+        <label title="Seek the playing clip to follow incoming timecode (Timecode settings in the config file)">
+          <input
+            type="checkbox"
+            checked={config.monitor1.chase_timecode}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, chase_timecode: e.target.checked }
+            })}
+          />
+          Chase timecode
+        </label>
+        
+        {config.monitor1.chase_timecode && (
+          <label>
+            Clip starts at timecode (seconds):
+            <input
+              type="number"
+              min="0"
+              step="0.04"
+              value={config.monitor1.timecode_offset_secs}
+              onChange={(e) => saveConfig({
+                ...config,
+                monitor1: { ...config.monitor1, timecode_offset_secs: Math.max(0, parseFloat(e.target.value) || 0) }
+              })}
+            />
+          </label>
+        )}
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor1.pixel_map.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, pixel_map: { ...config.monitor1.pixel_map, enabled: e.target.checked } }
+            })}
+          />
+          Pixel map output ({config.monitor1.pixel_map.regions.length} regions)
+        </label>
+        
+        <label title="Windows only: Resolume, TouchDesigner and other Spout receivers on this machine can take the picture">
+          <input
+            type="checkbox"
+            checked={config.monitor1.spout.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, spout: { ...config.monitor1.spout, enabled: e.target.checked } }
+            })}
+          />
+          Spout output
+        </label>
+        
+        {config.monitor1.spout.enabled && (
+          <label>
+            Spout Sender Name:
+            <input
+              type="text"
+              value={config.monitor1.spout.sender_name}
+              placeholder={`StagePlayer DMX ${config.monitor1.name}`}
+              onChange={(e) => saveConfig({
+                ...config,
+                monitor1: { ...config.monitor1, spout: { ...config.monitor1.spout, sender_name: e.target.value } }
+              })}
+            />
+          </label>
+        )}
+        
+        <label>
+          Effects Channel (pixelate, chroma shift, vignette; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor1.effects_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, effects_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Crossfade Channel (clip change transition time; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.crossfade_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, crossfade_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label title="Used when the crossfade channel is off and the clip has no transition_ms of its own">
+          Clip Crossfade (ms; 0 = cut):
+          <input
+            type="number"
+            min="0"
+            max="10000"
+            step="100"
+            value={config.monitor1.clip_crossfade_ms}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, clip_crossfade_ms: Math.max(0, Math.min(10000, parseInt(e.target.value) || 0)) }
+            })}
+          />
+        </label>
+        
+        <label>
+          Position Channel (X offset, Y offset, scale; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor1.position_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, position_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Rotation Channel (overrides orientation; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.rotation_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, rotation_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Tint Channel (R, G, B from here; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor1.tint_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, tint_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Strobe Channel (1-255 = 1-25 Hz; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.strobe_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, strobe_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Bank Channel (selects a ###_name subfolder; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.bank_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, bank_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Preload Channel (buffers the clip it names, to select next; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.preload_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, preload_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Dimming Curve:
+          <select
+            value={config.monitor1.dimming_curve}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, dimming_curve: e.target.value as DimmingCurve }
+            })}
+          >
+            <option value="Linear">Linear</option>
+            <option value="Gamma">Perceptual (gamma 2.2)</option>
+            <option value="Cie">Perceptual (CIE 1931)</option>
+          </select>
+        </label>
+        
+        <label title="Eases stepped console dimmer fades; the output reaches about 63% of each change within this time">
+          Dimmer Smoothing (ms; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="5000"
+            step="10"
+            value={config.monitor1.dimmer_smoothing_ms}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, dimmer_smoothing_ms: Math.max(0, Math.min(5000, parseInt(e.target.value) || 0)) }
+            })}
+          />
+        </label>
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor1.subtitles.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, subtitles: { ...config.monitor1.subtitles, enabled: e.target.checked } }
+            })}
+          />
+          Show subtitles from .srt / .vtt files
+        </label>
+        
+        <label>
+          Subtitle Channel (on at 50%; 0 = always on):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor1.subtitles.channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor1: { ...config.monitor1, subtitles: { ...config.monitor1.subtitles, channel: channel || null } } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Output Display:
+          <select
+            className="display-select"
+            value={config.monitor1.display_index}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { 
+                ...config.monitor1, 
+                display_index: parseInt(e.target.value),
+                window_x: null,
+                window_y: null
+              }
+            })}
+          >
+            {availableDisplays.map((display) => (
+              <option key={display.index} value={display.index}>
+                {display.name} ({display.width}×{display.height}){display.is_primary ? ' - Primary' : ''}
+              </option>
+            ))}
+          </select>
+        </label>
+        
+        <label>
+          Media Folder:
+          <div className="folder-input-group">
+            <input
+              type="text"
+              value={config.monitor1.media_folder}
+              onChange={(e) => saveConfig({
+                ...config,
+                monitor1: { ...config.monitor1, media_folder: e.target.value }
+              })}
+            />
+            <button type="button" className="browse-button" onClick={browseFolderMonitor1}>
+              Browse
+            </button>
+            <button type="button" className="browse-button" onClick={() => openInExplorer(config.monitor1.media_folder)} disabled={!config.monitor1.media_folder}>
+              Open
+            </button>
+          </div>
+        </label>
+        
+        <label>
+          Resolution:
+          <select
+            value={config.monitor1.resolution.type}
+            onChange={(e) => {
+              const type = e.target.value as 'HD' | 'FourK' | 'Custom'
+              let newResolution: typeof config.monitor1.resolution
+              if (type === 'HD') {
+                newResolution = { type: 'HD', width: 1920, height: 1080 }
+              } else if (type === 'FourK') {
+                newResolution = { type: 'FourK', width: 3840, height: 2160 }
+              } else {
+                // Keep existing custom values or default to HD resolution
+                const currentWidth = config.monitor1.resolution.width || 1920
+                const currentHeight = config.monitor1.resolution.height || 1080
+                newResolution = { type: 'Custom', width: currentWidth, height: currentHeight }
+              }
+              saveConfig({
+                ...config,
+                monitor1: { ...config.monitor1, resolution: newResolution }
+              })
+            }}
+          >
+            <option value="HD">HD (1920×1080)</option>
+            <option value="FourK">4K (3840×2160)</option>
+            <option value="Custom">Custom</option>
+          </select>
+        </label>
+        
+        {config.monitor1.resolution.type === 'Custom' && (
+          <>
+            <label>
+              Width:
+              <input
+                type="number"
+                min="1"
+                max="7680"
+                value={config.monitor1.resolution.width}
+                onChange={(e) => saveConfig({
+                  ...config,
+                  monitor1: {
+                    ...config.monitor1,
+                    resolution: {
+                      type: 'Custom',
+                      width: parseInt(e.target.value) || 1920,
+                      height: config.monitor1.resolution.height
+                    }
+                  }
+                })}
+              />
+            </label>
+            <label>
+              Height:
+              <input
+                type="number"
+                min="1"
+                max="4320"
+                value={config.monitor1.resolution.height}
+                onChange={(e) => saveConfig({
+                  ...config,
+                  monitor1: {
+                    ...config.monitor1,
+                    resolution: {
+                      type: 'Custom',
+                      width: config.monitor1.resolution.width,
+                      height: parseInt(e.target.value) || 1080
+                    }
+                  }
+                })}
+              />
+            </label>
+          </>
+        )}
+        
+        <label>
+          Orientation:
+          <select
+            value={config.monitor1.orientation}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor1: { ...config.monitor1, orientation: e.target.value as 'Horizontal' | 'Vertical' }
+            })}
+          >
+            <option value="Horizontal">Horizontal</option>
+            <option value="Vertical">Vertical</option>
+          </select>
+        </label>
+      </div>
+
+      <div className="card" data-drop-monitor="monitor2">
+        <h3>
+          <EditableName 
+            name={config.monitor2.name}
+            onSave={(newName) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, name: newName }
+            })}
+          />
+        </h3>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor2.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, enabled: e.target.checked }
+            })}
+          />
+          Enabled
+        </label>
+        
+        <label>
+          Start Channel:
+          <input
+            type="number"
+            min="1"
+            max="510"
+            value={config.monitor2.start_channel}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, start_channel: parseInt(e.target.value) }
+            })}
+          />
+        </label>
+        
+        <label title="Clip selected by a coarse/fine channel pair; plays files named 00261_name.ext">
+          <input
+            type="checkbox"
+            checked={config.monitor2.clip_16bit}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, clip_16bit: e.target.checked }
+            })}
+          />
+          16-bit clip selection (Ch {getClipChannel(config.monitor2)}/{getClipFineChannel(config.monitor2)}: coarse/fine)
+        </label>
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor2.volume_control}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, volume_control: e.target.checked }
+            })}
+          />
+          Volume channel (Ch {getVolumeChannel(config.monitor2)})
+        </label>
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor2.ken_burns}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, ken_burns: e.target.checked }
+            })}
+          />
+          Ken Burns channels (Ch {getPanChannel(config.monitor2)}/{getZoomChannel(config.monitor2)}/{getSpeedChannel(config.monitor2)}: pan, zoom, speed)
+        </label>
+        
+        <ChannelPatch
+          monitor={config.monitor2}
+          onChange={(channel_map) => saveConfig({ ...config, monitor2: { ...config.monitor2, channel_map } })}
+        />
+        
+        <label title="Seek the playing clip to follow incoming timecode (Timecode settings in the config file)">
+          <input
+            type="checkbox"
+            checked={config.monitor2.chase_timecode}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, chase_timecode: e.target.checked }
+            })}
+          />
+          Chase timecode
+        </label>
+        
+        {config.monitor2.chase_timecode && (
+          <label>
+            Clip starts at timecode (seconds):
+            <input
+              type="number"
+              min="0"
+              step="0.04"
+              value={config.monitor2.timecode_offset_secs}
+              onChange={(e) => saveConfig({
+                ...config,
+                monitor2: { ...config.monitor2, timecode_offset_secs: Math.max(0, parseFloat(e.target.value) || 0) }
+              })}
+            />
+          </label>
+        )}
+        
+        <label>
+          <input
+            type="checkbox"
+            checked={config.monitor2.pixel_map.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, pixel_map: { ...config.monitor2.pixel_map, enabled: e.target.checked } }
+            })}
+          />
+          Pixel map output ({config.monitor2.pixel_map.regions.length} regions)
+        </label>
+        
+        <label title="Windows only: Resolume, TouchDesigner and other Spout receivers on this machine can take the picture">
+          <input
+            type="checkbox"
+            checked={config.monitor2.spout.enabled}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, spout: { ...config.monitor2.spout, enabled: e.target.checked } }
+            })}
+          />
+          Spout output
+        </label>
+        
+        {config.monitor2.spout.enabled && (
+          <label>
+            Spout Sender Name:
+            <input
+              type="text"
+              value={config.monitor2.spout.sender_name}
+              placeholder={`StagePlayer DMX ${config.monitor2.name}`}
+              onChange={(e) => saveConfig({
+                ...config,
+                monitor2: { ...config.monitor2, spout: { ...config.monitor2.spout, sender_name: e.target.value } }
+              })}
+            />
+          </label>
+        )}
+        
+        <label>
+          Effects Channel (pixelate, chroma shift, vignette; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor2.effects_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, effects_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Crossfade Channel (clip change transition time; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor2.crossfade_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, crossfade_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label title="Used when the crossfade channel is off and the clip has no transition_ms of its own">
+          Clip Crossfade (ms; 0 = cut):
+          <input
+            type="number"
+            min="0"
+            max="10000"
+            step="100"
+            value={config.monitor2.clip_crossfade_ms}
+            onChange={(e) => saveConfig({
+              ...config,
+              monitor2: { ...config.monitor2, clip_crossfade_ms: Math.max(0, Math.min(10000, parseInt(e.target.value) || 0)) }
+            })}
+          />
+        </label>
+        
+        <label>
+          Position Channel (X offset, Y offset, scale; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor2.position_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, position_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Rotation Channel (overrides orientation; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor2.rotation_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, rotation_channel: channel || null } })
+            }}
+          />
+        </label>
+        
+        <label>
+          Tint Channel (R, G, B from here; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="510"
+            value={config.monitor2.tint_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(510, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, tint_channel: channel || null } })
+            }}
+          />
         </label>
         
         <label>
-          Media Folder:
-          <div className="folder-input-group">
-            <input
-              type="text"
-              value={config.monitor1.media_folder}
-              onChange={(e) => saveConfig({
-                ...config,
-                monitor1: { ...config.monitor1, media_folder: e.target.value }
-              })}
-            />
-            <button type="button" className="browse-button" onClick={browseFolderMonitor1}>
-              Browse
-            </button>
-          </div>
+          Strobe Channel (1-255 = 1-25 Hz; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor2.strobe_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, strobe_channel: channel || null } })
+            }}
+          />
         </label>
         
         <label>
-          Resolution:
-          <select
-            value={config.monitor1.resolution.type}
+          Bank Channel (selects a ###_name subfolder; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor2.bank_channel ?? 0}
             onChange={(e) => {
-              const type = e.target.value as 'HD' | 'FourK' | 'Custom'
-              let newResolution: typeof config.monitor1.resolution
-              if (type === 'HD') {
-                newResolution = { type: 'HD', width: 1920, height: 1080 }
-              } else if (type === 'FourK') {
-                newResolution = { type: 'FourK', width: 3840, height: 2160 }
-              } else {
-                // Keep existing custom values or default to HD resolution
-                const currentWidth = config.monitor1.resolution.width || 1920
-                const currentHeight = config.monitor1.resolution.height || 1080
-                newResolution = { type: 'Custom', width: currentWidth, height: currentHeight }
-              }
-              saveConfig({
-                ...config,
-                monitor1: { ...config.monitor1, resolution: newResolution }
-              })
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, bank_channel: channel || null } })
             }}
-          >
-            <option value="HD">HD (1920×1080)</option>
-            <option value="FourK">4K (3840×2160)</option>
-            <option value="Custom">Custom</option>
-          </select>
+          />
         </label>
         
-        {config.monitor1.resolution.type === 'Custom' && (
-          <>
-            <label>
-              Width:
-              <input
-                type="number"
-                min="1"
-                max="7680"
-                value={config.monitor1.resolution.width}
-                onChange={(e) => saveConfig({
-                  ...config,
-                  monitor1: {
-                    ...config.monitor1,
-                    resolution: {
-                      type: 'Custom',
-                      width: parseInt(e.target.value) || 1920,
-                      height: config.monitor1.resolution.height
-                    }
-                  }
-                })}
-              />
-            </label>
-            <label>
-              Height:
-              <input
-                type="number"
-                min="1"
-                max="4320"
-                value={config.monitor1.resolution.height}
-                onChange={(e) => saveConfig({
-                  ...config,
-                  monitor1: {
-                    ...config.monitor1,
-                    resolution: {
-                      type: 'Custom',
-                      width: config.monitor1.resolution.width,
-                      height: parseInt(e.target.value) || 1080
-                    }
-                  }
-                })}
-              />
-            </label>
-          </>
-        )}
+        <label>
+          Preload Channel (buffers the clip it names, to select next; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.monitor2.preload_channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, preload_channel: channel || null } })
+            }}
+          />
+        </label>
         
         <label>
-          Orientation:
+          Dimming Curve:
           <select
-            value={config.monitor1.orientation}
+            value={config.monitor2.dimming_curve}
             onChange={(e) => saveConfig({
               ...config,
-              monitor1: { ...config.monitor1, orientation: e.target.value as 'Horizontal' | 'Vertical' }
+              monitor2: { ...config.monitor2, dimming_curve: e.target.value as DimmingCurve }
             })}
           >
-            <option value="Horizontal">Horizontal</option>
-            <option value="Vertical">Vertical</option>
+            <option value="Linear">Linear</option>
+            <option value="Gamma">Perceptual (gamma 2.2)</option>
+            <option value="Cie">Perceptual (CIE 1931)</option>
           </select>
         </label>
-      </div>
-
-      <div className="card">
-        <h3>
-          <EditableName 
-            name={config.monitor2.name}
-            onSave={(newName) => saveConfig({
+        
+        <label title="Eases stepped console dimmer fades; the output reaches about 63% of each change within this time">
+          Dimmer Smoothing (ms; 0 = off):
+          <input
+            type="number"
+            min="0"
+            max="5000"
+            step="10"
+            value={config.monitor2.dimmer_smoothing_ms}
+            onChange={(e) => saveConfig({
               ...config,
-              monitor2: { ...config.monitor2, name: newName }
+              monitor2: { ...config.monitor2, dimmer_smoothing_ms: Math.max(0, Math.min(5000, parseInt(e.target.value) || 0)) }
             })}
           />
-        </h3>
+        </label>
+        
         <label>
           <input
             type="checkbox"
-            checked={config.monitor2.enabled}
+            checked={config.monitor2.subtitles.enabled}
             onChange={(e) => saveConfig({
               ...config,
-              monitor2: { ...config.monitor2, enabled: e.target.checked }
+              monitor2: { ...config.monitor2, subtitles: { ...config.monitor2.subtitles, enabled: e.target.checked } }
             })}
           />
-          Enabled
+          Show subtitles from .srt / .vtt files
         </label>
         
         <label>
-          Start Channel:
+          Subtitle Channel (on at 50%; 0 = always on):
           <input
             type="number"
-            min="1"
-            max="510"
-            value={config.monitor2.start_channel}
-            onChange={(e) => saveConfig({
-              ...config,
-              monitor2: { ...config.monitor2, start_channel: parseInt(e.target.value) }
-            })}
+            min="0"
+            max="512"
+            value={config.monitor2.subtitles.channel ?? 0}
+            onChange={(e) => {
+              const channel = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              saveConfig({ ...config, monitor2: { ...config.monitor2, subtitles: { ...config.monitor2.subtitles, channel: channel || null } } })
+            }}
           />
         </label>
         
@@ -1250,6 +2747,9 @@ function ConfigSection({
             <button type="button" className="browse-button" onClick={browseFolderMonitor2}>
               Browse
             </button>
+            <button type="button" className="browse-button" onClick={() => openInExplorer(config.monitor2.media_folder)} disabled={!config.monitor2.media_folder}>
+              Open
+            </button>
           </div>
         </label>
         
@@ -1356,6 +2856,9 @@ function ConfigSection({
             <button type="button" className="browse-button" onClick={browseFolderPresentation}>
               Browse
             </button>
+            <button type="button" className="browse-button" onClick={() => openInExplorer(config.presentation_folder)} disabled={!config.presentation_folder}>
+              Open
+            </button>
           </div>
         </label>
         <p className="info">Files in this folder will appear in the Presentation panel for drag-and-drop playback.</p>
@@ -1375,14 +2878,285 @@ function ConfigSection({
             <button type="button" className="browse-button" onClick={browseFolderConvert}>
               Browse
             </button>
+            <button type="button" className="browse-button" onClick={() => openInExplorer(config.convert_folder)} disabled={!config.convert_folder}>
+              Open
+            </button>
           </div>
         </label>
         <p className="info">Converted video clips will be saved to this folder.</p>
+        <label>
+          Deinterlace:
+          <select
+            value={config.conversion.deinterlace}
+            onChange={(e) => saveConfig({ ...config, conversion: { ...config.conversion, deinterlace: e.target.value as Deinterlace } })}
+          >
+            <option value="Off">Off</option>
+            <option value="Yadif">yadif (fast)</option>
+            <option value="Bwdif">bwdif (best quality)</option>
+          </select>
+        </label>
+        <label>
+          Frame Rate:
+          <select
+            value={config.conversion.frame_rate ?? 0}
+            onChange={(e) => saveConfig({ ...config, conversion: { ...config.conversion, frame_rate: parseInt(e.target.value) || null } })}
+          >
+            <option value="0">Keep source</option>
+            <option value="24">24 fps</option>
+            <option value="25">25 fps</option>
+            <option value="30">30 fps</option>
+            <option value="50">50 fps</option>
+            <option value="60">60 fps</option>
+          </select>
+        </label>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.conversion.interpolate}
+            disabled={!config.conversion.frame_rate}
+            onChange={(e) => saveConfig({ ...config, conversion: { ...config.conversion, interpolate: e.target.checked } })}
+          />
+          Motion-interpolate frame rate changes (smoother, much slower)
+        </label>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.conversion.tone_map}
+            onChange={(e) => saveConfig({ ...config, conversion: { ...config.conversion, tone_map: e.target.checked } })}
+          />
+          Tone-map HDR (HDR10 / HLG) sources to SDR
+        </label>
+        <p className="info">Applied when clips are converted on import and when splitting. Deinterlacing only touches frames flagged as interlaced.</p>
+      </div>
+
+      <div className="card">
+        <h3>Cue Stack</h3>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.cue_stack.enabled}
+            onChange={(e) => saveConfig({ ...config, cue_stack: { ...config.cue_stack, enabled: e.target.checked } })}
+          />
+          Enable cue stack
+        </label>
+        <label>
+          Go Channel (0 = none):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={config.cue_stack.go_channel}
+            onChange={(e) => saveConfig({
+              ...config,
+              cue_stack: { ...config.cue_stack, go_channel: Math.max(0, Math.min(512, parseInt(e.target.value) || 0)) }
+            })}
+          />
+        </label>
+        <p className="info">Raising the Go channel past 50% runs the next cue. Each monitor column is clip / dimmer / playtype; untick it to leave that monitor unchanged.</p>
+        <table style={{ fontSize: '13px', width: '100%' }}>
+          <thead>
+            <tr>
+              <th></th>
+              <th>Name</th>
+              <th>{config.monitor1.name}</th>
+              <th>{config.monitor2.name}</th>
+              <th>Fade out / in (ms)</th>
+              <th></th>
+            </tr>
+          </thead>
+          <tbody>
+            {config.cue_stack.cues.map((cue, i) => (
+              <tr key={i} style={{ background: cueState?.current === i ? '#1e3a1e' : undefined }}>
+                <td>{i + 1}</td>
+                <td>
+                  <input type="text" value={cue.name} onChange={(e) => updateCue(i, { ...cue, name: e.target.value })} />
+                </td>
+                {lookInputs(cue, i, 'monitor1')}
+                {lookInputs(cue, i, 'monitor2')}
+                <td>
+                  <input
+                    type="number"
+                    min="0"
+                    style={{ width: '70px' }}
+                    value={cue.fade_out_ms}
+                    onChange={(e) => updateCue(i, { ...cue, fade_out_ms: Math.max(0, parseInt(e.target.value) || 0) })}
+                  />
+                  <input
+                    type="number"
+                    min="0"
+                    style={{ width: '70px' }}
+                    value={cue.fade_in_ms}
+                    onChange={(e) => updateCue(i, { ...cue, fade_in_ms: Math.max(0, parseInt(e.target.value) || 0) })}
+                  />
+                </td>
+                <td>
+                  <button type="button" className="browse-button" onClick={() => saveCues(config.cue_stack.cues.filter((_, j) => j !== i))}>
+                    Delete
+                  </button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+        <div className="folder-input-group" style={{ marginTop: '8px' }}>
+          <button type="button" className="browse-button" onClick={addCue}>Add Cue</button>
+          <button type="button" className="browse-button" onClick={cueGo} disabled={!config.cue_stack.enabled || cueState?.running}>GO</button>
+          <button type="button" className="browse-button" onClick={() => invoke('cue_reset')}>Reset</button>
+        </div>
+        <p className="info">
+          {cueState?.current != null ? `Current: ${cueState.current + 1} ${cueState.current_name ?? ''}` : 'Before first cue'}
+        </p>
+      </div>
+
+      <div className="card">
+        <h3>Timeline</h3>
+        <p className="info">
+          {config.timeline.events.length} event(s). Events are set in configuration.json under <code>timeline</code>.
+        </p>
+        <div className="folder-input-group">
+          <button type="button" className="browse-button" onClick={() => invoke('timeline_play')} disabled={timelineState?.playing}>Play</button>
+          <button type="button" className="browse-button" onClick={() => invoke('timeline_pause')} disabled={!timelineState?.playing}>Pause</button>
+          <button type="button" className="browse-button" onClick={() => invoke('timeline_locate', { positionSecs: 0 })}>Back to Start</button>
+        </div>
+        <p className="info">Position: {(timelineState?.position_secs ?? 0).toFixed(1)}s</p>
+      </div>
+
+      <div className="card">
+        <h3>Startup</h3>
+        <label>
+          <input
+            type="checkbox"
+            checked={autostart}
+            onChange={(e) => toggleAutostart(e.target.checked)}
+          />
+          Launch at logon in production mode
+        </label>
+        <p className="info">Registers a Windows scheduled task. Combine with automatic logon for unattended installs.</p>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.kiosk_mode}
+            onChange={(e) => saveConfig({ ...config, kiosk_mode: e.target.checked })}
+          />
+          Kiosk mode
+        </label>
+        <p className="info">Blocks sleep and notifications, keeps outputs on top, reopens closed outputs and prevents closing the app. Turn off to quit.</p>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.output_click_through}
+            onChange={(e) => saveConfig({ ...config, output_click_through: e.target.checked })}
+          />
+          Click-through outputs
+        </label>
+        <p className="info">Output windows ignore the mouse so stray clicks can't focus or move them. Untick to position them during setup.</p>
+        <div className="folder-input-group">
+          <button type="button" className="browse-button" onClick={() => toggleSetupWindows(!setupWindows)}>
+            {setupWindows ? 'Back to Production Geometry' : 'Windowed Setup'}
+          </button>
+        </div>
+        <p className="info">Shows open outputs as ordinary half-size windows for bench setup and content checks. Their saved positions are kept.</p>
+      </div>
+
+      <div className="card">
+        <h3>Updates</h3>
+        <label>
+          Channel:
+          <select
+            value={config.updates.channel}
+            onChange={(e) => saveConfig({
+              ...config,
+              updates: { ...config.updates, channel: e.target.value as 'Stable' | 'Beta' }
+            })}
+          >
+            <option value="Stable">Stable</option>
+            <option value="Beta">Beta</option>
+          </select>
+        </label>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.updates.check_on_startup}
+            onChange={(e) => saveConfig({ ...config, updates: { ...config.updates, check_on_startup: e.target.checked } })}
+          />
+          Check for updates at startup
+        </label>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.updates.auto_install}
+            onChange={(e) => saveConfig({ ...config, updates: { ...config.updates, auto_install: e.target.checked } })}
+          />
+          Install updates automatically
+        </label>
+        <label>
+          <input
+            type="checkbox"
+            checked={config.updates.defer}
+            onChange={(e) => saveConfig({ ...config, updates: { ...config.updates, defer: e.target.checked } })}
+          />
+          Defer updates (show period)
+        </label>
+        <p className="info">Updates are never installed while production is running.</p>
+        <div className="folder-input-group">
+          <button type="button" className="browse-button" onClick={checkForUpdates}>
+            Check Now
+          </button>
+          {availableUpdate && (
+            <button type="button" className="browse-button" onClick={installUpdate} disabled={config.updates.defer}>
+              Install {availableUpdate.version}
+            </button>
+          )}
+        </div>
+        {updateStatus && <p className="info">{updateStatus}</p>}
       </div>
     </div>
   )
 }
 
+// Moves a monitor's fixed functions off the start channel layout, one
+// channel each; 0 puts a function back in the layout. Overlaps are refused
+// when the configuration is saved.
+function ChannelPatch({
+  monitor,
+  onChange
+}: {
+  monitor: MonitorConfig
+  onChange: (channelMap: ChannelMap) => void
+}) {
+  const functions: [keyof ChannelMap, string, number][] = [
+    ['clip', 'Clip', getClipChannel(monitor)],
+    ['clip_fine', 'Clip fine', getClipFineChannel(monitor)],
+    ['dimmer', 'Dimmer', getDimmerChannel(monitor)],
+    ['playtype', 'Playtype', getPlaytypeChannel(monitor)],
+    ['volume', 'Volume', getVolumeChannel(monitor)],
+    ['pan', 'Pan', getPanChannel(monitor)],
+    ['zoom', 'Zoom', getZoomChannel(monitor)],
+    ['speed', 'Speed', getSpeedChannel(monitor)],
+  ]
+  
+  return (
+    <details>
+      <summary>Channel patch</summary>
+      {functions.map(([key, label, channel]) => (
+        <label key={key}>
+          {label} (Ch {channel}; 0 = layout):
+          <input
+            type="number"
+            min="0"
+            max="512"
+            value={monitor.channel_map[key] ?? 0}
+            onChange={(e) => {
+              const patched = Math.max(0, Math.min(512, parseInt(e.target.value) || 0))
+              onChange({ ...monitor.channel_map, [key]: patched || null })
+            }}
+          />
+        </label>
+      ))}
+    </details>
+  )
+}
+
 // Layout Section Component
 function LayoutSection({ config, saveConfig }: { config: AppConfig, saveConfig: (cfg: AppConfig) => void }) {
   const isStacked = config.layout === 'HorizontalStacked' || config.layout === 'VerticalStacked';
@@ -1730,7 +3504,8 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
 
   const loadMediaFiles = async (folder: string, setter: (files: string[]) => void) => {
     try {
-      const files = await invoke<string[]>('get_media_files', { folder })
+      const entries = await invoke<MediaEntry[]>('list_media', { folder })
+      const files = entries.map(e => e.name)
       console.log('Loaded media files from', folder, ':', files)
       setter(files)
     } catch (err) {
@@ -2031,45 +3806,46 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
       }
 
       // Listen for DMX update events
-      unlistenFn = await listen('dmx-update', (event: any) => {
-        const update = event.payload as DmxUpdate
-        console.log('DMX Update - Ch:', update.channel, 'Val:', update.value)
-
-        // Check Monitor 1 channels
-        const m1ClipCh = config.monitor1.start_channel
-        const m1DimmerCh = config.monitor1.start_channel + 1
-        const m1ModeCh = config.monitor1.start_channel + 2
-
-        // Check Monitor 2 channels
-        const m2ClipCh = config.monitor2.start_channel
-        const m2DimmerCh = config.monitor2.start_channel + 1
-        const m2ModeCh = config.monitor2.start_channel + 2
-
-        // Update Monitor 1
-        if (config.monitor1.enabled) {
-          if (update.channel === m1ClipCh) {
-            console.log('Monitor 1 Video:', update.value)
-            setMonitor1Video(update.value)
-          } else if (update.channel === m1DimmerCh) {
-            console.log('Monitor 1 Dimmer:', update.value)
-            setMonitor1Dimmer(update.value)
-          } else if (update.channel === m1ModeCh) {
-            console.log('Monitor 1 Mode:', update.value)
-            setMonitor1Mode(update.value)
+      unlistenFn = await listen<DmxUpdate>('dmx-update', (event) => {
+        for (const [channel, value] of event.payload.channels) {
+          console.log('DMX Update - Ch:', channel, 'Val:', value)
+
+          // Check Monitor 1 channels
+          const m1ClipCh = getClipChannel(config.monitor1)
+          const m1DimmerCh = getDimmerChannel(config.monitor1)
+          const m1ModeCh = getPlaytypeChannel(config.monitor1)
+
+          // Check Monitor 2 channels
+          const m2ClipCh = getClipChannel(config.monitor2)
+          const m2DimmerCh = getDimmerChannel(config.monitor2)
+          const m2ModeCh = getPlaytypeChannel(config.monitor2)
+
+          // Update Monitor 1
+          if (config.monitor1.enabled) {
+            if (channel === m1ClipCh) {
+              console.log('Monitor 1 Video:', value)
+              setMonitor1Video(value)
+            } else if (channel === m1DimmerCh) {
+              console.log('Monitor 1 Dimmer:', value)
+              setMonitor1Dimmer(value)
+            } else if (channel === m1ModeCh) {
+              console.log('Monitor 1 Mode:', value)
+              setMonitor1Mode(value)
+            }
           }
-        }
 
-        // Update Monitor 2
-        if (config.monitor2.enabled) {
-          if (update.channel === m2ClipCh) {
-            console.log('Monitor 2 Video:', update.value)
-            setMonitor2Video(update.value)
-          } else if (update.channel === m2DimmerCh) {
-            console.log('Monitor 2 Dimmer:', update.value)
-            setMonitor2Dimmer(update.value)
-          } else if (update.channel === m2ModeCh) {
-            console.log('Monitor 2 Mode:', update.value)
-            setMonitor2Mode(update.value)
+          // Update Monitor 2
+          if (config.monitor2.enabled) {
+            if (channel === m2ClipCh) {
+              console.log('Monitor 2 Video:', value)
+              setMonitor2Video(value)
+            } else if (channel === m2DimmerCh) {
+              console.log('Monitor 2 Dimmer:', value)
+              setMonitor2Dimmer(value)
+            } else if (channel === m2ModeCh) {
+              console.log('Monitor 2 Mode:', value)
+              setMonitor2Mode(value)
+            }
           }
         }
       })
@@ -2088,8 +3864,8 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
       // modes calling start_sacn_listener and would kill the new listener.
       // stop_sacn_listener is called only from explicit user-initiated actions.
     }
-  }, [config.preview, config.monitor1.enabled, config.monitor1.start_channel, 
-      config.monitor2.enabled, config.monitor2.start_channel])
+  }, [config.preview, config.monitor1.enabled, config.monitor1.start_channel, config.monitor1.channel_map, 
+      config.monitor2.enabled, config.monitor2.start_channel, config.monitor2.channel_map])
 
   return (
     <div className="section">
@@ -2153,7 +3929,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor1.start_channel}</span>
+                  <span className="channel-label">Ch {getClipChannel(config.monitor1)}</span>
                 </div>
                 
                 <div className="vertical-slider-control">
@@ -2187,7 +3963,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor1.start_channel + 1}</span>
+                  <span className="channel-label">Ch {getDimmerChannel(config.monitor1)}</span>
                 </div>
                 
                 <div className="vertical-slider-control">
@@ -2221,7 +3997,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor1.start_channel + 2}</span>
+                  <span className="channel-label">Ch {getPlaytypeChannel(config.monitor1)}</span>
                 </div>
               </div>
             </div>
@@ -2262,7 +4038,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor2.start_channel}</span>
+                  <span className="channel-label">Ch {getClipChannel(config.monitor2)}</span>
                 </div>
                 
                 <div className="vertical-slider-control">
@@ -2296,7 +4072,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor2.start_channel + 1}</span>
+                  <span className="channel-label">Ch {getDimmerChannel(config.monitor2)}</span>
                 </div>
                 
                 <div className="vertical-slider-control">
@@ -2330,7 +4106,7 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
                       }}
                     />
                   </div>
-                  <span className="channel-label">Ch {config.monitor2.start_channel + 2}</span>
+                  <span className="channel-label">Ch {getPlaytypeChannel(config.monitor2)}</span>
                 </div>
               </div>
             </div>
@@ -2488,27 +4264,27 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
         {/* DMX Channel Display */}
         <div className="dmx-channel-display">
           <DmxChannelIndicator 
-            channel={config.monitor1.start_channel} 
+            channel={getClipChannel(config.monitor1)} 
             value={monitor1Video}
           />
           <DmxChannelIndicator 
-            channel={config.monitor1.start_channel + 1} 
+            channel={getDimmerChannel(config.monitor1)} 
             value={monitor1Dimmer}
           />
           <DmxChannelIndicator 
-            channel={config.monitor1.start_channel + 2} 
+            channel={getPlaytypeChannel(config.monitor1)} 
             value={monitor1Mode}
           />
           <DmxChannelIndicator 
-            channel={config.monitor2.start_channel} 
+            channel={getClipChannel(config.monitor2)} 
             value={monitor2Video}
           />
           <DmxChannelIndicator 
-            channel={config.monitor2.start_channel + 1} 
+            channel={getDimmerChannel(config.monitor2)} 
             value={monitor2Dimmer}
           />
           <DmxChannelIndicator 
-            channel={config.monitor2.start_channel + 2} 
+            channel={getPlaytypeChannel(config.monitor2)} 
             value={monitor2Mode}
           />
         </div>
@@ -2519,9 +4295,9 @@ function PreviewSection({ config, saveConfig }: { config: AppConfig, saveConfig:
         <div className="card">
           <PreviewTestPanel 
             universe={config.sacn.universe} 
-            monitor1Start={config.monitor1.start_channel}
+            monitor1Channels={[getClipChannel(config.monitor1), getDimmerChannel(config.monitor1), getPlaytypeChannel(config.monitor1)]}
             monitor1Name={config.monitor1.name}
-            monitor2Start={config.monitor2.start_channel}
+            monitor2Channels={[getClipChannel(config.monitor2), getDimmerChannel(config.monitor2), getPlaytypeChannel(config.monitor2)]}
             monitor2Name={config.monitor2.name}
             onValuesChange={(m1Video, m1Dimmer, m1Mode, m2Video, m2Dimmer, m2Mode) => {
               setMonitor1Video(m1Video)
@@ -2565,8 +4341,8 @@ function PresentationSection({ config }: { config: AppConfig }) {
   // Load media files when folder changes
   useEffect(() => {
     if (config.presentation_folder) {
-      invoke<string[]>('get_media_files', { folder: config.presentation_folder })
-        .then(files => setMediaFiles(files))
+      invoke<MediaEntry[]>('list_media', { folder: config.presentation_folder })
+        .then(entries => setMediaFiles(entries.map(e => e.name)))
         .catch(() => setMediaFiles([]))
     } else {
       setMediaFiles([])
@@ -2904,7 +4680,7 @@ export default App
 function ToolsSection({ config }: { config: AppConfig }) {
   const [files, setFiles] = useState<string[]>([])
   const [selectedFile, setSelectedFile] = useState<string | null>(null)
-  const [probe, setProbe] = useState<{ w: number; h: number } | null>(null)
+  const [probe, setProbe] = useState<{ w: number; h: number; hdr: boolean; transfer: string | null } | null>(null)
   const [probeError, setProbeError] = useState('')
   const [ffmpegOk, setFfmpegOk] = useState<boolean | null>(null)
   const [ffmpegMsg, setFfmpegMsg] = useState('')
@@ -2935,8 +4711,8 @@ function ToolsSection({ config }: { config: AppConfig }) {
     setConvertError('')
     const fullPath = `${config.convert_folder}\\${name}`
     try {
-      const [w, h] = await invoke<[number, number]>('probe_media', { sourcePath: fullPath })
-      setProbe({ w, h })
+      const info = await invoke<MediaProbe>('probe_media', { sourcePath: fullPath })
+      setProbe({ w: info.width, h: info.height, hdr: info.hdr, transfer: info.color_transfer })
     } catch (err: any) {
       setProbeError(String(err))
     }
@@ -3059,6 +4835,11 @@ function ToolsSection({ config }: { config: AppConfig }) {
                   {isSupported
                     ? <span style={{ marginLeft: '10px', color: '#5f5', fontSize: '13px' }}>✓ 1080×3840 — ready to split</span>
                     : <span style={{ marginLeft: '10px', color: '#f88', fontSize: '13px' }}>✗ Must be 1080×3840 to split</span>}
+                  {probe.hdr && (
+                    <div style={{ marginTop: '6px', fontSize: '13px', color: config.conversion.tone_map ? '#aaa' : '#fc6' }}>
+                      HDR source ({probe.transfer === 'arib-std-b67' ? 'HLG' : 'PQ'}){config.conversion.tone_map ? ' — will be tone-mapped to SDR' : ' — tone-mapping is off; it will look washed out'}
+                    </div>
+                  )}
                 </div>
               ) : probeError ? (
                 <p style={{ color: '#f88', fontSize: '13px', marginBottom: '14px' }}>{probeError}</p>
@@ -3125,3 +4906,4 @@ function ToolsSection({ config }: { config: AppConfig }) {
     </div>
   )
 }
+
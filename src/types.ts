@@ -7,14 +7,56 @@ export type Resolution =
 
 export type Orientation = 'Horizontal' | 'Vertical';
 
+export type DpiSizing = 'Physical' | 'Logical';
+export type DimmingCurve = 'Linear' | 'Gamma' | 'Cie';
+
+export type GpuPreference =
+  | { type: 'Default' }
+  | { type: 'HighPerformance' }
+  | { type: 'LowPower' }
+  | { type: 'Adapter', luid: string };
+
 export type SacnMode = 'Multicast' | 'Unicast';
 
+export type MergeMode = 'Htp' | 'Ltp';
+
+export type TerminationAction = 'Hold' | 'Blackout';
+
+export type DataLossAction = 'Hold' | 'FadeToBlack' | 'FallbackClip';
+
 export interface SacnConfig {
   universe: number;
   mode: SacnMode;
   ip_address: string;
   unicast_ip: string;
   network_interface: string;
+  extra_inputs: string[];
+  artnet_universe: number;
+  merge_mode: MergeMode;
+  on_termination: TerminationAction;
+  on_data_loss: DataLossAction;
+  data_loss_fade_secs: number;
+  allowed_sources: string[];
+  failover_input: string;
+  failover_timeout_secs: number;
+  clip_stable_ms: number;
+  clip_stable_frames: number;
+}
+
+export type Generator =
+  | { type: 'SolidColor'; start_channel: number }
+  | { type: 'Strobe'; start_channel: number }
+  | { type: 'Gradient'; start_channel: number }
+  | { type: 'Noise'; start_channel: number };
+
+export interface GeneratorClip {
+  clip: number;
+  generator: Generator;
+}
+
+export interface SubtitleConfig {
+  enabled: boolean;
+  channel: number | null;
 }
 
 export interface MonitorConfig {
@@ -27,6 +69,56 @@ export interface MonitorConfig {
   display_index: number;
   window_x: number | null;
   window_y: number | null;
+  fallback_clip: number | null;
+  fallback_timeout_secs: number;
+  dpi_sizing: DpiSizing;
+  gpu: GpuPreference;
+  channel_map: ChannelMap;
+  clip_16bit: boolean;
+  volume_control: boolean;
+  ken_burns: boolean;
+  slideshow_interval_secs: number;
+  slideshow_crossfade_ms: number;
+  generators: GeneratorClip[];
+  pixel_map: PixelMapConfig;
+  spout: SpoutConfig;
+  effects_channel: number | null;
+  crossfade_channel: number | null;
+  clip_crossfade_ms: number;
+  preload_channel: number | null;
+  position_channel: number | null;
+  rotation_channel: number | null;
+  tint_channel: number | null;
+  strobe_channel: number | null;
+  bank_channel: number | null;
+  subtitles: SubtitleConfig;
+  dimming_curve: DimmingCurve;
+  dimmer_smoothing_ms: number;
+  chase_timecode: boolean;
+  timecode_offset_secs: number;
+}
+
+export interface PixelRegion {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  pixels: number;
+  universe: number;
+  start_channel: number;
+}
+
+export interface PixelMapConfig {
+  enabled: boolean;
+  fps: number;
+  priority: number;
+  regions: PixelRegion[];
+}
+
+export interface SpoutConfig {
+  enabled: boolean;
+  sender_name: string;
+  fps: number;
 }
 
 export interface DisplayInfo {
@@ -35,6 +127,18 @@ export interface DisplayInfo {
   is_primary: boolean;
   width: number;
   height: number;
+  scale_factor: number;
+}
+
+export interface CaptureDevice {
+  name: string;
+}
+
+export interface GpuAdapter {
+  name: string;
+  luid: string;
+  vendor_id: number;
+  dedicated_memory_mb: number;
 }
 
 export type LayoutMode = 
@@ -45,6 +149,69 @@ export type LayoutMode =
 
 export type PreviewMode = 'Listen' | 'Test';
 
+export interface HotkeyConfig {
+  blackout: string;
+  freeze: string;
+  restore: string;
+}
+
+export type PanicAction = 'Blackout' | 'Freeze' | 'Restore';
+
+export interface FeedbackConfig {
+  enabled: boolean;
+  universe: number;
+  start_channel: number;
+  priority: number;
+}
+
+export type PlaybackProfile = 'Standard' | 'Lightweight';
+
+export type OutputPlacement = 'Auto' | 'Coordinates' | 'FullscreenOnOutput';
+
+export interface AudioFixtureConfig {
+  enabled: boolean;
+  start_channel: number;
+  media_folder: string;
+  output_device: string;
+}
+
+export type AudioReactiveMode = 'Dimmer' | 'AdvanceClip';
+
+export interface AudioReactiveConfig {
+  enabled: boolean;
+  input_device: string;
+  monitor_id: string;
+  mode: AudioReactiveMode;
+  sensitivity: number;
+  min_dimmer: number;
+  first_clip: number;
+  last_clip: number;
+}
+
+export type TriggerProtocol = 'Osc' | 'Udp';
+
+export interface TriggerOutputConfig {
+  enabled: boolean;
+  protocol: TriggerProtocol;
+  target: string;
+  osc_address: string;
+  udp_message: string;
+}
+
+export interface ClipFinished {
+  monitor_id: string;
+  clip: number | null;
+  media_url: string;
+}
+
+export type FolderPurpose = 'Media' | 'Convert' | 'Presentation';
+
+export interface LastFolders {
+  media: string | null;
+  convert: string | null;
+  presentation: string | null;
+}
+
 export interface AppConfig {
   sacn: SacnConfig;
   monitor1: MonitorConfig;
@@ -54,9 +221,39 @@ export interface AppConfig {
   production_mode: boolean;
   presentation_folder: string;
   convert_folder: string;
+  conversion: ConversionConfig;
+  hotkeys: HotkeyConfig;
+  feedback: FeedbackConfig;
+  playback_profile: PlaybackProfile;
+  kiosk_mode: boolean;
+  output_click_through: boolean;
+  output_placement: OutputPlacement;
+  audio_fixture: AudioFixtureConfig;
+  clip_finished_trigger: TriggerOutputConfig;
+  last_folders: LastFolders;
+  updates: UpdateConfig;
+  cue_stack: CueStackConfig;
+  audio_reactive: AudioReactiveConfig;
+  timeline: TimelineConfig;
+  macros: MacroConfig;
+  scripting: ScriptingConfig;
+  osc_input: OscInputConfig;
+  midi_input: MidiInputConfig;
+  http_api: HttpApiConfig;
+  mqtt: MqttConfig;
+  timecode: TimecodeConfig;
+  low_disk_space_mb: number;
 }
 
-export type MediaType = 'Video' | 'Image';
+export interface DiskSpace {
+  purpose: string;
+  folder: string;
+  free_bytes: number;
+  total_bytes: number;
+  low: boolean;
+}
+
+export type MediaType = 'Video' | 'Image' | 'Audio' | 'Web' | 'Capture' | 'Stream' | 'Plugin';
 
 export interface MediaFile {
   dmx_value: number;
@@ -65,10 +262,67 @@ export interface MediaFile {
   media_type: MediaType;
 }
 
+export interface MediaEntry {
+  dmx_value: number;
+  name: string;
+  path: string;
+  media_type: MediaType | null;
+  size: number;
+  duration: number | null;
+  warnings: string[];
+}
+
+// Levels from one packet: [channel, value] pairs
 export interface DmxUpdate {
   universe: number;
-  channel: number;
-  value: number;
+  channels: [number, number][];
+}
+
+// Emitted as 'playback-state' when a monitor's clip, dimmer or playtype changes
+// Decoded playtype channel (see PlayType in config.rs for the DMX ranges)
+export type PlayType = 'Loop' | 'Pause' | 'Restart' | 'FreezeLastFrame' | 'PlayOnce' | 'Slideshow';
+
+export interface PlaybackState {
+  monitor_id: string;
+  clip: number;
+  close_output: boolean;
+  dimmer: number;
+  playtype: number;
+  play_type: PlayType;
+  volume: number | null;
+  bank: number | null;
+  crossfade_ms: number | null;
+  transition_ms: number;
+  position: { x: number; y: number; scale: number } | null;
+  rotation: number | null;
+  tint: { r: number; g: number; b: number } | null;
+  strobe: number | null;
+  media_file: string | null;
+  media_url: string | null;
+  preload: number | null;
+  preload_url: string | null;
+}
+
+export interface FallbackEvent {
+  monitor_id: string;
+  clip: number | null;
+}
+
+export type PlaybackHealth = 'Playing' | 'Idle' | 'Error' | 'NoOutput';
+
+export interface MonitorPlayback {
+  monitor_id: string;
+  clip: number;
+  dimmer: number;
+  playtype: number;
+  position: number;
+  duration: number;
+  paused: boolean;
+  health: PlaybackHealth;
+}
+
+export interface LaunchOptions {
+  production: boolean;
 }
 
 export interface NetworkInterface {
@@ -77,14 +331,337 @@ export interface NetworkInterface {
 }
 
 // Helper functions for MonitorConfig
+// Per-function channel patch; null follows the start channel layout
+export interface ChannelMap {
+  clip: number | null;
+  clip_fine: number | null;
+  dimmer: number | null;
+  playtype: number | null;
+  volume: number | null;
+  pan: number | null;
+  zoom: number | null;
+  speed: number | null;
+}
+
+// Where the dimmer sits in the start channel layout: with 16-bit clip
+// selection the clip takes two channels and the rest move up
+function layoutDimmer(monitor: MonitorConfig): number {
+  return monitor.start_channel + (monitor.clip_16bit ? 2 : 1);
+}
+
+// Ken Burns channels follow the volume channel when it is on
+function layoutPan(monitor: MonitorConfig): number {
+  return layoutDimmer(monitor) + (monitor.volume_control ? 3 : 2);
+}
+
 export function getClipChannel(monitor: MonitorConfig): number {
-  return monitor.start_channel;
+  return monitor.channel_map.clip ?? monitor.start_channel;
+}
+
+export function getClipFineChannel(monitor: MonitorConfig): number {
+  return monitor.channel_map.clip_fine ?? monitor.start_channel + 1;
 }
 
 export function getDimmerChannel(monitor: MonitorConfig): number {
-  return monitor.start_channel + 1;
+  return monitor.channel_map.dimmer ?? layoutDimmer(monitor);
 }
 
 export function getPlaytypeChannel(monitor: MonitorConfig): number {
-  return monitor.start_channel + 2;
+  return monitor.channel_map.playtype ?? layoutDimmer(monitor) + 1;
+}
+
+export function getVolumeChannel(monitor: MonitorConfig): number {
+  return monitor.channel_map.volume ?? layoutDimmer(monitor) + 2;
+}
+
+export function getPanChannel(monitor: MonitorConfig): number {
+  return monitor.channel_map.pan ?? layoutPan(monitor);
+}
+
+export function getZoomChannel(monitor: MonitorConfig): number {
+  return monitor.channel_map.zoom ?? layoutPan(monitor) + 1;
+}
+
+export function getSpeedChannel(monitor: MonitorConfig): number {
+  return monitor.channel_map.speed ?? layoutPan(monitor) + 2;
+}
+
+export interface AppInfo {
+  version: string;
+  build_timestamp: number;
+  os: string;
+  ffmpeg_version: string | null;
+  sacn_version: string;
+}
+
+export type UpdateChannel = 'Stable' | 'Beta';
+
+export interface UpdateConfig {
+  channel: UpdateChannel;
+  check_on_startup: boolean;
+  auto_install: boolean;
+  defer: boolean;
+}
+
+export interface UpdateInfo {
+  current_version: string;
+  version: string;
+  notes: string | null;
+}
+
+export interface SelfTestStep {
+  name: string;
+  passed: boolean;
+  detail: string;
+}
+
+export interface SelfTestReport {
+  passed: boolean;
+  steps: SelfTestStep[];
+}
+
+export interface CueLook {
+  // Up to 65535 on a monitor with 16-bit clip selection
+  clip: number;
+  dimmer: number;
+  playtype: number;
+}
+
+export interface Cue {
+  name: string;
+  monitor1: CueLook | null;
+  monitor2: CueLook | null;
+  fade_out_ms: number;
+  fade_in_ms: number;
+}
+
+export interface CueStackConfig {
+  enabled: boolean;
+  go_channel: number;
+  cues: Cue[];
+}
+
+export interface CueState {
+  current: number | null;
+  current_name: string | null;
+  running: boolean;
+}
+
+export interface TimelineEvent {
+  at_secs: number;
+  monitor_id: string;
+  look: CueLook;
+}
+
+export interface TimelineConfig {
+  events: TimelineEvent[];
+  loop_at_secs: number | null;
+}
+
+export type MacroAction =
+  | { SelectClip: { monitor_id: string; clip: number } }
+  | { SetDimmer: { monitor_id: string; dimmer: number } }
+  | { Wait: { ms: number } }
+  | { SendOsc: { target: string; address: string; args: number[] } };
+
+export interface Macro {
+  name: string;
+  value: number;
+  actions: MacroAction[];
+}
+
+export interface MacroConfig {
+  enabled: boolean;
+  control_channel: number;
+  macros: Macro[];
+}
+
+export type Deinterlace = 'Off' | 'Yadif' | 'Bwdif';
+
+export interface ConversionConfig {
+  deinterlace: Deinterlace;
+  frame_rate: number | null;
+  interpolate: boolean;
+  tone_map: boolean;
+}
+
+export interface MediaProbe {
+  width: number;
+  height: number;
+  color_transfer: string | null;
+  hdr: boolean;
+}
+
+export interface ScriptingConfig {
+  enabled: boolean;
+  script_path: string;
+  signal_loss_secs: number;
+}
+
+export interface OscInputConfig {
+  enabled: boolean;
+  port: number;
+  clip_address: string;
+  dimmer_address: string;
+  playtype_address: string;
+  blackout_address: string;
+}
+
+export interface HttpApiConfig {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+export interface MqttConfig {
+  enabled: boolean;
+  host: string;
+  port: number;
+  client_id: string;
+  username: string;
+  password: string;
+  status_topic: string;
+  command_topic: string;
+}
+
+export type TimecodeSource = 'ArtNet' | 'Midi';
+
+export interface TimecodeConfig {
+  enabled: boolean;
+  source: TimecodeSource;
+  midi_device: string;
+  drive_timeline: boolean;
+  max_drift_ms: number;
+}
+
+export interface MidiInputConfig {
+  enabled: boolean;
+  device: string;
+  msc_device_id: number;
+  monitor1: MidiMapping;
+  monitor2: MidiMapping;
+}
+
+export interface MidiMapping {
+  channel: number;
+  clip_offset: number;
+  dimmer_cc: number | null;
+  playtype_cc: number | null;
+  msc_cue_list: number | null;
+  msc_stop_playtype: number;
+  msc_resume_playtype: number;
+}
+
+export interface TimelineState {
+  playing: boolean;
+  position_secs: number;
+}
+
+export interface SacnSource {
+  cid: string;
+  name: string;
+  ip: string;
+  priority: number;
+}
+
+export type SourceWarningKind = 'EqualPriority' | 'HigherPriority';
+
+export interface SourceWarning {
+  kind: SourceWarningKind;
+  universe: number;
+  source: SacnSource;
+  other: SacnSource;
+  message: string;
+}
+
+export interface SourceConflict {
+  universe: number;
+  sources: SacnSource[];
+  channels: number[];
+  message: string;
+}
+
+export interface AvailableInput {
+  name: string;
+  running: boolean;
+}
+
+export interface FailoverEvent {
+  active: string;
+  on_backup: boolean;
+  message: string;
+}
+
+export interface InterfaceError {
+  interface: string;
+  message: string;
+}
+
+export interface InterfaceChanged {
+  interface: string;
+  old_ip: string;
+  new_ip: string;
+  message: string;
+}
+
+export interface SignalLossEvent {
+  universe: number;
+  action: DataLossAction;
+}
+
+export interface ActiveSource extends SacnSource {
+  packets_per_sec: number;
+  secs_since_seen: number;
+  packets: number;
+}
+
+export interface SourceTerminated {
+  universe: number;
+  source: SacnSource;
+  remaining: number;
+  action: TerminationAction;
+  message: string;
+}
+
+export interface ListenerSnapshot {
+  listening: boolean;
+  universe: number;
+  mode: SacnMode;
+  secs_since_last_packet: number | null;
+}
+
+export interface DiscoveredSource {
+  cid: string;
+  name: string;
+  ip: string;
+  universes: number[];
+  secs_since_seen: number;
+}
+
+export interface SacnStats {
+  packets: number;
+  out_of_sequence: number;
+  sequence_gaps: number;
+  rejected: number;
+  packets_per_sec: number;
+  secs_since_last_packet: number | null;
+  max_gap_ms: number;
+  jitter_ms: number;
+  sources: ActiveSource[];
+}
+
+export interface RecordingSummary {
+  path: string;
+  frames: number;
+  duration_secs: number;
+}
+
+export interface StateSnapshot {
+  taken_at: number;
+  version: string;
+  monitors: MonitorPlayback[];
+  listener: ListenerSnapshot;
+  dmx: number[];
+  cue: CueState;
+  timeline: TimelineState;
 }